@@ -1,17 +1,154 @@
 //! Job handler trait and registry.
 
-use queue_core::{Job, JobResult};
+use queue_core::{Job, JobLogLevel, JobLogLine, JobResult, JobTypeInfo};
+use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio_util::sync::CancellationToken;
 
 /// Result type for job handlers.
-pub type HandlerResult = Result<JobResult, String>;
+pub type HandlerResult = Result<JobResult, JobError>;
+
+/// Why a job handler failed, and whether running the job again could help.
+///
+/// Plain strings convert into [`JobError::Transient`], so handlers that
+/// return `Err("...".into())` keep being retried as before.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum JobError {
+    /// A failure that may clear up on its own, such as a timeout; retried.
+    #[error("{0}")]
+    Transient(String),
+    /// A failure retrying won't fix, such as invalid input; never retried.
+    #[error("{0}")]
+    Permanent(String),
+}
+
+impl JobError {
+    /// A failure worth retrying.
+    pub fn transient(message: impl Into<String>) -> Self {
+        JobError::Transient(message.into())
+    }
+
+    /// A failure that should fail the job immediately, whatever its retries.
+    pub fn permanent(message: impl Into<String>) -> Self {
+        JobError::Permanent(message.into())
+    }
+
+    /// Whether the job may be retried after this failure.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, JobError::Transient(_))
+    }
+
+    /// The failure message, as recorded on the job.
+    pub fn into_message(self) -> String {
+        match self {
+            JobError::Transient(message) | JobError::Permanent(message) => message,
+        }
+    }
+}
+
+impl From<String> for JobError {
+    fn from(message: String) -> Self {
+        JobError::Transient(message)
+    }
+}
+
+impl From<&str> for JobError {
+    fn from(message: &str) -> Self {
+        JobError::Transient(message.to_string())
+    }
+}
 
 /// Future type for async job handlers.
 pub type HandlerFuture = Pin<Box<dyn Future<Output = HandlerResult> + Send>>;
 
+/// Wrapper run around every handler call, for behavior like timing or logging.
+///
+/// It's given the job and the future of the next layer in, and returns a
+/// future that awaits it; whatever runs before and after that await runs
+/// before and after the handler.
+pub type Middleware = Arc<dyn Fn(&Job, HandlerFuture) -> HandlerFuture + Send + Sync>;
+
+/// Per-execution context handed to a job handler.
+///
+/// Lines logged through the context are stored with the job and archived into
+/// its history record. Clones share the same buffer, so the context can be
+/// moved into the handler's future.
+///
+/// When the job is cancelled while running, the context's cancellation token
+/// fires. Handlers that watch it can clean up and return early; handlers that
+/// don't are dropped once the worker's grace period runs out.
+#[derive(Debug, Clone, Default)]
+pub struct JobContext {
+    logs: Arc<Mutex<Vec<JobLogLine>>>,
+    cancel: CancellationToken,
+}
+
+impl JobContext {
+    /// Create a context with an empty log buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a context that observes the given cancellation token.
+    pub fn with_cancellation(cancel: CancellationToken) -> Self {
+        Self {
+            logs: Arc::default(),
+            cancel,
+        }
+    }
+
+    /// Token that fires when the job is cancelled.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    /// Check if the job has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Wait until the job is cancelled.
+    pub async fn cancelled(&self) {
+        self.cancel.cancelled().await;
+    }
+
+    /// Append a line to the job's log.
+    pub fn log(&self, level: JobLogLevel, message: impl Into<String>) {
+        self.logs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(JobLogLine::new(level, message));
+    }
+
+    /// Append a debug line to the job's log.
+    pub fn debug(&self, message: impl Into<String>) {
+        self.log(JobLogLevel::Debug, message);
+    }
+
+    /// Append an info line to the job's log.
+    pub fn info(&self, message: impl Into<String>) {
+        self.log(JobLogLevel::Info, message);
+    }
+
+    /// Append a warning line to the job's log.
+    pub fn warn(&self, message: impl Into<String>) {
+        self.log(JobLogLevel::Warn, message);
+    }
+
+    /// Append an error line to the job's log.
+    pub fn error(&self, message: impl Into<String>) {
+        self.log(JobLogLevel::Error, message);
+    }
+
+    /// Drain the lines logged so far.
+    pub fn take_logs(&self) -> Vec<JobLogLine> {
+        std::mem::take(&mut *self.logs.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
 /// Trait for job handlers.
 ///
 /// Implement this trait to define how jobs of a specific type are processed.
@@ -19,16 +156,40 @@ pub trait JobHandler: Send + Sync + 'static {
     /// The job type this handler processes.
     fn job_type(&self) -> &str;
 
-    /// Process a job and return the result.
-    fn handle(&self, job: &Job) -> HandlerFuture;
+    /// Process a job and return the result, logging through `ctx`.
+    fn handle(&self, job: &Job, ctx: JobContext) -> HandlerFuture;
+
+    /// JSON Schema that payloads for this job type must satisfy.
+    ///
+    /// Jobs whose payload doesn't match are rejected at enqueue time.
+    fn payload_schema(&self) -> Option<JsonValue> {
+        None
+    }
+
+    /// Example payload for this job type, offered in the admin UI's test fire.
+    fn sample_payload(&self) -> Option<JsonValue> {
+        None
+    }
+
+    /// Whether this handler does blocking or CPU-heavy work.
+    ///
+    /// Blocking handlers are polled on tokio's blocking thread pool so they
+    /// don't stall other jobs. Do the work inside the returned future, not in
+    /// [`handle`](Self::handle) itself.
+    fn is_blocking(&self) -> bool {
+        false
+    }
 }
 
 /// Registry for job handlers.
 ///
-/// Maps job types to their handlers for dynamic dispatch.
+/// Maps job types to their handlers for dynamic dispatch. A registry can be
+/// layered over a fallback, in which case its own handlers take precedence.
 #[derive(Default)]
 pub struct JobHandlerRegistry {
     handlers: HashMap<String, Arc<dyn JobHandler>>,
+    middleware: Vec<Middleware>,
+    fallback: Option<Arc<JobHandlerRegistry>>,
 }
 
 impl JobHandlerRegistry {
@@ -36,63 +197,249 @@ impl JobHandlerRegistry {
     pub fn new() -> Self {
         Self {
             handlers: HashMap::new(),
+            middleware: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    /// Layer this registry over `fallback`, resolving our own handlers first.
+    pub fn layered_over(mut self, fallback: Arc<JobHandlerRegistry>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Register a handler for a job type, replacing any existing one.
+    ///
+    /// Replacing a handler logs a warning and returns the old one. Use
+    /// [`try_register`](Self::try_register) to treat duplicates as an error.
+    pub fn register<H: JobHandler>(&mut self, handler: H) -> Option<Arc<dyn JobHandler>> {
+        let job_type = handler.job_type().to_string();
+        let replaced = self.handlers.insert(job_type.clone(), Arc::new(handler));
+        if replaced.is_some() {
+            tracing::warn!("Replaced existing handler for job type '{}'", job_type);
         }
+        replaced
     }
 
-    /// Register a handler for a job type.
-    pub fn register<H: JobHandler>(&mut self, handler: H) {
+    /// Register a handler, failing if this registry already has one for its job type.
+    ///
+    /// Handlers inherited from a fallback registry don't count as duplicates,
+    /// since overriding them is what layering is for.
+    pub fn try_register<H: JobHandler>(&mut self, handler: H) -> Result<(), String> {
         let job_type = handler.job_type().to_string();
+        if self.handlers.contains_key(&job_type) {
+            return Err(format!(
+                "A handler for job type '{}' is already registered",
+                job_type
+            ));
+        }
         self.handlers.insert(job_type, Arc::new(handler));
+        Ok(())
+    }
+
+    /// Wrap every handler call in `middleware`.
+    ///
+    /// Middleware registered first runs outermost. A fallback registry's
+    /// middleware wraps this registry's, so it also sees jobs handled here.
+    pub fn register_middleware<F>(&mut self, middleware: F)
+    where
+        F: Fn(&Job, HandlerFuture) -> HandlerFuture + Send + Sync + 'static,
+    {
+        self.middleware.push(Arc::new(middleware));
+    }
+
+    /// Run `handler` on `job`, wrapped in the registered middleware.
+    ///
+    /// Blocking handlers run on the blocking thread pool; the middleware still
+    /// runs inline around them.
+    pub fn run(&self, handler: &dyn JobHandler, job: &Job, ctx: JobContext) -> HandlerFuture {
+        let future = handler.handle(job, ctx);
+        if handler.is_blocking() {
+            self.wrap(job, on_blocking_thread(future))
+        } else {
+            self.wrap(job, future)
+        }
+    }
+
+    /// Wrap a handler's future in our middleware, then the fallback's.
+    fn wrap(&self, job: &Job, mut future: HandlerFuture) -> HandlerFuture {
+        for middleware in self.middleware.iter().rev() {
+            future = middleware(job, future);
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.wrap(job, future),
+            None => future,
+        }
     }
 
     /// Get a handler for a job type.
     pub fn get(&self, job_type: &str) -> Option<Arc<dyn JobHandler>> {
-        self.handlers.get(job_type).cloned()
+        self.handlers
+            .get(job_type)
+            .cloned()
+            .or_else(|| self.fallback.as_ref()?.get(job_type))
     }
 
     /// Check if a handler exists for a job type.
     pub fn has_handler(&self, job_type: &str) -> bool {
+        self.get(job_type).is_some()
+    }
+
+    /// Check if this registry itself, rather than its fallback, handles a job type.
+    pub fn owns_handler(&self, job_type: &str) -> bool {
         self.handlers.contains_key(job_type)
     }
 
+    /// Validate a payload against the schema registered for its job type.
+    ///
+    /// Job types without a handler or without a schema accept any payload.
+    pub fn validate_payload(&self, job_type: &str, payload: &JsonValue) -> Result<(), String> {
+        let Some(schema) = self.get(job_type).and_then(|h| h.payload_schema()) else {
+            return Ok(());
+        };
+
+        let validator = jsonschema::validator_for(&schema)
+            .map_err(|e| format!("Invalid payload schema for job type '{}': {}", job_type, e))?;
+
+        let errors: Vec<String> = validator
+            .iter_errors(payload)
+            .map(|e| format!("{} (at '{}')", e, e.instance_path))
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid payload for job type '{}': {}",
+                job_type,
+                errors.join("; ")
+            ))
+        }
+    }
+
     /// List all registered job types.
     pub fn job_types(&self) -> Vec<&str> {
-        self.handlers.keys().map(|s| s.as_str()).collect()
+        let mut types: Vec<&str> = self.handlers.keys().map(|s| s.as_str()).collect();
+        if let Some(fallback) = &self.fallback {
+            for job_type in fallback.job_types() {
+                if !self.handlers.contains_key(job_type) {
+                    types.push(job_type);
+                }
+            }
+        }
+        types
+    }
+
+    /// Describe every registered job type, sorted by name.
+    pub fn job_type_infos(&self) -> Vec<JobTypeInfo> {
+        let mut infos: Vec<JobTypeInfo> = self
+            .job_types()
+            .into_iter()
+            .map(|job_type| JobTypeInfo {
+                job_type: job_type.to_string(),
+                sample_payload: self.get(job_type).and_then(|h| h.sample_payload()),
+            })
+            .collect();
+        infos.sort_by(|a, b| a.job_type.cmp(&b.job_type));
+        infos
+    }
+}
+
+impl std::fmt::Debug for JobHandlerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobHandlerRegistry")
+            .field("job_types", &self.job_types())
+            .field("middleware", &self.middleware.len())
+            .finish()
     }
 }
 
+/// Drive `future` to completion on tokio's blocking thread pool.
+///
+/// Dropping the returned future (on timeout, say) can't interrupt the thread;
+/// the handler runs to the end and its result is discarded.
+fn on_blocking_thread(future: HandlerFuture) -> HandlerFuture {
+    Box::pin(async move {
+        let runtime = tokio::runtime::Handle::current();
+        tokio::task::spawn_blocking(move || runtime.block_on(future))
+            .await
+            .unwrap_or_else(|e| {
+                Err(JobError::transient(format!(
+                    "Blocking handler did not finish: {}",
+                    e
+                )))
+            })
+    })
+}
+
 /// A simple function-based job handler.
 pub struct FnHandler<F>
 where
-    F: Fn(&Job) -> HandlerFuture + Send + Sync + 'static,
+    F: Fn(&Job, JobContext) -> HandlerFuture + Send + Sync + 'static,
 {
     job_type: String,
     handler: F,
+    schema: Option<JsonValue>,
+    sample: Option<JsonValue>,
+    blocking: bool,
 }
 
 impl<F> FnHandler<F>
 where
-    F: Fn(&Job) -> HandlerFuture + Send + Sync + 'static,
+    F: Fn(&Job, JobContext) -> HandlerFuture + Send + Sync + 'static,
 {
     /// Create a new function-based handler.
     pub fn new(job_type: impl Into<String>, handler: F) -> Self {
         Self {
             job_type: job_type.into(),
             handler,
+            schema: None,
+            sample: None,
+            blocking: false,
         }
     }
+
+    /// Require payloads to match the given JSON Schema.
+    pub fn with_schema(mut self, schema: JsonValue) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Offer `payload` as an example for this job type.
+    pub fn with_sample_payload(mut self, payload: JsonValue) -> Self {
+        self.sample = Some(payload);
+        self
+    }
+
+    /// Run this handler on the blocking thread pool.
+    pub fn blocking(mut self) -> Self {
+        self.blocking = true;
+        self
+    }
 }
 
 impl<F> JobHandler for FnHandler<F>
 where
-    F: Fn(&Job) -> HandlerFuture + Send + Sync + 'static,
+    F: Fn(&Job, JobContext) -> HandlerFuture + Send + Sync + 'static,
 {
     fn job_type(&self) -> &str {
         &self.job_type
     }
 
-    fn handle(&self, job: &Job) -> HandlerFuture {
-        (self.handler)(job)
+    fn handle(&self, job: &Job, ctx: JobContext) -> HandlerFuture {
+        (self.handler)(job, ctx)
+    }
+
+    fn payload_schema(&self) -> Option<JsonValue> {
+        self.schema.clone()
+    }
+
+    fn sample_payload(&self) -> Option<JsonValue> {
+        self.sample.clone()
+    }
+
+    fn is_blocking(&self) -> bool {
+        self.blocking
     }
 }
 
@@ -100,7 +447,13 @@ where
 #[macro_export]
 macro_rules! job_handler {
     ($job_type:expr, |$job:ident| $body:expr) => {
-        $crate::FnHandler::new($job_type, |$job: &core::Job| {
+        $crate::FnHandler::new($job_type, |$job: &core::Job, _ctx: $crate::JobContext| {
+            let $job = $job.clone();
+            Box::pin(async move { $body })
+        })
+    };
+    ($job_type:expr, |$job:ident, $ctx:ident| $body:expr) => {
+        $crate::FnHandler::new($job_type, |$job: &core::Job, $ctx: $crate::JobContext| {
             let $job = $job.clone();
             Box::pin(async move { $body })
         })
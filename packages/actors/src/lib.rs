@@ -12,10 +12,10 @@
 //! # Usage
 //!
 //! ```ignore
-//! use actors::{Supervisor, SupervisorMessage, start_supervisor};
+//! use actors::{SupervisorArgs, SupervisorMessage, start_supervisor};
 //!
 //! // Start the supervisor
-//! let (supervisor, handle) = start_supervisor().await?;
+//! let (supervisor, handle) = start_supervisor(SupervisorArgs::new(handlers)).await?;
 //!
 //! // Create a queue via message
 //! supervisor.send_message(SupervisorMessage::CreateQueue { ... })?;
@@ -27,15 +27,26 @@ mod persistence;
 mod queue_actor;
 pub mod registry;
 mod supervisor;
+mod webhook;
 mod worker_actor;
 
-pub use handler::{FnHandler, HandlerResult, JobHandler, JobHandlerRegistry};
-pub use messages::{QueueMessage, SupervisorMessage, WorkerMessage};
+pub use handler::{
+    FnHandler, HandlerFuture, HandlerResult, JobContext, JobError, JobHandler, JobHandlerRegistry,
+    Middleware,
+};
+pub use messages::{
+    BatchEnqueueError, EnqueueError, HeartbeatSampling, JOB_NOT_FOUND, JOB_TYPE_NOT_ALLOWED,
+    QUEUE_NOT_ACCEPTING, QUEUE_NOT_FOUND, QueueMessage, SupervisorMessage, SupervisorStatus,
+    UNKNOWN_JOB_TYPE, WorkerMessage,
+};
 pub use persistence::StatePersistence;
-pub use queue_actor::QueueActor;
+pub use queue_actor::{QueueActor, QueueActorState};
 pub use registry::{ActorRegistry, global_registry};
-pub use supervisor::{Supervisor, start_supervisor};
-pub use worker_actor::WorkerActor;
+pub use supervisor::{
+    Supervisor, SupervisorArgs, declare_queues, restore_queues, start_supervisor,
+};
+pub use webhook::{WEBHOOK_SIGNATURE_HEADER, WebhookSender};
+pub use worker_actor::{WorkerActor, WorkerArgs};
 
 /// Re-export ractor types for convenience.
 pub use ractor::{Actor, ActorRef, RpcReplyPort, concurrency};
@@ -1,50 +1,138 @@
 //! Message types for actor communication.
 
-use queue_core::{Job, JobEvent, JobId, JobResult, Queue, QueueId, QueueStats};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use queue_core::{
+    CancellationReason, EnqueueReceipt, Job, JobEvent, JobId, JobLogLine, JobOverrides, JobResult,
+    JobTypeConfig, JobTypeInfo, Priority, Queue, QueueConfig, QueueId, QueueStats, WorkerInfo,
+};
 use ractor::RpcReplyPort;
+use tokio_util::sync::CancellationToken;
+
+use crate::handler::JobHandlerRegistry;
+
+/// Error reply when no queue holds the requested job.
+pub const JOB_NOT_FOUND: &str = "Job not found";
+
+/// Error reply when the supervisor has no queue with the requested ID.
+pub const QUEUE_NOT_FOUND: &str = "Queue not found";
+
+/// Error reply when a queue's state doesn't allow new jobs.
+pub const QUEUE_NOT_ACCEPTING: &str = "Queue is not accepting jobs";
+
+/// Error reply prefix when a queue's `allowed_job_types` doesn't list the job's type.
+pub const JOB_TYPE_NOT_ALLOWED: &str = "Job type is not allowed on this queue";
+
+/// Error reply prefix when a strict queue has no handler for the job's type.
+pub const UNKNOWN_JOB_TYPE: &str = "No handler registered for job type";
 
 /// Messages for the QueueActor.
 #[derive(Debug)]
 pub enum QueueMessage {
-    /// Enqueue a new job.
+    /// Enqueue a new job, filling unset settings from the queue's config.
     Enqueue {
         job: Box<Job>,
-        reply: RpcReplyPort<Result<Job, String>>,
+        overrides: JobOverrides,
+        reply: RpcReplyPort<Result<Job, EnqueueError>>,
+    },
+
+    /// Enqueue a new job like `Enqueue`, also reporting its place in line.
+    TryEnqueue {
+        job: Box<Job>,
+        overrides: JobOverrides,
+        reply: RpcReplyPort<Result<EnqueueReceipt, EnqueueError>>,
     },
 
-    /// Request the next job for a worker.
+    /// Request the next job for a worker, along with its cancellation token.
     RequestJob {
         worker_id: String,
-        reply: RpcReplyPort<Option<Job>>,
+        reply: RpcReplyPort<Option<(Job, CancellationToken)>>,
     },
 
-    /// Report job completion.
+    /// Report job completion with the lines the handler logged.
     JobCompleted {
         job_id: JobId,
         worker_id: String,
         result: JobResult,
+        logs: Vec<JobLogLine>,
     },
 
-    /// Report job failure.
+    /// Report job failure with the lines the handler logged.
     JobFailed {
         job_id: JobId,
         worker_id: String,
         error: String,
+        logs: Vec<JobLogLine>,
+        /// False when another attempt can't succeed, so the job fails without retrying.
+        retryable: bool,
     },
 
     /// Cancel a job.
     CancelJob {
         job_id: JobId,
-        reason: Option<String>,
+        reason: Option<CancellationReason>,
         reply: RpcReplyPort<Result<(), String>>,
     },
 
+    /// Cancel every non-terminal job matching the status filter (all when `None`).
+    CancelJobs {
+        status_filter: Option<String>,
+        reason: Option<CancellationReason>,
+        reply: RpcReplyPort<usize>,
+    },
+
+    /// Cancel every non-terminal job carrying `tag`.
+    CancelJobsByTag {
+        tag: String,
+        reason: Option<CancellationReason>,
+        reply: RpcReplyPort<usize>,
+    },
+
+    /// Cancel each of the given jobs that isn't finished yet.
+    CancelJobsById {
+        job_ids: Vec<JobId>,
+        reason: Option<CancellationReason>,
+        reply: RpcReplyPort<usize>,
+    },
+
     /// Retry a failed job.
     RetryJob {
         job_id: JobId,
         reply: RpcReplyPort<Result<Job, String>>,
     },
 
+    /// Retry every failed or cancelled job carrying `tag`.
+    RetryJobsByTag {
+        tag: String,
+        reply: RpcReplyPort<usize>,
+    },
+
+    /// Retry each of the given jobs that failed or was cancelled.
+    RetryJobsById {
+        job_ids: Vec<JobId>,
+        reply: RpcReplyPort<usize>,
+    },
+
+    /// Change the priority of a pending job.
+    Reprioritize {
+        job_id: JobId,
+        priority: Priority,
+        reply: RpcReplyPort<Result<Job, String>>,
+    },
+
+    /// Hold a pending job back from workers.
+    PauseJob {
+        job_id: JobId,
+        reply: RpcReplyPort<Result<Job, String>>,
+    },
+
+    /// Return a paused job to the pending queue.
+    ResumeJob {
+        job_id: JobId,
+        reply: RpcReplyPort<Result<Job, String>>,
+    },
+
     /// Get a job by ID.
     GetJob {
         job_id: JobId,
@@ -61,27 +149,55 @@ pub enum QueueMessage {
     /// Pause the queue.
     Pause,
 
-    /// Resume the queue.
+    /// Resume the queue, or start it again once stopped.
     Resume,
 
+    /// Stop taking new jobs, then stop once the queued ones have finished.
+    Drain,
+
+    /// Stop processing jobs; running jobs still finish.
+    Stop,
+
+    /// Stop accepting and handing out jobs ahead of a system shutdown,
+    /// without changing the queue's persisted state.
+    Close { reply: RpcReplyPort<()> },
+
     /// Get queue info.
     GetInfo { reply: RpcReplyPort<Queue> },
 
     /// Get queue stats.
     GetStats { reply: RpcReplyPort<QueueStats> },
 
+    /// Drop all pending jobs without archiving them, returning how many were removed.
+    Purge {
+        reply: RpcReplyPort<Result<u64, String>>,
+    },
+
+    /// Take every running job back from its worker and return it to pending,
+    /// returning how many were requeued.
+    RequeueRunning { reply: RpcReplyPort<u64> },
+
+    /// Archive the current stats window and reset the live counters.
+    RolloverStats { reply: RpcReplyPort<QueueStats> },
+
     /// Shutdown the queue gracefully.
     Shutdown,
 
     /// Periodic tick for housekeeping.
     Tick,
+
+    /// Retry writing jobs that were accepted while the database was failing.
+    RetryPersist,
 }
 
 /// Messages for the WorkerActor.
 #[derive(Debug)]
 pub enum WorkerMessage {
-    /// Start working on a job.
-    ProcessJob { job: Box<Job> },
+    /// Start working on a job; `cancel` fires if the job is cancelled mid-run.
+    ProcessJob {
+        job: Box<Job>,
+        cancel: CancellationToken,
+    },
 
     /// Stop current job (cancel).
     StopJob { reason: String },
@@ -89,8 +205,11 @@ pub enum WorkerMessage {
     /// Check if worker is idle.
     IsIdle { reply: RpcReplyPort<bool> },
 
-    /// Shutdown the worker.
-    Shutdown,
+    /// Shutdown the worker, acknowledging once its current job has settled.
+    Shutdown { reply: RpcReplyPort<()> },
+
+    /// Poll the queue now instead of waiting for the next heartbeat.
+    Poll,
 
     /// Heartbeat tick.
     Heartbeat,
@@ -103,6 +222,9 @@ pub enum SupervisorMessage {
     CreateQueue {
         name: String,
         description: Option<String>,
+        config: QueueConfig,
+        tags: Vec<String>,
+        tenant_id: Option<String>,
         reply: RpcReplyPort<Result<Queue, String>>,
     },
     /// Register an existing queue from persistence.
@@ -111,12 +233,28 @@ pub enum SupervisorMessage {
         reply: RpcReplyPort<Result<Queue, String>>,
     },
 
+    /// Override handlers for a queue by name, layered over the global registry.
+    ///
+    /// Must be sent before the queue is created or registered.
+    SetQueueHandlers {
+        queue_name: String,
+        handlers: JobHandlerRegistry,
+        reply: RpcReplyPort<Result<(), String>>,
+    },
+
     /// Get a queue by ID.
     GetQueue {
         queue_id: QueueId,
         reply: RpcReplyPort<Option<Queue>>,
     },
 
+    /// Resolve the settings a job type runs with in a queue.
+    GetJobTypeConfig {
+        queue_id: QueueId,
+        job_type: String,
+        reply: RpcReplyPort<Result<JobTypeConfig, String>>,
+    },
+
     /// Get a queue by name.
     GetQueueByName {
         name: String,
@@ -126,6 +264,23 @@ pub enum SupervisorMessage {
     /// List all queues.
     ListQueues { reply: RpcReplyPort<Vec<Queue>> },
 
+    /// List the job types the global handler registry can run, or with a
+    /// `queue_id`, those that queue would accept and has a handler for.
+    ListJobTypes {
+        queue_id: Option<QueueId>,
+        reply: RpcReplyPort<Result<Vec<JobTypeInfo>, String>>,
+    },
+
+    /// List live workers with their current job and last heartbeat.
+    ListWorkers {
+        reply: RpcReplyPort<Vec<WorkerInfo>>,
+    },
+
+    /// Get a snapshot of the supervisor's queues and workers.
+    GetStatus {
+        reply: RpcReplyPort<SupervisorStatus>,
+    },
+
     /// Pause a queue.
     PauseQueue {
         queue_id: QueueId,
@@ -138,17 +293,78 @@ pub enum SupervisorMessage {
         reply: RpcReplyPort<Result<(), String>>,
     },
 
+    /// Pause every queue carrying a tag, replying with how many matched.
+    PauseQueuesByTag {
+        tag: String,
+        reply: RpcReplyPort<usize>,
+    },
+
+    /// Resume every queue carrying a tag, replying with how many matched.
+    ResumeQueuesByTag {
+        tag: String,
+        reply: RpcReplyPort<usize>,
+    },
+
+    /// Drain a queue: finish its queued jobs without accepting new ones, then stop.
+    DrainQueue {
+        queue_id: QueueId,
+        reply: RpcReplyPort<Result<(), String>>,
+    },
+
+    /// Stop a queue.
+    StopQueue {
+        queue_id: QueueId,
+        reply: RpcReplyPort<Result<(), String>>,
+    },
+
     /// Delete a queue.
+    ///
+    /// With `drain`, the queue first drains for up to that long and is deleted
+    /// once it stops or the time runs out. Jobs still running at deletion are
+    /// cancelled.
     DeleteQueue {
         queue_id: QueueId,
+        drain: Option<Duration>,
         reply: RpcReplyPort<Result<(), String>>,
     },
 
+    /// Drop all pending jobs from a queue.
+    PurgeQueue {
+        queue_id: QueueId,
+        reply: RpcReplyPort<Result<u64, String>>,
+    },
+
+    /// Return a queue's running jobs to pending, e.g. when they're stuck after a crash.
+    RequeueRunning {
+        queue_id: QueueId,
+        reply: RpcReplyPort<Result<u64, String>>,
+    },
+
     /// Enqueue a job to a specific queue.
     EnqueueJob {
         queue_id: QueueId,
         job: Job,
-        reply: RpcReplyPort<Result<Job, String>>,
+        overrides: JobOverrides,
+        reply: RpcReplyPort<Result<Job, EnqueueError>>,
+    },
+
+    /// Enqueue a job to a specific queue, reporting its place in line.
+    TryEnqueueJob {
+        queue_id: QueueId,
+        job: Job,
+        overrides: JobOverrides,
+        reply: RpcReplyPort<Result<EnqueueReceipt, EnqueueError>>,
+    },
+
+    /// Enqueue several jobs to a queue in order, with the queue's default
+    /// retries and timeout.
+    ///
+    /// Stops at the first job that is rejected; the ones before it stay
+    /// enqueued and are returned with the error.
+    EnqueueJobs {
+        queue_id: QueueId,
+        jobs: Vec<Job>,
+        reply: RpcReplyPort<Result<Vec<Job>, BatchEnqueueError>>,
     },
 
     /// Get a job from any queue.
@@ -160,10 +376,67 @@ pub enum SupervisorMessage {
     /// Cancel a job.
     CancelJob {
         job_id: JobId,
-        reason: Option<String>,
+        reason: Option<CancellationReason>,
         reply: RpcReplyPort<Result<(), String>>,
     },
 
+    /// Cancel every non-terminal job in a queue matching the status filter.
+    CancelJobs {
+        queue_id: QueueId,
+        status_filter: Option<String>,
+        reason: Option<CancellationReason>,
+        reply: RpcReplyPort<Result<usize, String>>,
+    },
+
+    /// Cancel every non-terminal job in a queue carrying `tag`.
+    CancelJobsByTag {
+        queue_id: QueueId,
+        tag: String,
+        reason: Option<CancellationReason>,
+        reply: RpcReplyPort<Result<usize, String>>,
+    },
+
+    /// Cancel each of the given jobs in a queue that isn't finished yet.
+    CancelJobsById {
+        queue_id: QueueId,
+        job_ids: Vec<JobId>,
+        reason: Option<CancellationReason>,
+        reply: RpcReplyPort<Result<usize, String>>,
+    },
+
+    /// Retry every failed or cancelled job in a queue carrying `tag`.
+    RetryJobsByTag {
+        queue_id: QueueId,
+        tag: String,
+        reply: RpcReplyPort<Result<usize, String>>,
+    },
+
+    /// Retry each of the given jobs in a queue that failed or was cancelled.
+    RetryJobsById {
+        queue_id: QueueId,
+        job_ids: Vec<JobId>,
+        reply: RpcReplyPort<Result<usize, String>>,
+    },
+
+    /// Change the priority of a pending job in whichever queue holds it.
+    ReprioritizeJob {
+        job_id: JobId,
+        priority: Priority,
+        reply: RpcReplyPort<Result<Job, String>>,
+    },
+
+    /// Pause a pending job in whichever queue holds it.
+    PauseJob {
+        job_id: JobId,
+        reply: RpcReplyPort<Result<Job, String>>,
+    },
+
+    /// Resume a paused job in whichever queue holds it.
+    ResumeJob {
+        job_id: JobId,
+        reply: RpcReplyPort<Result<Job, String>>,
+    },
+
     /// Subscribe to events.
     Subscribe {
         sender: tokio::sync::broadcast::Sender<JobEvent>,
@@ -172,13 +445,129 @@ pub enum SupervisorMessage {
     /// Broadcast an event to all subscribers.
     BroadcastEvent { event: JobEvent },
 
+    /// A worker is alive and may stay silent for `busy_for` while it runs a job.
+    WorkerHeartbeat {
+        worker_id: String,
+        current_job: Option<JobId>,
+        busy_for: Duration,
+    },
+
+    /// Set how long a worker may miss heartbeats before it is replaced.
+    SetWorkerHeartbeatTimeout { timeout: Duration },
+
+    /// Cap how many jobs may run at once across all queues; `None` lifts the cap.
+    SetMaxConcurrentJobs { limit: Option<u32> },
+
+    /// Set how many worker heartbeat events reach subscribers.
+    SetHeartbeatSampling { sampling: HeartbeatSampling },
+
+    /// Roll every queue's stats over into a new reporting window.
+    RolloverStats,
+
     /// Shutdown all queues.
     Shutdown,
 
+    /// Close every queue, wait up to `timeout` for running jobs to finish,
+    /// then shut down. Replies with how many jobs were left to run on restart.
+    ShutdownGraceful {
+        timeout: Duration,
+        reply: RpcReplyPort<u64>,
+    },
+
     /// Periodic tick for housekeeping.
     Tick,
 }
 
+/// How worker heartbeat events are forwarded to subscribers.
+///
+/// Heartbeats still reach the supervisor's liveness tracking either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeartbeatSampling {
+    /// Forward every heartbeat.
+    #[default]
+    All,
+    /// Forward at most one heartbeat per worker per interval.
+    Every(Duration),
+    /// Drop all heartbeat events.
+    Off,
+}
+
+/// Snapshot of the supervisor's in-memory state.
+#[derive(Debug, Clone)]
+pub struct SupervisorStatus {
+    /// Live info for every queue.
+    pub queues: Vec<Queue>,
+    /// Number of worker actors across all queues.
+    pub worker_count: usize,
+    /// When the supervisor started.
+    pub started_at: DateTime<Utc>,
+}
+
+impl SupervisorStatus {
+    /// Whole seconds since the supervisor started.
+    pub fn uptime_secs(&self) -> u64 {
+        queue_core::duration_ms(self.started_at, Utc::now()) / 1000
+    }
+}
+
+/// Why a job was not enqueued.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EnqueueError {
+    /// The queue already holds `max_queue_size` pending jobs; retry once it drains.
+    #[error("Queue is full ({current} of {max} jobs pending)")]
+    QueueFull { current: usize, max: usize },
+
+    /// The job's serialized payload is larger than the queue's `max_payload_bytes`.
+    #[error("Payload is {size} bytes, over the queue's limit of {max} bytes")]
+    PayloadTooLarge { size: usize, max: usize },
+
+    /// The queue is paused, draining or closing.
+    #[error("{}", QUEUE_NOT_ACCEPTING)]
+    NotAccepting,
+
+    /// The queue's `allowed_job_types` doesn't list the job's type.
+    #[error("{}: {}", JOB_TYPE_NOT_ALLOWED, .0)]
+    JobTypeNotAllowed(String),
+
+    /// The queue is strict and no handler is registered for the job's type.
+    #[error("{}: {}", UNKNOWN_JOB_TYPE, .0)]
+    UnknownJobType(String),
+
+    /// The payload doesn't match the schema registered for its job type.
+    #[error("{0}")]
+    InvalidPayload(String),
+
+    /// The supervisor has no queue with the requested ID.
+    #[error("{}", QUEUE_NOT_FOUND)]
+    QueueNotFound,
+
+    /// Any other rejection, described for the caller.
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<String> for EnqueueError {
+    fn from(reason: String) -> Self {
+        EnqueueError::Other(reason)
+    }
+}
+
+impl From<&str> for EnqueueError {
+    fn from(reason: &str) -> Self {
+        EnqueueError::Other(reason.to_string())
+    }
+}
+
+/// Why a batch enqueue stopped, with the jobs enqueued before it did.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{error} ({} earlier jobs enqueued)", enqueued.len())]
+pub struct BatchEnqueueError {
+    /// Jobs enqueued before the rejected one; they are not rolled back.
+    pub enqueued: Vec<Job>,
+    /// Why the next job was rejected.
+    pub error: EnqueueError,
+}
+
 /// Result type for internal operations.
 #[allow(dead_code)]
 pub type ActorResult<T> = Result<T, ActorError>;
@@ -1,19 +1,58 @@
 //! Queue actor for managing jobs in a single queue.
 
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-
-use chrono::Utc;
-use queue_core::{Job, JobEvent, JobId, JobStatus, Queue, QueueState, QueueStats};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use queue_core::{
+    CancellationReason, EnqueueReceipt, Job, JobEvent, JobId, JobLogLine, JobOverrides, JobStatus,
+    Priority, PriorityCounts, Queue, QueueState, QueueStats,
+};
 use ractor::{Actor, ActorProcessingErr, ActorRef};
 use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+use crate::messages::{EnqueueError, JOB_NOT_FOUND, QueueMessage, SupervisorMessage};
+use crate::webhook::WebhookSender;
+
+/// How long past its timeout a running job may go unreported before its lease
+/// expires and it is reclaimed. Workers report timeouts themselves, so anything
+/// still running this late belongs to a worker that has died or hung.
+const LEASE_GRACE: Duration = Duration::from_secs(30);
+
+/// Finished jobs the circuit breaker measures the failure rate over.
+const BREAKER_WINDOW: usize = 20;
+
+/// Finished jobs needed before the circuit breaker can trip, so a single early
+/// failure doesn't pause the queue.
+const BREAKER_MIN_OUTCOMES: usize = 5;
+
+/// Pause between attempts to write jobs accepted while the database was failing.
+const PERSIST_RETRY_INTERVAL: Duration = Duration::from_millis(500);
 
-use crate::messages::{QueueMessage, SupervisorMessage};
+/// Store a job's status and attempts, advancing its version to match the database.
+async fn persist_status(job: &mut Job) -> Result<(), db::DbError> {
+    let stored = db::repositories::JobRepository::update_attempts(job).await?;
+    job.version = stored.version;
+    Ok(())
+}
 
 /// Wrapper for priority queue ordering (higher priority first, older jobs first).
 #[derive(Debug, Clone)]
 struct PriorityJob {
     job: Job,
+    /// Priority the job is ordered by: `job.priority` raised by its boost.
+    priority: Priority,
+}
+
+impl PriorityJob {
+    fn new(job: Job) -> Self {
+        Self {
+            priority: job.priority.boosted(job.priority_boost),
+            job,
+        }
+    }
 }
 
 impl PartialEq for PriorityJob {
@@ -33,7 +72,7 @@ impl PartialOrd for PriorityJob {
 impl Ord for PriorityJob {
     fn cmp(&self, other: &Self) -> Ordering {
         // Higher priority first
-        match self.job.priority.cmp(&other.job.priority) {
+        match self.priority.cmp(&other.priority) {
             Ordering::Equal => {
                 // Older jobs first (earlier created_at)
                 other.job.created_at.cmp(&self.job.created_at)
@@ -49,14 +88,45 @@ pub struct QueueActorState {
     pub queue: Queue,
     /// Pending jobs (priority queue).
     pending: BinaryHeap<PriorityJob>,
+    /// Pending jobs held back until their `run_at`.
+    delayed: HashMap<JobId, Job>,
+    /// Jobs paused individually; never handed to workers until resumed.
+    paused: HashMap<JobId, Job>,
     /// Running jobs by ID.
     running: HashMap<JobId, Job>,
+    /// Cancellation tokens handed to the workers running each job.
+    cancel_tokens: HashMap<JobId, CancellationToken>,
     /// All jobs by ID for quick lookup.
     jobs: HashMap<JobId, Job>,
     /// Event broadcaster.
     event_tx: Option<broadcast::Sender<JobEvent>>,
     /// Supervisor reference for event forwarding.
     supervisor: Option<ActorRef<SupervisorMessage>>,
+    /// Start of the current stats reporting window.
+    stats_window_start: DateTime<Utc>,
+    /// Extra time past a job's timeout before its lease expires.
+    lease_grace: Duration,
+    /// Set when the system is shutting down; no jobs are accepted or handed out.
+    closing: bool,
+    /// When a job was last handed to a worker, for spacing starts under `rate_limit`.
+    last_dispatch: Option<Instant>,
+    /// Set when a worker was last turned away by the rate limit.
+    rate_limited: bool,
+    /// Delivers finished-job events to the queue's `webhook_url`.
+    webhooks: Option<WebhookSender>,
+    /// Recent job outcomes for the circuit breaker, oldest first; `true` is a failure.
+    outcomes: VecDeque<bool>,
+    /// When a tripped circuit breaker lets the queue try again.
+    breaker_open_until: Option<Instant>,
+    /// Set once the breaker reopens the queue; the next outcome decides whether it trips again.
+    breaker_half_open: bool,
+    /// Jobs accepted under `accept_unpersisted` that aren't in the database yet.
+    ///
+    /// They are created, not updated, once the database takes them, so each is
+    /// written exactly once.
+    unpersisted: HashSet<JobId>,
+    /// Set while a `RetryPersist` is on its way.
+    persist_retry_scheduled: bool,
 }
 
 impl QueueActorState {
@@ -65,10 +135,24 @@ impl QueueActorState {
         Self {
             queue,
             pending: BinaryHeap::new(),
+            delayed: HashMap::new(),
+            paused: HashMap::new(),
             running: HashMap::new(),
+            cancel_tokens: HashMap::new(),
             jobs: HashMap::new(),
             event_tx: None,
             supervisor: None,
+            stats_window_start: Utc::now(),
+            lease_grace: LEASE_GRACE,
+            closing: false,
+            last_dispatch: None,
+            rate_limited: false,
+            webhooks: None,
+            outcomes: VecDeque::new(),
+            breaker_open_until: None,
+            breaker_half_open: false,
+            unpersisted: HashSet::new(),
+            persist_retry_scheduled: false,
         }
     }
 
@@ -84,26 +168,706 @@ impl QueueActorState {
         self
     }
 
+    /// Set the sender used for the queue's webhook.
+    pub fn with_webhooks(mut self, webhooks: WebhookSender) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Set how long past its timeout a running job is kept before being reclaimed.
+    pub fn with_lease_grace(mut self, grace: Duration) -> Self {
+        self.lease_grace = grace;
+        self
+    }
+
     /// Broadcast an event.
+    ///
+    /// Events go out through exactly one path so subscribers observe them in
+    /// emission order: the shared channel when present, otherwise forwarded
+    /// through the supervisor.
     fn broadcast(&self, event: JobEvent) {
-        if let Some(ref tx) = self.event_tx {
-            let _ = tx.send(event.clone());
+        if event.is_job_finished()
+            && let (Some(webhooks), Some(url)) = (&self.webhooks, &self.queue.config.webhook_url)
+        {
+            webhooks.send(url.clone(), &event);
         }
-        if let Some(ref supervisor) = self.supervisor {
+
+        if let Some(ref tx) = self.event_tx {
+            let _ = tx.send(event);
+        } else if let Some(ref supervisor) = self.supervisor {
             let _ = supervisor.send_message(SupervisorMessage::BroadcastEvent { event });
         }
     }
 
-    /// Update and broadcast stats.
-    fn update_stats(&mut self) {
-        self.queue.stats = QueueStats {
+    /// Broadcast the canonical status transition event for a job.
+    fn broadcast_status_change(&self, job_id: JobId, old_status: JobStatus, new_status: JobStatus) {
+        self.broadcast(JobEvent::JobStatusChanged {
+            job_id,
+            queue_id: self.queue.id,
+            old_status,
+            new_status,
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// Append handler log lines to a job, in memory and in the database.
+    async fn record_logs(&self, job: &mut Job, logs: Vec<JobLogLine>) {
+        if logs.is_empty() {
+            return;
+        }
+        if let Err(e) = db::repositories::JobRepository::append_logs(job.id, &logs).await {
+            tracing::warn!("Failed to store logs for job {}: {}", job.id, e);
+        }
+        job.logs.extend(logs);
+    }
+
+    /// Remove a running job on behalf of the worker reporting its outcome.
+    ///
+    /// Reports from a worker whose job was reclaimed or cancelled in the
+    /// meantime are ignored, even if the job is now running elsewhere.
+    fn take_running(&mut self, job_id: JobId, worker_id: &str) -> Option<Job> {
+        let owned = matches!(
+            self.running.get(&job_id).map(|job| &job.status),
+            Some(JobStatus::Running { worker_id: owner, .. }) if owner == worker_id
+        );
+        if !owned {
+            tracing::debug!(
+                "Ignoring report for job {} from {}: it no longer holds the job",
+                job_id,
+                worker_id
+            );
+            return None;
+        }
+        self.cancel_tokens.remove(&job_id);
+        self.running.remove(&job_id)
+    }
+
+    /// Broadcast that a running job was taken back from its worker.
+    fn broadcast_reclaimed(&self, job_id: JobId, old_status: JobStatus, reason: String) {
+        let worker_id = match &old_status {
+            JobStatus::Running { worker_id, .. } => worker_id.clone(),
+            _ => String::new(),
+        };
+        self.broadcast(JobEvent::JobReclaimed {
+            job_id,
+            queue_id: self.queue.id,
+            worker_id,
+            reason,
+            timestamp: Utc::now(),
+        });
+        self.broadcast_status_change(job_id, old_status, JobStatus::Pending);
+    }
+
+    /// Return running jobs whose lease has expired to the pending heap.
+    ///
+    /// A job's lease runs for its timeout plus the lease grace period. Its
+    /// cancellation token is fired in case the worker is merely hung, and any
+    /// report that arrives from it afterwards is ignored.
+    async fn reclaim_expired_leases(&mut self) {
+        let now = Utc::now();
+        let expired: Vec<JobId> = self
+            .running
+            .values()
+            .filter(|job| match &job.status {
+                JobStatus::Running { started_at, .. } => {
+                    let lease =
+                        Duration::from_secs(job.timeout_secs).saturating_add(self.lease_grace);
+                    chrono::Duration::from_std(lease)
+                        .ok()
+                        .and_then(|lease| started_at.checked_add_signed(lease))
+                        .is_some_and(|deadline| deadline < now)
+                }
+                _ => false,
+            })
+            .map(|job| job.id)
+            .collect();
+        if expired.is_empty() {
+            return;
+        }
+
+        for job_id in expired {
+            let reason = format!(
+                "Lease expired {}s past the job timeout",
+                self.lease_grace.as_secs()
+            );
+            if self.reclaim(job_id, reason).await {
+                tracing::warn!(
+                    "Reclaimed job {} in queue {} after its lease expired",
+                    job_id,
+                    self.queue.name
+                );
+            }
+        }
+
+        self.update_stats();
+    }
+
+    /// Take a running job back from its worker and return it to the pending heap.
+    ///
+    /// Its attempt count is left alone. Returns false if the job wasn't running.
+    async fn reclaim(&mut self, job_id: JobId, reason: String) -> bool {
+        let Some(mut job) = self.running.remove(&job_id) else {
+            return false;
+        };
+        if let Some(cancel) = self.cancel_tokens.remove(&job_id) {
+            cancel.cancel();
+        }
+
+        let old_status = job.status.clone();
+        job.status = JobStatus::Pending;
+        job.updated_at = Utc::now();
+        job.finish_attempt(job.updated_at, Some(reason.clone()));
+
+        if let Err(e) = persist_status(&mut job).await {
+            tracing::warn!("Failed to mark reclaimed job {} pending: {}", job_id, e);
+        }
+
+        self.jobs.insert(job_id, job.clone());
+        self.pending.push(PriorityJob::new(job));
+        self.broadcast_reclaimed(job_id, old_status, reason);
+        true
+    }
+
+    /// Validate, persist and queue a new job, filling unset settings from the config.
+    async fn enqueue(
+        &mut self,
+        mut job: Job,
+        overrides: JobOverrides,
+    ) -> Result<Job, EnqueueError> {
+        overrides.apply(&mut job, &self.queue.config);
+        job.tenant_id = self.queue.tenant_id.clone();
+        job.status = job.waiting_status(Utc::now());
+        if self.closing || !self.queue.is_accepting_jobs() {
+            return Err(EnqueueError::NotAccepting);
+        }
+
+        if let Some(allowed) = &self.queue.config.allowed_job_types
+            && !allowed.contains(&job.job_type)
+        {
+            return Err(EnqueueError::JobTypeNotAllowed(job.job_type));
+        }
+
+        if let Some(max) = self.queue.config.max_payload_bytes {
+            let size = serde_json::to_vec(&job.payload)
+                .map_err(|e| format!("Failed to serialize payload: {}", e))?
+                .len();
+            if size > max {
+                return Err(EnqueueError::PayloadTooLarge { size, max });
+            }
+        }
+
+        // Check queue size limit
+        let current = self.pending.len() + self.delayed.len();
+        if let Some(max) = self.queue.config.max_queue_size
+            && current >= max
+        {
+            return Err(EnqueueError::QueueFull { current, max });
+        }
+
+        if let Err(e) = db::repositories::JobRepository::create(&job).await {
+            if !self.queue.config.accept_unpersisted {
+                return Err(format!("Failed to persist job: {}", e).into());
+            }
+            tracing::warn!(
+                "Failed to persist job {}, keeping it in memory and retrying: {}",
+                job.id,
+                e
+            );
+            self.unpersisted.insert(job.id);
+        }
+
+        self.jobs.insert(job.id, job.clone());
+        self.push_pending(job.clone());
+
+        self.broadcast(JobEvent::JobEnqueued {
+            job: job.clone(),
+            timestamp: Utc::now(),
+        });
+        self.update_stats();
+
+        Ok(job)
+    }
+
+    /// Store a job that is about to start, first creating it if enqueue couldn't.
+    async fn persist_start(&mut self, job: &mut Job) -> Result<(), db::DbError> {
+        self.ensure_persisted(job.id).await?;
+        persist_status(job).await
+    }
+
+    /// Create a job accepted while the database was failing, as it is now.
+    ///
+    /// Does nothing for jobs already stored. A failed create whose row exists
+    /// anyway (an earlier write that timed out but landed) counts as done.
+    async fn ensure_persisted(&mut self, job_id: JobId) -> Result<(), db::DbError> {
+        if !self.unpersisted.contains(&job_id) {
+            return Ok(());
+        }
+        let Some(job) = self.jobs.get(&job_id) else {
+            // Left the queue before it was ever written
+            self.unpersisted.remove(&job_id);
+            return Ok(());
+        };
+
+        if let Err(e) = db::repositories::JobRepository::create(job).await
+            && db::repositories::JobRepository::get(job_id).await.is_err()
+        {
+            return Err(e);
+        }
+        tracing::info!("Persisted job {} after an earlier failure", job_id);
+        self.unpersisted.remove(&job_id);
+        Ok(())
+    }
+
+    /// Retry creating every job accepted while the database was failing.
+    async fn retry_persist(&mut self) {
+        let ids: Vec<JobId> = self.unpersisted.iter().copied().collect();
+        for job_id in ids {
+            if let Err(e) = self.ensure_persisted(job_id).await {
+                tracing::debug!("Job {} still not persisted: {}", job_id, e);
+            }
+        }
+    }
+
+    /// Send a `RetryPersist` after a pause if any job is waiting to be written.
+    fn schedule_persist_retry(&mut self, myself: &ActorRef<QueueMessage>) {
+        if self.unpersisted.is_empty() || self.persist_retry_scheduled {
+            return;
+        }
+        self.persist_retry_scheduled = true;
+
+        let myself = myself.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(PERSIST_RETRY_INTERVAL).await;
+            let _ = myself.send_message(QueueMessage::RetryPersist);
+        });
+    }
+
+    /// How many pending jobs will be handed out before `job`.
+    ///
+    /// A scheduled job isn't on the heap yet, so everything pending is ahead of it.
+    fn position_of(&self, job: &Job) -> usize {
+        match self.pending.iter().find(|entry| entry.job.id == job.id) {
+            Some(entry) => self.pending.iter().filter(|other| *other > entry).count(),
+            None => self.pending.len(),
+        }
+    }
+
+    /// Queue a waiting job, holding it back if it's scheduled for later.
+    fn push_pending(&mut self, job: Job) {
+        if job.is_due(Utc::now()) {
+            self.pending.push(PriorityJob::new(job));
+        } else {
+            self.delayed.insert(job.id, job);
+        }
+    }
+
+    /// Move scheduled jobs whose time has come onto the pending heap.
+    async fn promote_due_jobs(&mut self) {
+        let now = Utc::now();
+        let due: Vec<JobId> = self
+            .delayed
+            .values()
+            .filter(|job| job.is_due(now))
+            .map(|job| job.id)
+            .collect();
+        if due.is_empty() {
+            return;
+        }
+
+        for job_id in due {
+            let Some(mut job) = self.delayed.remove(&job_id) else {
+                continue;
+            };
+            if let JobStatus::Scheduled { .. } = job.status {
+                let old_status = std::mem::replace(&mut job.status, JobStatus::Pending);
+                job.updated_at = now;
+                if let Err(e) = persist_status(&mut job).await {
+                    tracing::warn!("Failed to mark scheduled job {} pending: {}", job_id, e);
+                }
+                self.jobs.insert(job_id, job.clone());
+                self.broadcast_status_change(job_id, old_status, JobStatus::Pending);
+            }
+            self.pending.push(PriorityJob::new(job));
+        }
+        self.update_stats();
+    }
+
+    /// Cancel a job, removing it from the pending heap or running set.
+    async fn cancel_job(
+        &mut self,
+        job_id: JobId,
+        reason: Option<CancellationReason>,
+    ) -> Result<(), String> {
+        let Some(mut job) = self.jobs.get(&job_id).cloned() else {
+            return Err(JOB_NOT_FOUND.into());
+        };
+        if job.status.is_terminal() {
+            return Err(format!(
+                "Job is already {} and cannot be cancelled",
+                job.status.as_str()
+            ));
+        }
+        let now = Utc::now();
+
+        // Remove from pending or running, asking a running handler to stop
+        self.running.remove(&job_id);
+        if let Some(cancel) = self.cancel_tokens.remove(&job_id) {
+            cancel.cancel();
+        }
+        self.pending.retain(|pj| pj.job.id != job_id);
+        self.delayed.remove(&job_id);
+        self.paused.remove(&job_id);
+
+        let old_status = job.status.clone();
+        job.status = JobStatus::Cancelled {
+            cancelled_at: now,
+            reason: reason.clone(),
+        };
+        job.updated_at = now;
+        job.finish_attempt(
+            now,
+            Some(
+                reason
+                    .as_ref()
+                    .map_or("Cancelled".into(), |r| r.to_string()),
+            ),
+        );
+
+        if let Err(e) = persist_status(&mut job).await {
+            tracing::warn!("Failed to update job {} status: {}", job_id, e);
+        }
+
+        self.jobs.insert(job_id, job.clone());
+
+        self.broadcast(JobEvent::JobCancelled {
+            job_id,
+            queue_id: self.queue.id,
+            reason,
+            timestamp: now,
+        });
+        self.broadcast_status_change(job_id, old_status, job.status);
+        self.update_stats();
+
+        Ok(())
+    }
+
+    /// Cancel every non-terminal job matching `filter`, returning how many were cancelled.
+    async fn cancel_matching(
+        &mut self,
+        reason: Option<CancellationReason>,
+        filter: impl Fn(&Job) -> bool,
+    ) -> usize {
+        let job_ids: Vec<JobId> = self
+            .jobs
+            .values()
+            .filter(|j| !j.status.is_terminal() && filter(j))
+            .map(|j| j.id)
+            .collect();
+
+        let mut cancelled = 0;
+        for job_id in job_ids {
+            if self.cancel_job(job_id, reason.clone()).await.is_ok() {
+                cancelled += 1;
+            }
+        }
+        cancelled
+    }
+
+    /// Put a failed or cancelled job back in the pending heap.
+    ///
+    /// Jobs that failed for good were archived, so their active record is
+    /// created again.
+    async fn retry_job(&mut self, job_id: JobId) -> Result<Job, String> {
+        let Some(mut job) = self.jobs.get(&job_id).cloned() else {
+            return Err(JOB_NOT_FOUND.into());
+        };
+        if !job.status.can_retry() {
+            return Err("Job cannot be retried".into());
+        }
+
+        let now = Utc::now();
+        let old_status = job.status.clone();
+        job.status = JobStatus::Pending;
+        job.updated_at = now;
+
+        match persist_status(&mut job).await {
+            Ok(()) => {}
+            Err(db::DbError::NotFound(_)) => {
+                job = db::repositories::JobRepository::create(&job)
+                    .await
+                    .map_err(|e| format!("Failed to restore job: {}", e))?;
+            }
+            Err(e) => return Err(format!("Failed to update job: {}", e)),
+        }
+
+        self.jobs.insert(job_id, job.clone());
+        self.pending.push(PriorityJob::new(job.clone()));
+        self.broadcast_status_change(job_id, old_status, JobStatus::Pending);
+        self.update_stats();
+        Ok(job)
+    }
+
+    /// Retry each of `job_ids`, returning how many were put back in line.
+    async fn retry_jobs(&mut self, job_ids: Vec<JobId>) -> usize {
+        let mut retried = 0;
+        for job_id in job_ids {
+            match self.retry_job(job_id).await {
+                Ok(_) => retried += 1,
+                Err(e) => tracing::warn!("Failed to retry job {}: {}", job_id, e),
+            }
+        }
+        retried
+    }
+
+    /// Change a pending job's priority.
+    async fn reprioritize_job(&mut self, job_id: JobId, priority: Priority) -> Result<Job, String> {
+        let Some(mut job) = self
+            .pending
+            .iter()
+            .find(|pj| pj.job.id == job_id)
+            .map(|pj| pj.job.clone())
+            .or_else(|| self.delayed.get(&job_id).cloned())
+        else {
+            return Err(match self.jobs.get(&job_id) {
+                Some(job) => format!(
+                    "Job is {} and can only be reprioritized while pending",
+                    job.status.as_str()
+                ),
+                None => JOB_NOT_FOUND.into(),
+            });
+        };
+
+        // An explicit priority replaces any retry boost
+        job.priority = priority;
+        job.priority_boost = 0;
+        job.updated_at = Utc::now();
+        let stored = db::repositories::JobRepository::update(&job)
+            .await
+            .map_err(|e| format!("Failed to update job: {}", e))?;
+        job.version = stored.version;
+
+        // BinaryHeap can't re-sift an entry in place, so drop it and push the
+        // updated copy; retain rebuilds the heap from the remaining jobs
+        self.pending.retain(|pj| pj.job.id != job_id);
+        self.delayed.remove(&job_id);
+        self.push_pending(job.clone());
+        self.jobs.insert(job_id, job.clone());
+        self.update_stats();
+
+        Ok(job)
+    }
+
+    /// Take a pending job out of the dispatch order until it's resumed.
+    async fn pause_job(&mut self, job_id: JobId) -> Result<Job, String> {
+        let Some(mut job) = self
+            .pending
+            .iter()
+            .find(|pj| pj.job.id == job_id)
+            .map(|pj| pj.job.clone())
+            .or_else(|| self.delayed.get(&job_id).cloned())
+        else {
+            return Err(match self.jobs.get(&job_id) {
+                Some(job) => format!(
+                    "Job is {} and can only be paused while pending",
+                    job.status.as_str()
+                ),
+                None => JOB_NOT_FOUND.into(),
+            });
+        };
+
+        let old_status = job.status.clone();
+        job.status = JobStatus::Paused;
+        job.updated_at = Utc::now();
+        persist_status(&mut job)
+            .await
+            .map_err(|e| format!("Failed to update job: {}", e))?;
+
+        self.pending.retain(|pj| pj.job.id != job_id);
+        self.delayed.remove(&job_id);
+        self.paused.insert(job_id, job.clone());
+        self.jobs.insert(job_id, job.clone());
+        self.broadcast_status_change(job_id, old_status, JobStatus::Paused);
+        self.update_stats();
+
+        Ok(job)
+    }
+
+    /// Put a paused job back in line for a worker.
+    async fn resume_job(&mut self, job_id: JobId) -> Result<Job, String> {
+        let Some(mut job) = self.paused.get(&job_id).cloned() else {
+            return Err(match self.jobs.get(&job_id) {
+                Some(job) => format!(
+                    "Job is {} and can only be resumed while paused",
+                    job.status.as_str()
+                ),
+                None => JOB_NOT_FOUND.into(),
+            });
+        };
+
+        let now = Utc::now();
+        job.status = job.waiting_status(now);
+        job.updated_at = now;
+        persist_status(&mut job)
+            .await
+            .map_err(|e| format!("Failed to update job: {}", e))?;
+
+        self.paused.remove(&job_id);
+        self.jobs.insert(job_id, job.clone());
+        self.push_pending(job.clone());
+        self.broadcast_status_change(job_id, JobStatus::Paused, job.status.clone());
+        self.update_stats();
+
+        Ok(job)
+    }
+
+    /// Move the queue to a new state, persisting and broadcasting the change.
+    async fn set_state(&mut self, new_state: QueueState) {
+        if let Err(e) =
+            db::repositories::QueueRepository::update_state(self.queue.id, new_state).await
+        {
+            tracing::warn!("Failed to persist queue state: {}", e);
+        }
+        self.change_state(new_state);
+    }
+
+    /// Move the queue to a new state and broadcast the change without persisting it.
+    fn change_state(&mut self, new_state: QueueState) {
+        let old_state = self.queue.state;
+        self.queue.state = new_state;
+        self.queue.updated_at = Utc::now();
+
+        self.broadcast(JobEvent::QueueStateChanged {
+            queue_id: self.queue.id,
+            old_state,
+            new_state,
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// Count a finished job towards the circuit breaker, tripping it on a failure spike.
+    fn record_outcome(&mut self, failed: bool, myself: &ActorRef<QueueMessage>) {
+        let Some(threshold) = self.queue.config.breaker_threshold else {
+            return;
+        };
+        // Jobs still finishing after the breaker tripped say nothing new
+        if self.breaker_open_until.is_some() {
+            return;
+        }
+
+        if std::mem::take(&mut self.breaker_half_open) {
+            if failed {
+                self.trip_breaker(myself);
+            } else {
+                tracing::info!("Circuit breaker closed for queue {}", self.queue.name);
+                self.outcomes.clear();
+            }
+            return;
+        }
+
+        self.outcomes.push_back(failed);
+        if self.outcomes.len() > BREAKER_WINDOW {
+            self.outcomes.pop_front();
+        }
+        let failures = self.outcomes.iter().filter(|failed| **failed).count();
+        if self.queue.state == QueueState::Running
+            && self.outcomes.len() >= BREAKER_MIN_OUTCOMES
+            && failures as f64 / self.outcomes.len() as f64 >= threshold
+        {
+            self.trip_breaker(myself);
+        }
+    }
+
+    /// Pause the queue and schedule a half-open retry once the cooldown is over.
+    ///
+    /// The pause isn't persisted, so a restart brings the queue back running.
+    fn trip_breaker(&mut self, myself: &ActorRef<QueueMessage>) {
+        let cooldown = self.queue.config.breaker_cooldown();
+        tracing::warn!(
+            "Circuit breaker tripped for queue {}; pausing for {:?}",
+            self.queue.name,
+            cooldown
+        );
+        self.outcomes.clear();
+        self.breaker_open_until = Some(Instant::now() + cooldown);
+        self.change_state(QueueState::Paused);
+
+        let myself = myself.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(cooldown).await;
+            let _ = myself.send_message(QueueMessage::Tick);
+        });
+    }
+
+    /// Let a trial job through once a tripped breaker's cooldown is over.
+    fn half_open_breaker_if_due(&mut self) {
+        match self.breaker_open_until {
+            Some(until) if Instant::now() >= until => self.breaker_open_until = None,
+            _ => return,
+        }
+        if self.queue.state == QueueState::Paused {
+            tracing::info!("Circuit breaker half-open for queue {}", self.queue.name);
+            self.breaker_half_open = true;
+            self.change_state(QueueState::Running);
+            self.update_stats();
+        }
+    }
+
+    /// Forget the circuit breaker's history, e.g. when the queue state is set by hand.
+    fn reset_breaker(&mut self) {
+        self.outcomes.clear();
+        self.breaker_open_until = None;
+        self.breaker_half_open = false;
+    }
+
+    /// Stop a draining queue once its last job has finished.
+    async fn finish_drain_if_idle(&mut self) {
+        if self.queue.state == QueueState::Draining
+            && self.pending.is_empty()
+            && self.delayed.is_empty()
+            && self.running.is_empty()
+        {
+            tracing::info!("Queue {} drained", self.queue.name);
+            self.set_state(QueueState::Stopped).await;
+        }
+    }
+
+    /// Current stats, with the gauges recomputed from the pending heap and running set.
+    fn live_stats(&self) -> QueueStats {
+        let now = Utc::now();
+        let mut pending_by_priority = PriorityCounts::default();
+        for pj in self.pending.iter() {
+            pending_by_priority.add(pj.job.priority);
+        }
+        // Scheduled jobs aren't waiting on a worker yet, so they don't age the queue
+        let oldest_pending = self.pending.iter().map(|pj| pj.job.created_at).min();
+
+        QueueStats {
             pending: self.pending.len() as u64,
+            scheduled: self.delayed.len() as u64,
             running: self.running.len() as u64,
-            completed: self.queue.stats.completed,
-            failed: self.queue.stats.failed,
-            avg_duration_ms: self.queue.stats.avg_duration_ms,
-            throughput_per_min: self.queue.stats.throughput_per_min,
-        };
+            oldest_pending_age_ms: oldest_pending
+                .map(|created_at| (now - created_at).num_milliseconds().max(0) as u64),
+            pending_by_priority,
+            rate_limited: self.rate_limited && !self.pending.is_empty(),
+            breaker_open: self.breaker_open_until.is_some(),
+            ..self.queue.stats.clone()
+        }
+    }
+
+    /// Whether the rate limit forbids starting another job yet.
+    ///
+    /// `rate_limit` is in jobs per second, so starts are spaced at least
+    /// `1 / rate_limit` seconds apart.
+    fn is_rate_limited(&self) -> bool {
+        match (self.queue.config.rate_limit, self.last_dispatch) {
+            (Some(rate), Some(last)) if rate > 0.0 => last.elapsed().as_secs_f64() < 1.0 / rate,
+            _ => false,
+        }
+    }
+
+    /// Update and broadcast stats.
+    fn update_stats(&mut self) {
+        self.queue.stats = self.live_stats();
 
         self.broadcast(JobEvent::QueueStatsUpdated {
             queue_id: self.queue.id,
@@ -133,12 +897,15 @@ impl Actor for QueueActor {
         match db::repositories::JobRepository::reset_running_to_pending_for_queue(args.queue.id)
             .await
         {
-            Ok(count) if count > 0 => {
+            Ok(reclaimed) if !reclaimed.is_empty() => {
                 tracing::info!(
                     "Reset {} stale running jobs to pending for queue {}",
-                    count,
+                    reclaimed.len(),
                     args.queue.name
                 );
+                for job in reclaimed {
+                    args.broadcast_reclaimed(job.id, job.status, "Server restarted".into());
+                }
             }
             Err(e) => {
                 tracing::warn!(
@@ -156,7 +923,7 @@ impl Actor for QueueActor {
                 let job_count = jobs.len();
                 for job in jobs {
                     args.jobs.insert(job.id, job.clone());
-                    args.pending.push(PriorityJob { job });
+                    args.push_pending(job);
                 }
                 if job_count > 0 {
                     tracing::info!(
@@ -175,6 +942,28 @@ impl Actor for QueueActor {
             }
         }
 
+        // Paused jobs stay out of the pending heap but must be known to be resumed
+        let paused_filter = db::repositories::JobFilter {
+            queue_id: Some(args.queue.id),
+            status: Some(JobStatus::Paused.as_str().to_string()),
+            ..Default::default()
+        };
+        match db::repositories::JobRepository::list(paused_filter).await {
+            Ok(jobs) => {
+                for job in jobs {
+                    args.jobs.insert(job.id, job.clone());
+                    args.paused.insert(job.id, job);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load paused jobs for queue {}: {}",
+                    args.queue.name,
+                    e
+                );
+            }
+        }
+
         // Update stats to reflect loaded jobs
         args.update_stats();
 
@@ -188,41 +977,34 @@ impl Actor for QueueActor {
         state: &mut Self::State,
     ) -> Result<(), ActorProcessingErr> {
         match message {
-            QueueMessage::Enqueue { job, reply } => {
-                let job = *job;
-                if !state.queue.is_accepting_jobs() {
-                    let _ = reply.send(Err("Queue is not accepting jobs".into()));
-                    return Ok(());
-                }
-
-                // Check queue size limit
-                if let Some(max_size) = state.queue.config.max_queue_size
-                    && state.pending.len() >= max_size
-                {
-                    let _ = reply.send(Err("Queue is full".into()));
-                    return Ok(());
-                }
-
-                if let Err(e) = db::repositories::JobRepository::create(&job).await {
-                    let _ = reply.send(Err(format!("Failed to persist job: {}", e)));
-                    return Ok(());
-                }
-
-                let job_id = job.id;
-                state.jobs.insert(job_id, job.clone());
-                state.pending.push(PriorityJob { job: job.clone() });
-
-                state.broadcast(JobEvent::JobEnqueued {
-                    job: job.clone(),
-                    timestamp: Utc::now(),
-                });
-                state.update_stats();
+            QueueMessage::Enqueue {
+                job,
+                overrides,
+                reply,
+            } => {
+                let _ = reply.send(state.enqueue(*job, overrides).await);
+                state.schedule_persist_retry(&myself);
+            }
 
-                let _ = reply.send(Ok(job));
+            QueueMessage::TryEnqueue {
+                job,
+                overrides,
+                reply,
+            } => {
+                let receipt = state
+                    .enqueue(*job, overrides)
+                    .await
+                    .map(|job| EnqueueReceipt {
+                        position: state.position_of(&job),
+                        pending_total: state.pending.len(),
+                        job,
+                    });
+                let _ = reply.send(receipt);
+                state.schedule_persist_retry(&myself);
             }
 
             QueueMessage::RequestJob { worker_id, reply } => {
-                if !state.queue.is_processing() {
+                if state.closing || !state.queue.is_processing() {
                     let _ = reply.send(None);
                     return Ok(());
                 }
@@ -233,10 +1015,39 @@ impl Actor for QueueActor {
                     return Ok(());
                 }
 
-                if let Some(priority_job) = state.pending.pop() {
-                    let mut job = priority_job.job;
+                state.promote_due_jobs().await;
+
+                // Only report throttling when there is work being held back
+                let throttled = !state.pending.is_empty() && state.is_rate_limited();
+                if throttled != state.rate_limited {
+                    state.rate_limited = throttled;
+                    state.update_stats();
+                }
+                if throttled {
+                    let _ = reply.send(None);
+                    return Ok(());
+                }
+
+                // Jobs past their deadline are cancelled rather than started
+                let next = loop {
+                    match state.pending.pop() {
+                        Some(pj) if pj.job.is_expired(Utc::now()) => {
+                            let job_id = pj.job.id;
+                            if let Err(e) = state
+                                .cancel_job(job_id, Some(CancellationReason::Expired))
+                                .await
+                            {
+                                tracing::warn!("Failed to cancel expired job {}: {}", job_id, e);
+                            }
+                        }
+                        next => break next,
+                    }
+                };
+
+                if let Some(PriorityJob { mut job, priority }) = next {
                     let now = Utc::now();
                     let previous_attempts = job.attempts;
+                    let old_status = job.status.clone();
 
                     job.attempts = job.attempts.saturating_add(1);
                     job.status = JobStatus::Running {
@@ -244,19 +1055,15 @@ impl Actor for QueueActor {
                         worker_id: worker_id.clone(),
                     };
                     job.updated_at = now;
+                    job.start_attempt(worker_id.clone(), now);
 
-                    if let Err(e) = db::repositories::JobRepository::update_status(
-                        job.id,
-                        &job.status,
-                        job.attempts,
-                    )
-                    .await
-                    {
+                    if let Err(e) = state.persist_start(&mut job).await {
                         tracing::warn!("Failed to mark job {} running: {}", job.id, e);
+                        job.attempts_log.pop();
                         job.attempts = previous_attempts;
                         job.status = JobStatus::Pending;
                         job.updated_at = now;
-                        state.pending.push(PriorityJob { job });
+                        state.pending.push(PriorityJob { job, priority });
                         state.update_stats();
                         let _ = reply.send(None);
                         return Ok(());
@@ -264,6 +1071,7 @@ impl Actor for QueueActor {
 
                     state.jobs.insert(job.id, job.clone());
                     state.running.insert(job.id, job.clone());
+                    state.last_dispatch = Some(Instant::now());
 
                     state.broadcast(JobEvent::JobStarted {
                         job_id: job.id,
@@ -271,9 +1079,12 @@ impl Actor for QueueActor {
                         worker_id,
                         timestamp: now,
                     });
+                    state.broadcast_status_change(job.id, old_status, job.status.clone());
                     state.update_stats();
 
-                    let _ = reply.send(Some(job));
+                    let cancel = CancellationToken::new();
+                    state.cancel_tokens.insert(job.id, cancel.clone());
+                    let _ = reply.send(Some((job, cancel)));
                 } else {
                     let _ = reply.send(None);
                 }
@@ -281,16 +1092,26 @@ impl Actor for QueueActor {
 
             QueueMessage::JobCompleted {
                 job_id,
-                worker_id: _,
+                worker_id,
                 result,
+                logs,
             } => {
-                if let Some(mut job) = state.running.remove(&job_id) {
+                if let Some(mut job) = state.take_running(job_id, &worker_id) {
+                    state.record_logs(&mut job, logs).await;
+
                     let now = Utc::now();
                     let started_at = match &job.status {
                         JobStatus::Running { started_at, .. } => *started_at,
                         _ => now,
                     };
-                    let duration_ms = (now - started_at).num_milliseconds() as u64;
+                    if now < started_at {
+                        tracing::warn!(
+                            "Job {} completed before it started (clock skew); clamping duration to 0",
+                            job_id
+                        );
+                    }
+                    let duration_ms = queue_core::duration_ms(started_at, now);
+                    let old_status = job.status.clone();
 
                     job.status = JobStatus::Completed {
                         started_at,
@@ -298,19 +1119,15 @@ impl Actor for QueueActor {
                         result,
                     };
                     job.updated_at = now;
+                    job.finish_attempt(now, None);
 
-                    if let Err(e) = db::repositories::JobRepository::update_status(
-                        job_id,
-                        &job.status,
-                        job.attempts,
-                    )
-                    .await
-                    {
+                    if let Err(e) = persist_status(&mut job).await {
                         tracing::warn!("Failed to update job {} status: {}", job_id, e);
                     }
 
                     state.jobs.insert(job_id, job.clone());
                     state.queue.stats.completed += 1;
+                    state.record_outcome(false, &myself);
 
                     state.broadcast(JobEvent::JobCompleted {
                         job_id,
@@ -318,21 +1135,27 @@ impl Actor for QueueActor {
                         duration_ms,
                         timestamp: now,
                     });
+                    state.broadcast_status_change(job_id, old_status, job.status.clone());
                     state.update_stats();
 
                     // Archive to database
                     if let Err(e) = db::repositories::JobRepository::archive(&job).await {
                         tracing::warn!("Failed to archive job {}: {}", job_id, e);
                     }
+                    state.finish_drain_if_idle().await;
                 }
             }
 
             QueueMessage::JobFailed {
                 job_id,
-                worker_id: _,
+                worker_id,
                 error,
+                logs,
+                retryable,
             } => {
-                if let Some(mut job) = state.running.remove(&job_id) {
+                if let Some(mut job) = state.take_running(job_id, &worker_id) {
+                    state.record_logs(&mut job, logs).await;
+
                     let now = Utc::now();
                     let started_at = match &job.status {
                         JobStatus::Running { started_at, .. } => *started_at,
@@ -340,7 +1163,8 @@ impl Actor for QueueActor {
                     };
 
                     let attempts = job.attempts;
-                    let will_retry = attempts < job.max_retries;
+                    let will_retry = retryable && attempts < job.max_retries;
+                    let old_status = job.status.clone();
 
                     job.status = JobStatus::Failed {
                         started_at,
@@ -349,6 +1173,7 @@ impl Actor for QueueActor {
                         attempts,
                     };
                     job.updated_at = now;
+                    job.finish_attempt(now, Some(error.clone()));
 
                     state.broadcast(JobEvent::JobFailed {
                         job_id,
@@ -358,23 +1183,20 @@ impl Actor for QueueActor {
                         will_retry,
                         timestamp: now,
                     });
+                    state.broadcast_status_change(job_id, old_status, job.status.clone());
 
                     if will_retry {
+                        let failed_status = job.status.clone();
                         job.status = JobStatus::Pending;
+                        job.priority_boost = state.queue.config.retry_priority_boost;
                         job.updated_at = now;
 
-                        if let Err(e) = db::repositories::JobRepository::update_status(
-                            job_id,
-                            &job.status,
-                            job.attempts,
-                        )
-                        .await
-                        {
+                        if let Err(e) = persist_status(&mut job).await {
                             tracing::warn!("Failed to mark job {} pending: {}", job_id, e);
                         }
 
-                        // Re-enqueue for retry
-                        state.pending.push(PriorityJob { job: job.clone() });
+                        // Re-enqueue for retry, ahead of same-priority work if boosted
+                        state.pending.push(PriorityJob::new(job.clone()));
 
                         state.broadcast(JobEvent::JobRetrying {
                             job_id,
@@ -382,14 +1204,9 @@ impl Actor for QueueActor {
                             attempt: attempts + 1,
                             timestamp: now,
                         });
+                        state.broadcast_status_change(job_id, failed_status, JobStatus::Pending);
                     } else {
-                        if let Err(e) = db::repositories::JobRepository::update_status(
-                            job_id,
-                            &job.status,
-                            job.attempts,
-                        )
-                        .await
-                        {
+                        if let Err(e) = persist_status(&mut job).await {
                             tracing::warn!("Failed to update job {} status: {}", job_id, e);
                         }
 
@@ -402,7 +1219,9 @@ impl Actor for QueueActor {
                     }
 
                     state.jobs.insert(job_id, job);
+                    state.record_outcome(true, &myself);
                     state.update_stats();
+                    state.finish_drain_if_idle().await;
                 }
             }
 
@@ -411,75 +1230,82 @@ impl Actor for QueueActor {
                 reason,
                 reply,
             } => {
-                if let Some(mut job) = state.jobs.get(&job_id).cloned() {
-                    let now = Utc::now();
+                let _ = reply.send(state.cancel_job(job_id, reason).await);
+                state.finish_drain_if_idle().await;
+            }
 
-                    // Remove from pending or running
-                    state.running.remove(&job_id);
-                    state.pending.retain(|pj| pj.job.id != job_id);
+            QueueMessage::CancelJobs {
+                status_filter,
+                reason,
+                reply,
+            } => {
+                let cancelled = state
+                    .cancel_matching(reason, |j| {
+                        status_filter
+                            .as_ref()
+                            .is_none_or(|s| j.status.as_str() == s)
+                    })
+                    .await;
+                let _ = reply.send(cancelled);
+                state.finish_drain_if_idle().await;
+            }
 
-                    job.status = JobStatus::Cancelled {
-                        cancelled_at: now,
-                        reason: reason.clone(),
-                    };
-                    job.updated_at = now;
+            QueueMessage::CancelJobsByTag { tag, reason, reply } => {
+                let cancelled = state.cancel_matching(reason, |j| j.has_tag(&tag)).await;
+                let _ = reply.send(cancelled);
+                state.finish_drain_if_idle().await;
+            }
 
-                    if let Err(e) = db::repositories::JobRepository::update_status(
-                        job_id,
-                        &job.status,
-                        job.attempts,
-                    )
-                    .await
-                    {
-                        tracing::warn!("Failed to update job {} status: {}", job_id, e);
-                    }
+            QueueMessage::CancelJobsById {
+                job_ids,
+                reason,
+                reply,
+            } => {
+                let cancelled = state
+                    .cancel_matching(reason, |j| job_ids.contains(&j.id))
+                    .await;
+                let _ = reply.send(cancelled);
+                state.finish_drain_if_idle().await;
+            }
 
-                    state.jobs.insert(job_id, job.clone());
+            QueueMessage::RetryJob { job_id, reply } => {
+                let _ = reply.send(state.retry_job(job_id).await);
+            }
 
-                    state.broadcast(JobEvent::JobCancelled {
-                        job_id,
-                        queue_id: state.queue.id,
-                        reason,
-                        timestamp: now,
-                    });
-                    state.update_stats();
+            QueueMessage::RetryJobsByTag { tag, reply } => {
+                let job_ids: Vec<JobId> = state
+                    .jobs
+                    .values()
+                    .filter(|j| j.status.can_retry() && j.has_tag(&tag))
+                    .map(|j| j.id)
+                    .collect();
 
-                    let _ = reply.send(Ok(()));
-                } else {
-                    let _ = reply.send(Err("Job not found".into()));
-                }
+                let _ = reply.send(state.retry_jobs(job_ids).await);
             }
 
-            QueueMessage::RetryJob { job_id, reply } => {
-                if let Some(mut job) = state.jobs.get(&job_id).cloned() {
-                    if !job.status.can_retry() {
-                        let _ = reply.send(Err("Job cannot be retried".into()));
-                        return Ok(());
-                    }
+            QueueMessage::RetryJobsById { job_ids, reply } => {
+                let job_ids: Vec<JobId> = job_ids
+                    .into_iter()
+                    .filter(|id| state.jobs.get(id).is_some_and(|j| j.status.can_retry()))
+                    .collect();
 
-                    let now = Utc::now();
-                    job.status = JobStatus::Pending;
-                    job.updated_at = now;
+                let _ = reply.send(state.retry_jobs(job_ids).await);
+            }
 
-                    if let Err(e) = db::repositories::JobRepository::update_status(
-                        job_id,
-                        &job.status,
-                        job.attempts,
-                    )
-                    .await
-                    {
-                        let _ = reply.send(Err(format!("Failed to update job: {}", e)));
-                        return Ok(());
-                    }
+            QueueMessage::Reprioritize {
+                job_id,
+                priority,
+                reply,
+            } => {
+                let _ = reply.send(state.reprioritize_job(job_id, priority).await);
+            }
 
-                    state.jobs.insert(job_id, job.clone());
-                    state.pending.push(PriorityJob { job: job.clone() });
-                    state.update_stats();
+            QueueMessage::PauseJob { job_id, reply } => {
+                let _ = reply.send(state.pause_job(job_id).await);
+            }
 
-                    let _ = reply.send(Ok(job));
-                } else {
-                    let _ = reply.send(Err("Job not found".into()));
-                }
+            QueueMessage::ResumeJob { job_id, reply } => {
+                let _ = reply.send(state.resume_job(job_id).await);
             }
 
             QueueMessage::GetJob { job_id, reply } => {
@@ -506,67 +1332,136 @@ impl Actor for QueueActor {
             }
 
             QueueMessage::Pause => {
-                let old_state = state.queue.state;
-                state.queue.state = QueueState::Paused;
-                state.queue.updated_at = Utc::now();
+                state.reset_breaker();
+                state.set_state(QueueState::Paused).await;
+            }
 
-                if let Err(e) = db::repositories::QueueRepository::update_state(
-                    state.queue.id,
-                    state.queue.state,
-                )
-                .await
+            QueueMessage::Resume => {
+                state.reset_breaker();
+                state.set_state(QueueState::Running).await;
+            }
+
+            QueueMessage::Drain => {
+                state.reset_breaker();
+                state.set_state(QueueState::Draining).await;
+                state.finish_drain_if_idle().await;
+            }
+
+            QueueMessage::Stop => {
+                state.reset_breaker();
+                state.set_state(QueueState::Stopped).await;
+            }
+
+            QueueMessage::Close { reply } => {
+                // Not persisted: the queue comes back in its saved state on restart
+                state.closing = true;
+                let _ = reply.send(());
+            }
+
+            QueueMessage::GetInfo { reply } => {
+                let _ = reply.send(state.queue.clone());
+            }
+
+            QueueMessage::GetStats { reply } => {
+                // Recomputed so the ages are current rather than as of the last change
+                let _ = reply.send(state.live_stats());
+            }
+
+            QueueMessage::Purge { reply } => {
+                match db::repositories::JobRepository::delete_pending_for_queue(state.queue.id)
+                    .await
                 {
-                    tracing::warn!("Failed to persist queue state: {}", e);
+                    Ok(_) => {
+                        let purged: Vec<JobId> = std::mem::take(&mut state.pending)
+                            .into_iter()
+                            .map(|pj| pj.job.id)
+                            .chain(state.delayed.drain().map(|(job_id, _)| job_id))
+                            .collect();
+                        for job_id in &purged {
+                            state.jobs.remove(job_id);
+                        }
+                        tracing::info!(
+                            "Purged {} pending jobs from queue {}",
+                            purged.len(),
+                            state.queue.name
+                        );
+                        state.update_stats();
+                        let _ = reply.send(Ok(purged.len() as u64));
+                        state.finish_drain_if_idle().await;
+                    }
+                    Err(e) => {
+                        let _ = reply.send(Err(format!("Failed to purge queue: {}", e)));
+                    }
                 }
+            }
 
-                state.broadcast(JobEvent::QueueStateChanged {
-                    queue_id: state.queue.id,
-                    old_state,
-                    new_state: QueueState::Paused,
-                    timestamp: Utc::now(),
-                });
+            QueueMessage::RequeueRunning { reply } => {
+                let running: Vec<JobId> = state.running.keys().copied().collect();
+                let mut requeued = 0;
+                for job_id in running {
+                    if state.reclaim(job_id, "Requeued by operator".into()).await {
+                        requeued += 1;
+                    }
+                }
+                if requeued > 0 {
+                    tracing::info!(
+                        "Requeued {} running jobs in queue {}",
+                        requeued,
+                        state.queue.name
+                    );
+                    state.update_stats();
+                }
+                let _ = reply.send(requeued);
             }
 
-            QueueMessage::Resume => {
-                let old_state = state.queue.state;
-                state.queue.state = QueueState::Running;
-                state.queue.updated_at = Utc::now();
+            QueueMessage::RolloverStats { reply } => {
+                let now = Utc::now();
+                let snapshot = state.queue.stats.clone();
 
-                if let Err(e) = db::repositories::QueueRepository::update_state(
+                if let Err(e) = db::repositories::QueueRepository::snapshot_stats(
                     state.queue.id,
-                    state.queue.state,
+                    &snapshot,
+                    state.stats_window_start,
+                    now,
                 )
                 .await
                 {
-                    tracing::warn!("Failed to persist queue state: {}", e);
+                    tracing::warn!(
+                        "Failed to archive stats for queue {}: {}",
+                        state.queue.name,
+                        e
+                    );
                 }
 
-                state.broadcast(JobEvent::QueueStateChanged {
-                    queue_id: state.queue.id,
-                    old_state,
-                    new_state: QueueState::Running,
-                    timestamp: Utc::now(),
-                });
-            }
-
-            QueueMessage::GetInfo { reply } => {
-                let _ = reply.send(state.queue.clone());
-            }
+                state.queue.stats = snapshot.rolled_over();
+                state.stats_window_start = now;
+                state.update_stats();
 
-            QueueMessage::GetStats { reply } => {
-                let _ = reply.send(state.queue.stats.clone());
+                let _ = reply.send(snapshot);
             }
 
             QueueMessage::Shutdown => {
                 tracing::info!("Shutting down queue: {}", state.queue.name);
+                for (_, cancel) in state.cancel_tokens.drain() {
+                    cancel.cancel();
+                }
                 // Could persist state here
                 myself.stop(None);
                 return Ok(());
             }
 
             QueueMessage::Tick => {
-                // Periodic housekeeping
-                // TODO: Check for timed-out jobs, persist state, etc.
+                // Periodic housekeeping: take back jobs from dead workers
+                state.reclaim_expired_leases().await;
+                state.promote_due_jobs().await;
+                state.half_open_breaker_if_due();
+                state.finish_drain_if_idle().await;
+            }
+
+            QueueMessage::RetryPersist => {
+                state.persist_retry_scheduled = false;
+                state.retry_persist().await;
+                state.schedule_persist_retry(&myself);
             }
         }
 
@@ -3,6 +3,8 @@
 use ractor::ActorRef;
 use std::collections::HashMap;
 use std::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::Notify;
 
 use crate::messages::{QueueMessage, SupervisorMessage};
 
@@ -12,6 +14,7 @@ use crate::messages::{QueueMessage, SupervisorMessage};
 /// references through the entire call stack.
 pub struct ActorRegistry {
     supervisor: RwLock<Option<ActorRef<SupervisorMessage>>>,
+    supervisor_registered: Notify,
     queues: RwLock<HashMap<String, ActorRef<QueueMessage>>>,
 }
 
@@ -20,6 +23,7 @@ impl ActorRegistry {
     pub fn new() -> Self {
         Self {
             supervisor: RwLock::new(None),
+            supervisor_registered: Notify::new(),
             queues: RwLock::new(HashMap::new()),
         }
     }
@@ -35,6 +39,7 @@ impl ActorRegistry {
                 *guard = Some(supervisor);
             }
         }
+        self.supervisor_registered.notify_waiters();
     }
 
     /// Get the supervisor.
@@ -45,6 +50,30 @@ impl ActorRegistry {
         }
     }
 
+    /// Get the supervisor, waiting up to `timeout` for it to be registered.
+    ///
+    /// Lets callers racing startup park until the supervisor is up instead of
+    /// failing straight away. Returns `None` if it isn't registered in time.
+    pub async fn wait_for_supervisor(
+        &self,
+        timeout: Duration,
+    ) -> Option<ActorRef<SupervisorMessage>> {
+        tokio::time::timeout(timeout, async {
+            loop {
+                // Listen before checking, so a registration in between isn't missed
+                let registered = self.supervisor_registered.notified();
+                tokio::pin!(registered);
+                registered.as_mut().enable();
+                if let Some(supervisor) = self.get_supervisor() {
+                    return supervisor;
+                }
+                registered.await;
+            }
+        })
+        .await
+        .ok()
+    }
+
     /// Register a queue actor.
     pub fn register_queue(&self, name: &str, queue: ActorRef<QueueMessage>) {
         match self.queues.write() {
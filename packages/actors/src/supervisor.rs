@@ -1,19 +1,68 @@
 //! Supervisor actor for managing all queues and workers.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
 
-use chrono::Utc;
-use queue_core::{JobEvent, Queue, QueueId};
-use ractor::{Actor, ActorProcessingErr, ActorRef, SupervisionEvent};
-use tokio::sync::broadcast;
+use chrono::{DateTime, Utc};
+use queue_core::{
+    EnqueueReceipt, HandlerSource, Job, JobEvent, JobId, JobOverrides, JobTypeConfig, JobTypeInfo,
+    Queue, QueueBuilder, QueueConfig, QueueId, QueueState, QueueStats, WorkerInfo,
+};
+use ractor::{Actor, ActorProcessingErr, ActorRef, RpcReplyPort, SupervisionEvent};
+use tokio::sync::{Semaphore, broadcast};
 
 use crate::handler::JobHandlerRegistry;
-use crate::messages::{QueueMessage, SupervisorMessage};
+use crate::messages::{
+    BatchEnqueueError, EnqueueError, HeartbeatSampling, JOB_NOT_FOUND, QUEUE_NOT_FOUND,
+    QueueMessage, SupervisorMessage, SupervisorStatus, WorkerMessage,
+};
 use crate::queue_actor::{QueueActor, QueueActorState};
+use crate::webhook::WebhookSender;
 use crate::worker_actor::{WorkerActor, WorkerArgs};
 
+/// Heartbeat tracking for a single worker.
+struct WorkerLiveness {
+    queue_id: QueueId,
+    worker: ActorRef<WorkerMessage>,
+    /// The worker is considered stalled if it hasn't heartbeat again by then.
+    deadline: Instant,
+    /// Job the worker reported running in its last heartbeat.
+    current_job: Option<JobId>,
+    /// When the worker last heartbeat (or was spawned).
+    last_heartbeat: DateTime<Utc>,
+    /// When a heartbeat event from the worker was last forwarded to subscribers.
+    last_event_forwarded: Option<Instant>,
+}
+
+/// Supervisor actor arguments.
+pub struct SupervisorArgs {
+    /// Handlers the workers of every queue run jobs with.
+    pub handlers: JobHandlerRegistry,
+    /// Cap on jobs running at once across all queues; `None` means no cap.
+    pub max_concurrent_jobs: Option<u32>,
+    /// How long archived jobs and logged events are kept; `None` keeps them forever.
+    pub history_retention: Option<Duration>,
+    /// How many worker heartbeat events reach subscribers.
+    pub heartbeat_sampling: HeartbeatSampling,
+    /// Secret queue webhooks are signed with; `None` sends them unsigned.
+    pub webhook_secret: Option<String>,
+}
+
+impl SupervisorArgs {
+    /// Arguments running `handlers`, with every other setting at its default.
+    pub fn new(handlers: JobHandlerRegistry) -> Self {
+        Self {
+            handlers,
+            max_concurrent_jobs: None,
+            history_retention: None,
+            heartbeat_sampling: HeartbeatSampling::All,
+            webhook_secret: None,
+        }
+    }
+}
+
 /// State for the supervisor actor.
 pub struct SupervisorState {
     /// All queue actors by ID.
@@ -24,21 +73,365 @@ pub struct SupervisorState {
     pub event_tx: broadcast::Sender<JobEvent>,
     /// Handler registry for workers.
     pub handlers: Arc<JobHandlerRegistry>,
+    /// Per-queue handler registries (by queue name), layered over `handlers`.
+    pub queue_handlers: HashMap<String, Arc<JobHandlerRegistry>>,
+    /// Worker actors by the queue they serve.
+    pub workers: HashMap<QueueId, Vec<ActorRef<WorkerMessage>>>,
+    /// When the supervisor started.
+    pub started_at: DateTime<Utc>,
+    /// How long archived jobs and logged events are kept; `None` keeps them forever.
+    pub history_retention: Option<Duration>,
+    /// How long a worker may miss heartbeats before it is replaced.
+    pub worker_heartbeat_timeout: Duration,
+    /// How many worker heartbeat events reach subscribers.
+    pub heartbeat_sampling: HeartbeatSampling,
+    /// Permits every worker holds while running a job, capping jobs across all queues.
+    pub job_permits: Arc<Semaphore>,
+    /// Total number of `job_permits`; `None` means no global cap.
+    max_concurrent_jobs: Option<u32>,
+    /// Permits still to be retired after a lowered cap, once running jobs release them.
+    permits_to_retire: Arc<AtomicU32>,
+    /// Task retiring `permits_to_retire` as permits are released.
+    retiring_permits: Option<tokio::task::JoinHandle<()>>,
+    /// Webhook sender shared by every queue actor.
+    pub webhooks: WebhookSender,
+    /// Heartbeat tracking by worker ID.
+    worker_liveness: HashMap<String, WorkerLiveness>,
     /// Worker counter for unique IDs.
     worker_counter: u64,
 }
 
 impl SupervisorState {
     /// Create a new supervisor state.
-    pub fn new(handlers: JobHandlerRegistry) -> Self {
+    ///
+    /// Fails if the webhook HTTP client can't be built.
+    pub fn new(args: SupervisorArgs) -> Result<Self, reqwest::Error> {
         let (event_tx, _) = broadcast::channel(1024);
-        Self {
+        let max_concurrent_jobs = args.max_concurrent_jobs;
+        let permits = max_concurrent_jobs.unwrap_or(UNLIMITED_JOB_PERMITS);
+        Ok(Self {
             queues: HashMap::new(),
             queue_info: HashMap::new(),
             event_tx,
-            handlers: Arc::new(handlers),
+            handlers: Arc::new(args.handlers),
+            queue_handlers: HashMap::new(),
+            workers: HashMap::new(),
+            started_at: Utc::now(),
+            history_retention: args.history_retention,
+            worker_heartbeat_timeout: DEFAULT_WORKER_HEARTBEAT_TIMEOUT,
+            heartbeat_sampling: args.heartbeat_sampling,
+            job_permits: Arc::new(Semaphore::new(permits as usize)),
+            max_concurrent_jobs,
+            permits_to_retire: Arc::new(AtomicU32::new(0)),
+            retiring_permits: None,
+            webhooks: WebhookSender::new(args.webhook_secret)?,
+            worker_liveness: HashMap::new(),
             worker_counter: 0,
+        })
+    }
+
+    /// Resize the shared job permits to `limit`; `None` lifts the global cap.
+    ///
+    /// Permits held by running jobs are only retired once those jobs finish;
+    /// raising the cap first cancels any retirements still waiting.
+    fn set_max_concurrent_jobs(&mut self, limit: Option<u32>) {
+        let old = self.max_concurrent_jobs.unwrap_or(UNLIMITED_JOB_PERMITS);
+        let new = limit.unwrap_or(UNLIMITED_JOB_PERMITS);
+        self.max_concurrent_jobs = limit;
+
+        if new > old {
+            let raise = new - old;
+            let pending = self
+                .permits_to_retire
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                    Some(n.saturating_sub(raise))
+                })
+                .unwrap_or(0);
+            self.job_permits
+                .add_permits((raise - pending.min(raise)) as usize);
+            if pending <= raise
+                && let Some(task) = self.retiring_permits.take()
+            {
+                task.abort();
+            }
+        } else if new < old {
+            self.permits_to_retire
+                .fetch_add(old - new, Ordering::SeqCst);
+            self.retire_permits();
+        }
+    }
+
+    /// Retire owed permits that are free now, and the rest as they're released.
+    fn retire_permits(&mut self) {
+        let available = self.job_permits.available_permits().min(u32::MAX as usize) as u32;
+        let taken = self
+            .permits_to_retire
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                Some(n - n.min(available))
+            })
+            .map_or(0, |n| n.min(available));
+        let forgotten = self.job_permits.forget_permits(taken as usize) as u32;
+        // A worker took some in between; they're retired once it's done
+        self.permits_to_retire
+            .fetch_add(taken - forgotten, Ordering::SeqCst);
+
+        if let Some(task) = self.retiring_permits.take() {
+            task.abort();
+        }
+        if self.permits_to_retire.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        let (permits, to_retire) = (self.job_permits.clone(), self.permits_to_retire.clone());
+        self.retiring_permits = Some(tokio::spawn(async move {
+            while to_retire.load(Ordering::SeqCst) > 0 {
+                let Ok(permit) = permits.clone().acquire_owned().await else {
+                    return;
+                };
+                // Only retire while still owed; a raised cap may have cancelled it
+                if to_retire
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok()
+                {
+                    permit.forget();
+                }
+            }
+        }));
+    }
+
+    /// Whether `event` should reach subscribers under the heartbeat sampling.
+    ///
+    /// Only worker heartbeats are sampled; every other event passes through.
+    fn should_forward(&mut self, event: &JobEvent) -> bool {
+        let JobEvent::WorkerHeartbeat { worker_id, .. } = event else {
+            return true;
+        };
+        let interval = match self.heartbeat_sampling {
+            HeartbeatSampling::All => return true,
+            HeartbeatSampling::Off => return false,
+            HeartbeatSampling::Every(interval) => interval,
+        };
+        let Some(liveness) = self.worker_liveness.get_mut(worker_id) else {
+            return true;
+        };
+
+        let now = Instant::now();
+        if liveness
+            .last_event_forwarded
+            .is_some_and(|last| now.duration_since(last) < interval)
+        {
+            return false;
         }
+        liveness.last_event_forwarded = Some(now);
+        true
+    }
+
+    /// Handler registry for a queue: its overrides if any, else the global one.
+    pub fn handlers_for(&self, queue_name: &str) -> Arc<JobHandlerRegistry> {
+        self.queue_handlers
+            .get(queue_name)
+            .cloned()
+            .unwrap_or_else(|| self.handlers.clone())
+    }
+
+    /// Send a message to every queue carrying `tag`, returning how many were reached.
+    fn send_to_tagged(&self, tag: &str, message: impl Fn() -> QueueMessage) -> usize {
+        self.queue_info
+            .values()
+            .filter(|queue| queue.has_tag(tag))
+            .filter_map(|queue| self.queues.get(&queue.id))
+            .filter(|queue_ref| queue_ref.send_message(message()).is_ok())
+            .count()
+    }
+
+    /// Fetch live info from one queue actor.
+    async fn get_queue(&self, queue_id: QueueId) -> Option<Queue> {
+        let queue_ref = self.queues.get(&queue_id)?;
+        let (tx, rx) = ractor::concurrency::oneshot();
+        queue_ref
+            .send_message(QueueMessage::GetInfo { reply: tx.into() })
+            .ok()?;
+        rx.await.ok()
+    }
+
+    /// Resolve which handler and defaults a job type gets in a queue.
+    fn job_type_config(&self, queue: &Queue, job_type: String) -> JobTypeConfig {
+        let overridden = self
+            .queue_handlers
+            .get(&queue.name)
+            .is_some_and(|handlers| handlers.owns_handler(&job_type));
+        let handler = if overridden {
+            Some(HandlerSource::Queue)
+        } else if self.handlers.has_handler(&job_type) {
+            Some(HandlerSource::Global)
+        } else {
+            None
+        };
+        let has_payload_schema = self
+            .handlers_for(&queue.name)
+            .get(&job_type)
+            .is_some_and(|h| h.payload_schema().is_some());
+
+        JobTypeConfig {
+            queue_id: queue.id,
+            job_type,
+            handler,
+            has_payload_schema,
+            timeout_secs: queue.config.default_timeout_secs,
+            max_retries: queue.config.default_max_retries,
+        }
+    }
+
+    /// Job types a queue has a handler for and its `allowed_job_types` lets in.
+    fn job_types_for(&self, queue: &Queue) -> Vec<JobTypeInfo> {
+        let allowed = queue.config.allowed_job_types.as_ref();
+        self.handlers_for(&queue.name)
+            .job_type_infos()
+            .into_iter()
+            .filter(|info| allowed.is_none_or(|types| types.contains(&info.job_type)))
+            .collect()
+    }
+
+    /// Fetch live info from every queue actor.
+    async fn list_queues(&self) -> Vec<Queue> {
+        let mut queues = Vec::new();
+        for queue_ref in self.queues.values() {
+            let (tx, rx) = ractor::concurrency::oneshot();
+            if queue_ref
+                .send_message(QueueMessage::GetInfo { reply: tx.into() })
+                .is_ok()
+                && let Ok(queue) = rx.await
+            {
+                queues.push(queue);
+            }
+        }
+        queues
+    }
+
+    /// Validate a job's payload and hand it to its queue.
+    async fn enqueue(
+        &self,
+        queue_id: QueueId,
+        job: Job,
+        overrides: JobOverrides,
+    ) -> Result<Job, EnqueueError> {
+        self.enqueue_with(queue_id, job, |job, reply| QueueMessage::Enqueue {
+            job,
+            overrides,
+            reply,
+        })
+        .await
+    }
+
+    /// Like [`Self::enqueue`], also reporting the job's place in line.
+    async fn try_enqueue(
+        &self,
+        queue_id: QueueId,
+        job: Job,
+        overrides: JobOverrides,
+    ) -> Result<EnqueueReceipt, EnqueueError> {
+        self.enqueue_with(queue_id, job, |job, reply| QueueMessage::TryEnqueue {
+            job,
+            overrides,
+            reply,
+        })
+        .await
+    }
+
+    /// Validate a job's type and payload and send it to its queue in the message `build` makes.
+    async fn enqueue_with<T, F>(
+        &self,
+        queue_id: QueueId,
+        job: Job,
+        build: F,
+    ) -> Result<T, EnqueueError>
+    where
+        T: Send + 'static,
+        F: FnOnce(Box<Job>, RpcReplyPort<Result<T, EnqueueError>>) -> QueueMessage,
+    {
+        let queue = self.queue_info.get(&queue_id);
+        let handlers = queue.map_or_else(|| self.handlers.clone(), |q| self.handlers_for(&q.name));
+        if queue.is_some_and(|q| q.config.strict_job_types) && !handlers.has_handler(&job.job_type)
+        {
+            return Err(EnqueueError::UnknownJobType(job.job_type));
+        }
+        handlers
+            .validate_payload(&job.job_type, &job.payload)
+            .map_err(EnqueueError::InvalidPayload)?;
+
+        let queue_ref = self
+            .queues
+            .get(&queue_id)
+            .ok_or(EnqueueError::QueueNotFound)?;
+        let (tx, rx) = ractor::concurrency::oneshot();
+        queue_ref
+            .send_message(build(Box::new(job), tx.into()))
+            .map_err(|_| "Failed to enqueue job".to_string())?;
+        let enqueued = rx
+            .await
+            .unwrap_or_else(|_| Err("Failed to enqueue job".into()))?;
+        self.wake_workers(queue_id);
+        Ok(enqueued)
+    }
+
+    /// Have a queue's idle workers poll now rather than waiting for their next tick.
+    fn wake_workers(&self, queue_id: QueueId) {
+        for worker in self.workers.get(&queue_id).into_iter().flatten() {
+            let _ = worker.send_message(WorkerMessage::Poll);
+        }
+    }
+
+    /// Send a job update to each queue until one reports it holds the job.
+    async fn update_job_in_queues<F>(&self, build: F) -> Result<Job, String>
+    where
+        F: Fn(RpcReplyPort<Result<Job, String>>) -> QueueMessage,
+    {
+        for queue_ref in self.queues.values() {
+            let (tx, rx) = ractor::concurrency::oneshot();
+            if queue_ref.send_message(build(tx.into())).is_ok()
+                && let Ok(result) = rx.await
+                && result
+                    .as_ref()
+                    .err()
+                    .is_none_or(|e| e.as_str() != JOB_NOT_FOUND)
+            {
+                return result;
+            }
+        }
+        Err(JOB_NOT_FOUND.into())
+    }
+
+    /// Ask one queue for a count, such as how many jobs a bulk action touched.
+    async fn count_in_queue<F>(&self, queue_id: QueueId, build: F) -> Result<usize, String>
+    where
+        F: FnOnce(RpcReplyPort<usize>) -> QueueMessage,
+    {
+        let queue_ref = self.queues.get(&queue_id).ok_or(QUEUE_NOT_FOUND)?;
+        let (tx, rx) = ractor::concurrency::oneshot();
+        queue_ref
+            .send_message(build(tx.into()))
+            .map_err(|e| format!("Failed to reach queue: {}", e))?;
+        rx.await.map_err(|_| "Queue did not reply".to_string())
+    }
+
+    /// Snapshot of every tracked worker, grouped by queue name.
+    fn list_workers(&self) -> Vec<WorkerInfo> {
+        let mut workers: Vec<WorkerInfo> = self
+            .worker_liveness
+            .iter()
+            .map(|(worker_id, liveness)| WorkerInfo {
+                worker_id: worker_id.clone(),
+                queue_id: liveness.queue_id,
+                queue_name: self
+                    .queue_info
+                    .get(&liveness.queue_id)
+                    .map(|q| q.name.clone())
+                    .unwrap_or_default(),
+                current_job: liveness.current_job,
+                last_heartbeat: liveness.last_heartbeat,
+            })
+            .collect();
+        workers.sort_by(|a, b| (&a.queue_name, &a.worker_id).cmp(&(&b.queue_name, &b.worker_id)));
+        workers
     }
 
     /// Generate a unique worker ID.
@@ -46,6 +439,214 @@ impl SupervisorState {
         self.worker_counter += 1;
         format!("worker-{}", self.worker_counter)
     }
+
+    /// Shut every queue down, then drain and forget every worker.
+    async fn stop_queues_and_workers(&mut self) {
+        self.worker_liveness.clear();
+        // Shutting the queues down first cancels their running jobs,
+        // so the workers settle without waiting them out
+        for queue_ref in self.queues.values() {
+            let _ = queue_ref.send_message(QueueMessage::Shutdown);
+        }
+        let workers = self.workers.drain().flat_map(|(_, w)| w).collect();
+        drain_workers(workers).await;
+    }
+}
+
+/// Permit count standing in for no global cap on running jobs.
+const UNLIMITED_JOB_PERMITS: u32 = u32::MAX;
+
+/// How long to wait for a worker to settle its current job during drain.
+const WORKER_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often a graceful shutdown checks whether running jobs have finished.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a worker may miss heartbeats, on top of any job it reported running.
+///
+/// Also covers the grace period a cancelled handler gets past its timeout.
+const DEFAULT_WORKER_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Time remaining until the next stats rollover boundary (midnight UTC).
+fn until_next_rollover(now: DateTime<Utc>) -> Duration {
+    now.date_naive()
+        .succ_opt()
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| (midnight.and_utc() - now).to_std().ok())
+        .unwrap_or(Duration::from_secs(24 * 60 * 60))
+}
+
+/// Shut workers down and wait until each has settled its current job.
+async fn drain_workers(workers: Vec<ActorRef<WorkerMessage>>) {
+    let acks = workers.iter().map(|worker| {
+        ractor::rpc::call(
+            worker,
+            |reply| WorkerMessage::Shutdown { reply },
+            Some(WORKER_DRAIN_TIMEOUT),
+        )
+    });
+
+    for (worker, ack) in workers
+        .iter()
+        .zip(futures_util::future::join_all(acks).await)
+    {
+        if !matches!(ack, Ok(ractor::rpc::CallResult::Success(()))) {
+            tracing::warn!("Worker {} did not acknowledge shutdown", worker.get_id());
+        }
+    }
+}
+
+/// Wait until a draining queue has stopped, returning false if `timeout` passes first.
+async fn wait_for_drain(queue_ref: &ActorRef<QueueMessage>, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let (tx, rx) = ractor::concurrency::oneshot();
+        if queue_ref
+            .send_message(QueueMessage::GetInfo { reply: tx.into() })
+            .is_err()
+        {
+            return true;
+        }
+        match rx.await {
+            Ok(queue) if queue.state == QueueState::Stopped => return true,
+            Ok(_) => {}
+            Err(_) => return true,
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+    }
+}
+
+/// Persist broadcast events to the event log so late subscribers can backfill.
+///
+/// Stats updates and heartbeats are left out: they are frequent, and each is
+/// superseded by the next.
+fn spawn_event_recorder(mut rx: broadcast::Receiver<JobEvent>) {
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(JobEvent::QueueStatsUpdated { .. } | JobEvent::WorkerHeartbeat { .. }) => {}
+                Ok(event) => {
+                    if let Err(e) = db::repositories::EventRepository::append(&event).await {
+                        tracing::warn!("Failed to log event: {}", e);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event log fell behind; {} events not recorded", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+/// Spawn a worker for a queue and start tracking its heartbeats.
+async fn spawn_worker(
+    myself: &ActorRef<SupervisorMessage>,
+    state: &mut SupervisorState,
+    queue_id: QueueId,
+    queue: ActorRef<QueueMessage>,
+    handlers: Arc<JobHandlerRegistry>,
+    poll_interval: Duration,
+) -> Option<ActorRef<WorkerMessage>> {
+    let worker_id = state.next_worker_id();
+    let args = WorkerArgs {
+        worker_id: worker_id.clone(),
+        queue_id,
+        queue,
+        handlers,
+        event_tx: Some(state.event_tx.clone()),
+        supervisor: Some(myself.clone()),
+        job_permits: Some(state.job_permits.clone()),
+        poll_interval,
+    };
+
+    let (worker, _handle) = Actor::spawn(None, WorkerActor, args).await.ok()?;
+    state
+        .workers
+        .entry(queue_id)
+        .or_default()
+        .push(worker.clone());
+    state.worker_liveness.insert(
+        worker_id.clone(),
+        WorkerLiveness {
+            queue_id,
+            worker: worker.clone(),
+            deadline: Instant::now() + state.worker_heartbeat_timeout,
+            current_job: None,
+            last_heartbeat: Utc::now(),
+            last_event_forwarded: None,
+        },
+    );
+    let _ = state.event_tx.send(JobEvent::WorkerConnected {
+        worker_id,
+        queue_id,
+        timestamp: Utc::now(),
+    });
+
+    Some(worker)
+}
+
+/// Kill workers that missed their heartbeat deadline and spawn replacements.
+///
+/// A job the stalled worker held stays running until its lease expires and the
+/// queue reclaims it.
+async fn replace_stalled_workers(
+    myself: &ActorRef<SupervisorMessage>,
+    state: &mut SupervisorState,
+) {
+    let now = Instant::now();
+    let stalled: Vec<String> = state
+        .worker_liveness
+        .iter()
+        .filter(|(_, liveness)| liveness.deadline < now)
+        .map(|(worker_id, _)| worker_id.clone())
+        .collect();
+
+    for worker_id in stalled {
+        let Some(liveness) = state.worker_liveness.remove(&worker_id) else {
+            continue;
+        };
+        tracing::warn!(
+            "Worker {} stopped sending heartbeats; replacing it",
+            worker_id
+        );
+
+        liveness.worker.kill();
+        if let Some(workers) = state.workers.get_mut(&liveness.queue_id) {
+            workers.retain(|worker| worker.get_id() != liveness.worker.get_id());
+        }
+        let _ = state.event_tx.send(JobEvent::WorkerDisconnected {
+            worker_id,
+            queue_id: liveness.queue_id,
+            timestamp: Utc::now(),
+        });
+
+        let Some(queue_ref) = state.queues.get(&liveness.queue_id).cloned() else {
+            continue;
+        };
+        let (handlers, poll_interval) = match state.queue_info.get(&liveness.queue_id) {
+            Some(queue) => (
+                state.handlers_for(&queue.name),
+                queue.config.poll_interval(),
+            ),
+            None => (
+                state.handlers.clone(),
+                QueueConfig::default().poll_interval(),
+            ),
+        };
+        spawn_worker(
+            myself,
+            state,
+            liveness.queue_id,
+            queue_ref,
+            handlers,
+            poll_interval,
+        )
+        .await;
+    }
 }
 
 async fn spawn_queue_actor(
@@ -55,24 +656,26 @@ async fn spawn_queue_actor(
 ) -> Result<ActorRef<QueueMessage>, ActorProcessingErr> {
     let queue_state = QueueActorState::new(queue.clone())
         .with_supervisor(myself.clone())
-        .with_event_tx(state.event_tx.clone());
+        .with_event_tx(state.event_tx.clone())
+        .with_webhooks(state.webhooks.clone());
 
     let (actor, _handle) =
         Actor::spawn(Some(format!("queue-{}", queue.id)), QueueActor, queue_state)
             .await
             .map_err(|e| ActorProcessingErr::from(format!("Failed to spawn queue: {}", e)))?;
 
+    let handlers = state.handlers_for(&queue.name);
+    state.workers.insert(queue.id, Vec::new());
     for _ in 0..queue.config.concurrency {
-        let worker_id = state.next_worker_id();
-        let args = WorkerArgs {
-            worker_id,
-            queue_id: queue.id,
-            queue: actor.clone(),
-            handlers: state.handlers.clone(),
-            event_tx: Some(state.event_tx.clone()),
-        };
-
-        Actor::spawn(None, WorkerActor, args).await.ok();
+        spawn_worker(
+            &myself,
+            state,
+            queue.id,
+            actor.clone(),
+            handlers.clone(),
+            queue.config.poll_interval(),
+        )
+        .await;
     }
 
     state.queues.insert(queue.id, actor.clone());
@@ -87,7 +690,7 @@ pub struct Supervisor;
 impl Actor for Supervisor {
     type Msg = SupervisorMessage;
     type State = SupervisorState;
-    type Arguments = JobHandlerRegistry;
+    type Arguments = SupervisorArgs;
 
     async fn pre_start(
         &self,
@@ -108,7 +711,23 @@ impl Actor for Supervisor {
             }
         });
 
-        Ok(SupervisorState::new(args))
+        // Roll queue stats over at each boundary
+        let myself_clone = myself.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(until_next_rollover(Utc::now())).await;
+                if myself_clone
+                    .send_message(SupervisorMessage::RolloverStats)
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let state = SupervisorState::new(args)?;
+        spawn_event_recorder(state.event_tx.subscribe());
+        Ok(state)
     }
 
     async fn handle(
@@ -121,18 +740,29 @@ impl Actor for Supervisor {
             SupervisorMessage::CreateQueue {
                 name,
                 description,
+                config,
+                tags,
+                tenant_id,
                 reply,
             } => {
+                if name.trim().is_empty() {
+                    let _ = reply.send(Err("Queue name cannot be empty".into()));
+                    return Ok(());
+                }
+
                 // Check if queue already exists
                 if state.queue_info.values().any(|q| q.name == name) {
                     let _ = reply.send(Err(format!("Queue '{}' already exists", name)));
                     return Ok(());
                 }
 
-                let mut queue = Queue::new(&name);
+                let mut queue = Queue::new(&name).with_config(config).with_tags(tags);
                 if let Some(desc) = description {
                     queue = queue.with_description(desc);
                 }
+                if let Some(tenant_id) = tenant_id {
+                    queue = queue.with_tenant(tenant_id);
+                }
 
                 // Save to database
                 match db::repositories::QueueRepository::create(&queue).await {
@@ -173,19 +803,35 @@ impl Actor for Supervisor {
                 let _ = reply.send(Ok(queue));
             }
 
-            SupervisorMessage::GetQueue { queue_id, reply } => {
-                if let Some(queue_ref) = state.queues.get(&queue_id) {
-                    let (tx, rx) = ractor::concurrency::oneshot();
-                    if queue_ref
-                        .send_message(QueueMessage::GetInfo { reply: tx.into() })
-                        .is_ok()
-                        && let Ok(queue) = rx.await
-                    {
-                        let _ = reply.send(Some(queue));
-                        return Ok(());
-                    }
+            SupervisorMessage::SetQueueHandlers {
+                queue_name,
+                handlers,
+                reply,
+            } => {
+                if state.queue_info.values().any(|q| q.name == queue_name) {
+                    let _ = reply.send(Err(format!("Queue '{}' is already running", queue_name)));
+                    return Ok(());
                 }
-                let _ = reply.send(None);
+
+                let layered = handlers.layered_over(state.handlers.clone());
+                state.queue_handlers.insert(queue_name, Arc::new(layered));
+                let _ = reply.send(Ok(()));
+            }
+
+            SupervisorMessage::GetQueue { queue_id, reply } => {
+                let _ = reply.send(state.get_queue(queue_id).await);
+            }
+
+            SupervisorMessage::GetJobTypeConfig {
+                queue_id,
+                job_type,
+                reply,
+            } => {
+                let config = match state.get_queue(queue_id).await {
+                    Some(queue) => Ok(state.job_type_config(&queue, job_type)),
+                    None => Err(QUEUE_NOT_FOUND.into()),
+                };
+                let _ = reply.send(config);
             }
 
             SupervisorMessage::GetQueueByName { name, reply } => {
@@ -208,18 +854,30 @@ impl Actor for Supervisor {
             }
 
             SupervisorMessage::ListQueues { reply } => {
-                let mut queues = Vec::new();
-                for queue_ref in state.queues.values() {
-                    let (tx, rx) = ractor::concurrency::oneshot();
-                    if queue_ref
-                        .send_message(QueueMessage::GetInfo { reply: tx.into() })
-                        .is_ok()
-                        && let Ok(queue) = rx.await
-                    {
-                        queues.push(queue);
-                    }
-                }
-                let _ = reply.send(queues);
+                let _ = reply.send(state.list_queues().await);
+            }
+
+            SupervisorMessage::ListJobTypes { queue_id, reply } => {
+                let job_types = match queue_id {
+                    None => Ok(state.handlers.job_type_infos()),
+                    Some(queue_id) => match state.get_queue(queue_id).await {
+                        Some(queue) => Ok(state.job_types_for(&queue)),
+                        None => Err(QUEUE_NOT_FOUND.into()),
+                    },
+                };
+                let _ = reply.send(job_types);
+            }
+
+            SupervisorMessage::ListWorkers { reply } => {
+                let _ = reply.send(state.list_workers());
+            }
+
+            SupervisorMessage::GetStatus { reply } => {
+                let _ = reply.send(SupervisorStatus {
+                    queues: state.list_queues().await,
+                    worker_count: state.workers.values().map(Vec::len).sum(),
+                    started_at: state.started_at,
+                });
             }
 
             SupervisorMessage::PauseQueue { queue_id, reply } => {
@@ -227,7 +885,7 @@ impl Actor for Supervisor {
                     queue_ref.send_message(QueueMessage::Pause)?;
                     let _ = reply.send(Ok(()));
                 } else {
-                    let _ = reply.send(Err("Queue not found".into()));
+                    let _ = reply.send(Err(QUEUE_NOT_FOUND.into()));
                 }
             }
 
@@ -236,13 +894,78 @@ impl Actor for Supervisor {
                     queue_ref.send_message(QueueMessage::Resume)?;
                     let _ = reply.send(Ok(()));
                 } else {
-                    let _ = reply.send(Err("Queue not found".into()));
+                    let _ = reply.send(Err(QUEUE_NOT_FOUND.into()));
+                }
+            }
+
+            SupervisorMessage::PauseQueuesByTag { tag, reply } => {
+                let _ = reply.send(state.send_to_tagged(&tag, || QueueMessage::Pause));
+            }
+
+            SupervisorMessage::ResumeQueuesByTag { tag, reply } => {
+                let _ = reply.send(state.send_to_tagged(&tag, || QueueMessage::Resume));
+            }
+
+            SupervisorMessage::DrainQueue { queue_id, reply } => {
+                if let Some(queue_ref) = state.queues.get(&queue_id) {
+                    queue_ref.send_message(QueueMessage::Drain)?;
+                    let _ = reply.send(Ok(()));
+                } else {
+                    let _ = reply.send(Err(QUEUE_NOT_FOUND.into()));
                 }
             }
 
-            SupervisorMessage::DeleteQueue { queue_id, reply } => {
+            SupervisorMessage::StopQueue { queue_id, reply } => {
+                if let Some(queue_ref) = state.queues.get(&queue_id) {
+                    queue_ref.send_message(QueueMessage::Stop)?;
+                    let _ = reply.send(Ok(()));
+                } else {
+                    let _ = reply.send(Err(QUEUE_NOT_FOUND.into()));
+                }
+            }
+
+            SupervisorMessage::DeleteQueue {
+                queue_id,
+                drain: Some(timeout),
+                reply,
+            } => {
+                let Some(queue_ref) = state.queues.get(&queue_id).cloned() else {
+                    let _ = reply.send(Err(QUEUE_NOT_FOUND.into()));
+                    return Ok(());
+                };
+                queue_ref.send_message(QueueMessage::Drain)?;
+
+                // Wait off the supervisor so other queues keep being served
+                let supervisor = myself.clone();
+                tokio::spawn(async move {
+                    if !wait_for_drain(&queue_ref, timeout).await {
+                        tracing::warn!(
+                            "Queue {} did not drain within {:?}; deleting anyway",
+                            queue_id,
+                            timeout
+                        );
+                    }
+                    let _ = supervisor.send_message(SupervisorMessage::DeleteQueue {
+                        queue_id,
+                        drain: None,
+                        reply,
+                    });
+                });
+            }
+
+            SupervisorMessage::DeleteQueue {
+                queue_id,
+                drain: None,
+                reply,
+            } => {
                 if let Some(queue_ref) = state.queues.remove(&queue_id) {
+                    state
+                        .worker_liveness
+                        .retain(|_, liveness| liveness.queue_id != queue_id);
+                    // Shutting the queue down first cancels its running jobs,
+                    // so the workers settle without waiting them out
                     queue_ref.send_message(QueueMessage::Shutdown)?;
+                    drain_workers(state.workers.remove(&queue_id).unwrap_or_default()).await;
                     state.queue_info.remove(&queue_id);
 
                     // Delete from database
@@ -257,32 +980,83 @@ impl Actor for Supervisor {
 
                     let _ = reply.send(Ok(()));
                 } else {
-                    let _ = reply.send(Err("Queue not found".into()));
+                    let _ = reply.send(Err(QUEUE_NOT_FOUND.into()));
                 }
             }
 
-            SupervisorMessage::EnqueueJob {
-                queue_id,
-                job,
-                reply,
-            } => {
+            SupervisorMessage::PurgeQueue { queue_id, reply } => {
                 if let Some(queue_ref) = state.queues.get(&queue_id) {
                     let (tx, rx) = ractor::concurrency::oneshot();
-                    queue_ref.send_message(QueueMessage::Enqueue {
-                        job: Box::new(job),
-                        reply: tx.into(),
-                    })?;
+                    queue_ref.send_message(QueueMessage::Purge { reply: tx.into() })?;
                     match rx.await {
                         Ok(result) => {
                             let _ = reply.send(result);
                         }
                         Err(_) => {
-                            let _ = reply.send(Err("Failed to enqueue job".into()));
+                            let _ = reply.send(Err("Failed to purge queue".into()));
+                        }
+                    }
+                } else {
+                    let _ = reply.send(Err(QUEUE_NOT_FOUND.into()));
+                }
+            }
+
+            SupervisorMessage::RequeueRunning { queue_id, reply } => {
+                if let Some(queue_ref) = state.queues.get(&queue_id) {
+                    let (tx, rx) = ractor::concurrency::oneshot();
+                    queue_ref.send_message(QueueMessage::RequeueRunning { reply: tx.into() })?;
+                    match rx.await {
+                        Ok(requeued) => {
+                            state.wake_workers(queue_id);
+                            let _ = reply.send(Ok(requeued));
+                        }
+                        Err(_) => {
+                            let _ = reply.send(Err("Failed to requeue running jobs".into()));
                         }
                     }
                 } else {
-                    let _ = reply.send(Err("Queue not found".into()));
+                    let _ = reply.send(Err(QUEUE_NOT_FOUND.into()));
+                }
+            }
+
+            SupervisorMessage::EnqueueJob {
+                queue_id,
+                job,
+                overrides,
+                reply,
+            } => {
+                let _ = reply.send(state.enqueue(queue_id, job, overrides).await);
+            }
+
+            SupervisorMessage::TryEnqueueJob {
+                queue_id,
+                job,
+                overrides,
+                reply,
+            } => {
+                let _ = reply.send(state.try_enqueue(queue_id, job, overrides).await);
+            }
+
+            SupervisorMessage::EnqueueJobs {
+                queue_id,
+                jobs,
+                reply,
+            } => {
+                let mut enqueued = Vec::with_capacity(jobs.len());
+                let mut result = Ok(());
+                for job in jobs {
+                    match state.enqueue(queue_id, job, JobOverrides::default()).await {
+                        Ok(job) => enqueued.push(job),
+                        Err(error) => {
+                            result = Err(error);
+                            break;
+                        }
+                    }
                 }
+                let _ = reply.send(match result {
+                    Ok(()) => Ok(enqueued),
+                    Err(error) => Err(BatchEnqueueError { enqueued, error }),
+                });
             }
 
             SupervisorMessage::GetJob { job_id, reply } => {
@@ -308,6 +1082,8 @@ impl Actor for Supervisor {
                 reason,
                 reply,
             } => {
+                // The first queue that knows the job decides the outcome, so a
+                // job that exists but can't be cancelled isn't reported missing
                 for queue_ref in state.queues.values() {
                     let (tx, rx) = ractor::concurrency::oneshot();
                     if queue_ref
@@ -317,13 +1093,138 @@ impl Actor for Supervisor {
                             reply: tx.into(),
                         })
                         .is_ok()
-                        && let Ok(Ok(())) = rx.await
+                        && let Ok(result) = rx.await
+                        && result
+                            .as_ref()
+                            .err()
+                            .is_none_or(|e| e.as_str() != JOB_NOT_FOUND)
                     {
-                        let _ = reply.send(Ok(()));
+                        let _ = reply.send(result);
                         return Ok(());
                     }
                 }
-                let _ = reply.send(Err("Job not found".into()));
+                let _ = reply.send(Err(JOB_NOT_FOUND.into()));
+            }
+
+            SupervisorMessage::ReprioritizeJob {
+                job_id,
+                priority,
+                reply,
+            } => {
+                let result = state
+                    .update_job_in_queues(|reply| QueueMessage::Reprioritize {
+                        job_id,
+                        priority,
+                        reply,
+                    })
+                    .await;
+                let _ = reply.send(result);
+            }
+
+            SupervisorMessage::PauseJob { job_id, reply } => {
+                let result = state
+                    .update_job_in_queues(|reply| QueueMessage::PauseJob { job_id, reply })
+                    .await;
+                let _ = reply.send(result);
+            }
+
+            SupervisorMessage::ResumeJob { job_id, reply } => {
+                let result = state
+                    .update_job_in_queues(|reply| QueueMessage::ResumeJob { job_id, reply })
+                    .await;
+                let _ = reply.send(result);
+            }
+
+            SupervisorMessage::CancelJobs {
+                queue_id,
+                status_filter,
+                reason,
+                reply,
+            } => {
+                if let Some(queue_ref) = state.queues.get(&queue_id) {
+                    let (tx, rx) = ractor::concurrency::oneshot();
+                    queue_ref.send_message(QueueMessage::CancelJobs {
+                        status_filter,
+                        reason,
+                        reply: tx.into(),
+                    })?;
+                    match rx.await {
+                        Ok(count) => {
+                            let _ = reply.send(Ok(count));
+                        }
+                        Err(_) => {
+                            let _ = reply.send(Err("Failed to cancel jobs".into()));
+                        }
+                    }
+                } else {
+                    let _ = reply.send(Err(QUEUE_NOT_FOUND.into()));
+                }
+            }
+
+            SupervisorMessage::CancelJobsByTag {
+                queue_id,
+                tag,
+                reason,
+                reply,
+            } => {
+                let result = state
+                    .count_in_queue(queue_id, |reply| QueueMessage::CancelJobsByTag {
+                        tag,
+                        reason,
+                        reply,
+                    })
+                    .await;
+                let _ = reply.send(result);
+            }
+
+            SupervisorMessage::CancelJobsById {
+                queue_id,
+                job_ids,
+                reason,
+                reply,
+            } => {
+                let result = state
+                    .count_in_queue(queue_id, |reply| QueueMessage::CancelJobsById {
+                        job_ids,
+                        reason,
+                        reply,
+                    })
+                    .await;
+                let _ = reply.send(result);
+            }
+
+            SupervisorMessage::RetryJobsById {
+                queue_id,
+                job_ids,
+                reply,
+            } => {
+                let result = state
+                    .count_in_queue(queue_id, |reply| QueueMessage::RetryJobsById {
+                        job_ids,
+                        reply,
+                    })
+                    .await;
+                if matches!(result, Ok(retried) if retried > 0) {
+                    state.wake_workers(queue_id);
+                }
+                let _ = reply.send(result);
+            }
+
+            SupervisorMessage::RetryJobsByTag {
+                queue_id,
+                tag,
+                reply,
+            } => {
+                let result = state
+                    .count_in_queue(queue_id, |reply| QueueMessage::RetryJobsByTag {
+                        tag,
+                        reply,
+                    })
+                    .await;
+                if matches!(result, Ok(retried) if retried > 0) {
+                    state.wake_workers(queue_id);
+                }
+                let _ = reply.send(result);
             }
 
             SupervisorMessage::Subscribe { sender } => {
@@ -339,27 +1240,145 @@ impl Actor for Supervisor {
             }
 
             SupervisorMessage::BroadcastEvent { event } => {
-                let _ = state.event_tx.send(event);
+                if state.should_forward(&event) {
+                    let _ = state.event_tx.send(event);
+                }
+            }
+
+            SupervisorMessage::RolloverStats => {
+                tracing::info!("Rolling over stats for {} queues", state.queues.len());
+                for queue_ref in state.queues.values() {
+                    let (tx, rx) = ractor::concurrency::oneshot();
+                    if queue_ref
+                        .send_message(QueueMessage::RolloverStats { reply: tx.into() })
+                        .is_ok()
+                    {
+                        let _ = rx.await;
+                    }
+                }
             }
 
             SupervisorMessage::Shutdown => {
                 tracing::info!("Shutting down supervisor");
+                state.stop_queues_and_workers().await;
+                myself.stop(None);
+                return Ok(());
+            }
+
+            SupervisorMessage::ShutdownGraceful { timeout, reply } => {
+                tracing::info!(
+                    "Gracefully shutting down supervisor (timeout {:?})",
+                    timeout
+                );
                 for queue_ref in state.queues.values() {
-                    let _ = queue_ref.send_message(QueueMessage::Shutdown);
+                    let (tx, rx) = ractor::concurrency::oneshot();
+                    if queue_ref
+                        .send_message(QueueMessage::Close { reply: tx.into() })
+                        .is_ok()
+                    {
+                        let _ = rx.await;
+                    }
                 }
+
+                // Pending jobs are already persisted; jobs still running at the
+                // deadline are reset to pending when the queues are next loaded
+                let deadline = Instant::now() + timeout;
+                let left = loop {
+                    let stats = state.list_queues().await;
+                    let running: u64 = stats.iter().map(|q| q.stats.running).sum();
+                    if running == 0 || Instant::now() >= deadline {
+                        break running
+                            + stats
+                                .iter()
+                                .map(|q| q.stats.pending + q.stats.scheduled)
+                                .sum::<u64>();
+                    }
+                    tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+                };
+                tracing::info!("Shutting down with {} jobs left to run", left);
+
+                state.stop_queues_and_workers().await;
+                let _ = reply.send(left);
                 myself.stop(None);
                 return Ok(());
             }
 
+            SupervisorMessage::WorkerHeartbeat {
+                worker_id,
+                current_job,
+                busy_for,
+            } => {
+                let timeout = state.worker_heartbeat_timeout;
+                if let Some(liveness) = state.worker_liveness.get_mut(&worker_id) {
+                    liveness.deadline = Instant::now() + busy_for + timeout;
+                    liveness.current_job = current_job;
+                    liveness.last_heartbeat = Utc::now();
+                }
+            }
+
+            SupervisorMessage::SetWorkerHeartbeatTimeout { timeout } => {
+                state.worker_heartbeat_timeout = timeout;
+            }
+
+            SupervisorMessage::SetMaxConcurrentJobs { limit } => {
+                state.set_max_concurrent_jobs(limit);
+            }
+
+            SupervisorMessage::SetHeartbeatSampling { sampling } => {
+                state.heartbeat_sampling = sampling;
+            }
+
             SupervisorMessage::Tick => {
-                // Periodic housekeeping
-                // TODO: Persist state, check for stale workers, etc.
+                replace_stalled_workers(&myself, state).await;
+
+                // Periodic housekeeping: let each queue reclaim expired leases,
+                // then persist every queue's stats in one write
+                for queue_ref in state.queues.values() {
+                    let _ = queue_ref.send_message(QueueMessage::Tick);
+                }
+                let stats: Vec<(QueueId, QueueStats)> = state
+                    .list_queues()
+                    .await
+                    .into_iter()
+                    .map(|q| (q.id, q.stats))
+                    .collect();
+                if let Err(e) = db::repositories::QueueRepository::update_stats_many(&stats).await {
+                    tracing::warn!("Failed to persist queue stats: {}", e);
+                }
+
+                if let Some(retention) = state.history_retention {
+                    match db::repositories::JobRepository::prune_history(retention).await {
+                        Ok(pruned) if pruned > 0 => {
+                            tracing::info!("Pruned {} archived jobs past retention", pruned);
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::warn!("Failed to prune job history: {}", e),
+                    }
+                    match db::repositories::EventRepository::prune(retention).await {
+                        Ok(pruned) if pruned > 0 => {
+                            tracing::info!("Pruned {} logged events past retention", pruned);
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::warn!("Failed to prune event log: {}", e),
+                    }
+                }
             }
         }
 
         Ok(())
     }
 
+    async fn post_stop(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        if let Some(task) = state.retiring_permits.take() {
+            task.abort();
+        }
+        Ok(())
+    }
+
     async fn handle_supervisor_evt(
         &self,
         _myself: ActorRef<Self::Msg>,
@@ -372,18 +1391,126 @@ impl Actor for Supervisor {
                 cell.get_name().unwrap_or_default(),
                 reason
             );
-            // TODO: Restart logic if needed
+            // Workers aren't linked; stalled or dead ones are replaced on Tick
+            // once their heartbeats stop.
         }
         Ok(())
     }
 }
 
-/// Start the supervisor with the given handler registry.
+/// Start the supervisor with the given handlers and settings.
+///
+/// Queues stored in the database are restored before this returns.
 pub async fn start_supervisor(
-    handlers: JobHandlerRegistry,
+    args: SupervisorArgs,
 ) -> Result<(ActorRef<SupervisorMessage>, tokio::task::JoinHandle<()>), ractor::SpawnErr> {
-    let (actor, handle) =
-        Actor::spawn(Some("supervisor".to_string()), Supervisor, handlers).await?;
+    let (actor, handle) = Actor::spawn(Some("supervisor".to_string()), Supervisor, args).await?;
+
+    match restore_queues(&actor).await {
+        Ok(restored) if !restored.is_empty() => {
+            tracing::info!("Restored {} queues from the database", restored.len());
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to restore queues: {}", e),
+    }
 
     Ok((actor, handle))
 }
+
+/// Register every queue stored in the database that isn't running yet.
+///
+/// Each restored queue rehydrates its own jobs, so a queue without any comes
+/// back empty. Jobs whose queue row is gone are left in place and logged.
+/// Returns the queues registered.
+pub async fn restore_queues(
+    supervisor: &ActorRef<SupervisorMessage>,
+) -> Result<Vec<Queue>, String> {
+    let stored = db::repositories::QueueRepository::list()
+        .await
+        .map_err(|e| format!("Failed to load queues: {}", e))?;
+
+    let (tx, rx) = ractor::concurrency::oneshot();
+    supervisor
+        .send_message(SupervisorMessage::ListQueues { reply: tx.into() })
+        .map_err(|e| format!("Failed to list queues: {}", e))?;
+    let running: HashSet<QueueId> = rx
+        .await
+        .map_err(|_| "Failed to list queues".to_string())?
+        .into_iter()
+        .map(|queue| queue.id)
+        .collect();
+
+    match db::repositories::JobRepository::count_by_status_all_queues().await {
+        Ok(counts) => {
+            for queue_id in counts.keys() {
+                if !stored.iter().any(|queue| queue.id == *queue_id) {
+                    tracing::warn!("Found jobs for queue {} which no longer exists", queue_id);
+                }
+            }
+        }
+        Err(e) => tracing::warn!("Failed to check for orphaned jobs: {}", e),
+    }
+
+    let mut restored = Vec::new();
+    for queue in stored {
+        if running.contains(&queue.id) {
+            continue;
+        }
+        let (tx, rx) = ractor::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::RegisterQueue {
+                queue: queue.clone(),
+                reply: tx.into(),
+            })
+            .map_err(|e| format!("Failed to register queue: {}", e))?;
+        match rx.await {
+            Ok(Ok(queue)) => restored.push(queue),
+            Ok(Err(e)) => tracing::warn!("Failed to register queue {}: {}", queue.id, e),
+            Err(_) => tracing::warn!("Timeout registering queue {}", queue.id),
+        }
+    }
+    Ok(restored)
+}
+
+/// Create each queue declared in code whose name isn't registered yet.
+///
+/// Queues already restored from the database keep their stored settings, so a
+/// changed declaration only applies to fresh databases. Returns the queues created.
+pub async fn declare_queues(
+    supervisor: &ActorRef<SupervisorMessage>,
+    queues: impl IntoIterator<Item = QueueBuilder>,
+) -> Result<Vec<Queue>, String> {
+    let (tx, rx) = ractor::concurrency::oneshot();
+    supervisor
+        .send_message(SupervisorMessage::ListQueues { reply: tx.into() })
+        .map_err(|e| format!("Failed to list queues: {}", e))?;
+    let existing: HashSet<String> = rx
+        .await
+        .map_err(|_| "Failed to list queues".to_string())?
+        .into_iter()
+        .map(|queue| queue.name)
+        .collect();
+
+    let mut created = Vec::new();
+    for queue in queues.into_iter().map(QueueBuilder::build) {
+        if existing.contains(&queue.name) {
+            continue;
+        }
+        let (tx, rx) = ractor::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::CreateQueue {
+                name: queue.name,
+                description: queue.description,
+                config: queue.config,
+                tags: queue.tags,
+                tenant_id: queue.tenant_id,
+                reply: tx.into(),
+            })
+            .map_err(|e| format!("Failed to create queue: {}", e))?;
+        created.push(
+            rx.await
+                .map_err(|_| "Failed to create queue".to_string())??,
+        );
+    }
+    Ok(created)
+}
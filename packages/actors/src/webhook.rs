@@ -0,0 +1,103 @@
+//! Signed webhook notifications for finished jobs.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use queue_core::JobEvent;
+use sha2::Sha256;
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the body>` when a secret is set.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// Attempts made to deliver a webhook before it is dropped.
+const DELIVERY_ATTEMPTS: u32 = 3;
+
+/// How long a single delivery attempt may take.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pause before the first retry; doubled for each one after.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Posts finished-job events to queue webhooks.
+///
+/// Clones share the HTTP client and signing secret.
+#[derive(Clone)]
+pub struct WebhookSender {
+    client: reqwest::Client,
+    secret: Option<Arc<str>>,
+}
+
+impl WebhookSender {
+    /// Create a sender signing with `secret`; bodies go unsigned without one.
+    ///
+    /// Fails if the HTTP client can't be built.
+    pub fn new(secret: Option<String>) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            secret: secret.map(Arc::from),
+        })
+    }
+
+    /// Deliver `event` to `url` in the background.
+    ///
+    /// Failed deliveries are retried with backoff, then logged and dropped;
+    /// they never affect the job.
+    pub fn send(&self, url: String, event: &JobEvent) {
+        let body = match serde_json::to_vec(event) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!("Failed to serialize webhook for {}: {}", url, e);
+                return;
+            }
+        };
+        let signature = self
+            .secret
+            .as_deref()
+            .and_then(|secret| sign(secret, &body));
+        let client = self.client.clone();
+
+        tokio::spawn(async move {
+            let mut backoff = RETRY_BACKOFF;
+            for attempt in 1..=DELIVERY_ATTEMPTS {
+                let mut request = client
+                    .post(&url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body.clone());
+                if let Some(ref signature) = signature {
+                    request = request.header(WEBHOOK_SIGNATURE_HEADER, signature);
+                }
+
+                match request.send().await.and_then(|r| r.error_for_status()) {
+                    Ok(_) => return,
+                    Err(e) if attempt < DELIVERY_ATTEMPTS => {
+                        tracing::debug!("Webhook to {} failed, retrying: {}", url, e);
+                        tokio::time::sleep(backoff).await;
+                        backoff *= 2;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Giving up on webhook to {} after {} attempts: {}",
+                            url,
+                            DELIVERY_ATTEMPTS,
+                            e
+                        );
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Sign `body` with `secret`, formatted for the signature header.
+fn sign(secret: &str, body: &[u8]) -> Option<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).ok()?;
+    mac.update(body);
+    Some(format!(
+        "sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
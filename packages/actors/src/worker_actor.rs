@@ -1,15 +1,17 @@
 //! Worker actor for executing jobs.
 
+use std::hash::{BuildHasher, RandomState};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chrono::Utc;
 use queue_core::{Job, JobEvent, QueueId};
 use ractor::{Actor, ActorProcessingErr, ActorRef};
-use tokio::sync::broadcast;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, broadcast};
+use tokio_util::sync::CancellationToken;
 
-use crate::handler::JobHandlerRegistry;
-use crate::messages::{QueueMessage, WorkerMessage};
+use crate::handler::{HandlerFuture, HandlerResult, JobContext, JobHandlerRegistry};
+use crate::messages::{QueueMessage, SupervisorMessage, WorkerMessage};
 
 /// State for the worker actor.
 pub struct WorkerActorState {
@@ -25,6 +27,12 @@ pub struct WorkerActorState {
     pub handlers: Arc<JobHandlerRegistry>,
     /// Event broadcaster.
     pub event_tx: Option<broadcast::Sender<JobEvent>>,
+    /// Supervisor that tracks this worker's heartbeats.
+    pub supervisor: Option<ActorRef<SupervisorMessage>>,
+    /// Permits shared by all workers, one held per running job.
+    pub job_permits: Option<Arc<Semaphore>>,
+    /// Permit held for the job this worker has claimed.
+    job_permit: Option<OwnedSemaphorePermit>,
     /// Whether the worker should continue running.
     pub running: bool,
 }
@@ -44,6 +52,9 @@ impl WorkerActorState {
             queue,
             handlers,
             event_tx: None,
+            supervisor: None,
+            job_permits: None,
+            job_permit: None,
             running: true,
         }
     }
@@ -54,10 +65,99 @@ impl WorkerActorState {
         self
     }
 
+    /// Set the supervisor reference.
+    pub fn with_supervisor(mut self, supervisor: ActorRef<SupervisorMessage>) -> Self {
+        self.supervisor = Some(supervisor);
+        self
+    }
+
+    /// Set the permits shared with other workers.
+    pub fn with_job_permits(mut self, permits: Arc<Semaphore>) -> Self {
+        self.job_permits = Some(permits);
+        self
+    }
+
     /// Check if the worker is idle.
     pub fn is_idle(&self) -> bool {
         self.current_job.is_none()
     }
+
+    /// Tell the supervisor this worker is alive and may stay silent for `busy_for`.
+    fn report_heartbeat(&self, busy_for: Duration) {
+        if let Some(ref supervisor) = self.supervisor {
+            let _ = supervisor.send_message(SupervisorMessage::WorkerHeartbeat {
+                worker_id: self.worker_id.clone(),
+                current_job: self.current_job.as_ref().map(|j| j.id),
+                busy_for,
+            });
+        }
+    }
+
+    /// Ask the queue for a job if idle and a shared permit is free.
+    ///
+    /// A claimed job holds its permit until it has run.
+    async fn request_job(
+        &mut self,
+        myself: &ActorRef<WorkerMessage>,
+    ) -> Result<(), ActorProcessingErr> {
+        if self.is_idle()
+            && self.job_permit.is_none()
+            && let Ok(permit) = self
+                .job_permits
+                .clone()
+                .map(Semaphore::try_acquire_owned)
+                .transpose()
+        {
+            let timeout = std::time::Duration::from_secs(5);
+            let result = ractor::rpc::call(
+                &self.queue,
+                |reply| QueueMessage::RequestJob {
+                    worker_id: self.worker_id.clone(),
+                    reply,
+                },
+                Some(timeout),
+            )
+            .await;
+            // ractor::rpc::call returns Result<CallResult<T>, MessagingErr<M>>
+            // CallResult can be Success(T), Timeout, or SenderError
+            if let Ok(ractor::rpc::CallResult::Success(Some((job, cancel)))) = result {
+                self.job_permit = permit;
+                myself.send_message(WorkerMessage::ProcessJob {
+                    job: Box::new(job),
+                    cancel,
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Random offset within one heartbeat interval.
+///
+/// Workers spawned together would otherwise poll their queue in lockstep.
+fn heartbeat_phase(interval: Duration) -> Duration {
+    let interval_ms = interval.as_millis() as u64;
+    // A fresh `RandomState` is randomly keyed, which is all the randomness needed here
+    let random = RandomState::new().hash_one(Instant::now());
+    Duration::from_millis(random % interval_ms)
+}
+
+/// How long a cancelled handler may keep running to clean up before it is dropped.
+const CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Run a handler future, giving it `grace` to finish once `cancel` fires.
+///
+/// Returns None if the handler was still running when the grace period ended.
+async fn run_cancellable(
+    mut run: HandlerFuture,
+    cancel: CancellationToken,
+    grace: Duration,
+) -> Option<HandlerResult> {
+    tokio::select! {
+        result = &mut run => return Some(result),
+        _ = cancel.cancelled() => {}
+    }
+    tokio::time::timeout(grace, run).await.ok()
 }
 
 /// Worker actor arguments.
@@ -67,6 +167,10 @@ pub struct WorkerArgs {
     pub queue: ActorRef<QueueMessage>,
     pub handlers: Arc<JobHandlerRegistry>,
     pub event_tx: Option<broadcast::Sender<JobEvent>>,
+    pub supervisor: Option<ActorRef<SupervisorMessage>>,
+    pub job_permits: Option<Arc<Semaphore>>,
+    /// How often the worker heartbeats and polls its queue while idle.
+    pub poll_interval: Duration,
 }
 
 /// Worker actor that executes jobs.
@@ -89,12 +193,21 @@ impl Actor for WorkerActor {
         if let Some(tx) = args.event_tx {
             state = state.with_event_tx(tx);
         }
+        if let Some(supervisor) = args.supervisor {
+            state = state.with_supervisor(supervisor);
+        }
+        if let Some(permits) = args.job_permits {
+            state = state.with_job_permits(permits);
+        }
 
-        // Start the work loop
+        // Start the work loop, offset so sibling workers don't poll in lockstep
         let myself_clone = myself.clone();
+        let poll_interval = args.poll_interval.max(Duration::from_millis(1));
         tokio::spawn(async move {
+            tokio::time::sleep(heartbeat_phase(poll_interval)).await;
+            let mut interval = tokio::time::interval(poll_interval);
             loop {
-                tokio::time::sleep(Duration::from_millis(100)).await;
+                interval.tick().await;
                 if myself_clone.send_message(WorkerMessage::Heartbeat).is_err() {
                     break;
                 }
@@ -111,33 +224,73 @@ impl Actor for WorkerActor {
         state: &mut Self::State,
     ) -> Result<(), ActorProcessingErr> {
         match message {
-            WorkerMessage::ProcessJob { job } => {
+            WorkerMessage::ProcessJob { job, cancel } => {
                 let job = *job;
                 state.current_job = Some(job.clone());
 
                 // Find handler for this job type
                 if let Some(handler) = state.handlers.get(&job.job_type) {
                     let job_id = job.id;
-                    let timeout = Duration::from_secs(job.timeout_secs);
+                    let timeout = job.attempt_timeout(Utc::now());
+                    // Heartbeats stop while the job runs inline
+                    state.report_heartbeat(timeout);
+                    // Yield so the woken supervisor isn't left queued behind a
+                    // handler that blocks this thread
+                    tokio::task::yield_now().await;
 
-                    // Execute with timeout
-                    let result = tokio::time::timeout(timeout, handler.handle(&job)).await;
+                    // Execute with timeout, keeping whatever was logged even if it times out
+                    let ctx = JobContext::with_cancellation(cancel.clone());
+                    let run = run_cancellable(
+                        state.handlers.run(handler.as_ref(), &job, ctx.clone()),
+                        cancel,
+                        CANCEL_GRACE_PERIOD,
+                    );
+                    let result = tokio::time::timeout(timeout, run).await;
+                    let logs = ctx.take_logs();
 
                     match result {
-                        Ok(Ok(job_result)) => {
+                        Ok(None) => {
+                            // Cancelled, and the handler ignored the grace period
+                            tracing::warn!(
+                                "Job {} did not stop within {:?} of cancellation; aborted",
+                                job_id,
+                                CANCEL_GRACE_PERIOD
+                            );
+                            state.queue.send_message(QueueMessage::JobFailed {
+                                job_id,
+                                worker_id: state.worker_id.clone(),
+                                error: "Aborted after cancellation".into(),
+                                logs,
+                                retryable: true,
+                            })?;
+                        }
+                        Ok(Some(Ok(job_result))) => {
                             // Job succeeded
                             state.queue.send_message(QueueMessage::JobCompleted {
                                 job_id,
                                 worker_id: state.worker_id.clone(),
                                 result: job_result,
+                                logs,
+                            })?;
+                        }
+                        Ok(Some(Err(error))) => {
+                            // Job failed with error; permanent errors skip any retries
+                            state.queue.send_message(QueueMessage::JobFailed {
+                                job_id,
+                                worker_id: state.worker_id.clone(),
+                                retryable: error.is_retryable(),
+                                error: error.into_message(),
+                                logs,
                             })?;
                         }
-                        Ok(Err(error)) => {
-                            // Job failed with error
+                        Err(_) if job.is_expired(Utc::now()) => {
+                            // Out of time for good; another attempt would be cancelled anyway
                             state.queue.send_message(QueueMessage::JobFailed {
                                 job_id,
                                 worker_id: state.worker_id.clone(),
-                                error,
+                                error: "Job passed its deadline".into(),
+                                logs,
+                                retryable: false,
                             })?;
                         }
                         Err(_) => {
@@ -146,27 +299,35 @@ impl Actor for WorkerActor {
                                 job_id,
                                 worker_id: state.worker_id.clone(),
                                 error: "Job timed out".into(),
+                                logs,
+                                retryable: true,
                             })?;
                         }
                     }
                 } else {
-                    // No handler for this job type
+                    // No handler for this job type; retrying would hit the same gap
                     state.queue.send_message(QueueMessage::JobFailed {
                         job_id: job.id,
                         worker_id: state.worker_id.clone(),
                         error: format!("No handler for job type: {}", job.job_type),
+                        logs: Vec::new(),
+                        retryable: false,
                     })?;
                 }
 
                 state.current_job = None;
+                state.job_permit = None;
             }
 
             WorkerMessage::StopJob { reason } => {
+                state.job_permit = None;
                 if let Some(job) = state.current_job.take() {
                     state.queue.send_message(QueueMessage::JobFailed {
                         job_id: job.id,
                         worker_id: state.worker_id.clone(),
                         error: format!("Stopped: {}", reason),
+                        logs: Vec::new(),
+                        retryable: true,
                     })?;
                 }
             }
@@ -175,50 +336,46 @@ impl Actor for WorkerActor {
                 let _ = reply.send(state.is_idle());
             }
 
-            WorkerMessage::Shutdown => {
+            WorkerMessage::Shutdown { reply } => {
+                // Jobs run inline in `ProcessJob`, so by the time this message is
+                // handled the current job has already been reported to the queue.
                 tracing::info!("Shutting down worker: {}", state.worker_id);
                 state.running = false;
                 myself.stop(None);
+                let _ = reply.send(());
                 return Ok(());
             }
 
+            WorkerMessage::Poll => {
+                if state.running {
+                    state.request_job(&myself).await?;
+                }
+            }
+
             WorkerMessage::Heartbeat => {
                 if !state.running {
                     myself.stop(None);
                     return Ok(());
                 }
 
-                // If idle, request a job
-                if state.is_idle() {
-                    let timeout = std::time::Duration::from_secs(5);
-                    let result = ractor::rpc::call(
-                        &state.queue,
-                        |reply| QueueMessage::RequestJob {
-                            worker_id: state.worker_id.clone(),
-                            reply,
-                        },
-                        Some(timeout),
-                    )
-                    .await;
-                    // ractor::rpc::call returns Result<CallResult<T>, MessagingErr<M>>
-                    // CallResult can be Success(T), Timeout, or SenderError
-                    if let Ok(ractor::rpc::CallResult::Success(Some(job))) = result {
-                        myself.send_message(WorkerMessage::ProcessJob { job: Box::new(job) })?;
-                    }
-                }
+                state.request_job(&myself).await?;
 
-                // Broadcast heartbeat event
-                if let Some(ref tx) = state.event_tx {
-                    let _ = tx.send(JobEvent::WorkerHeartbeat {
-                        worker_id: state.worker_id.clone(),
-                        queue_id: state
-                            .current_job
-                            .as_ref()
-                            .map_or(state.queue_id, |j| j.queue_id),
-                        current_job: state.current_job.as_ref().map(|j| j.id),
-                        timestamp: Utc::now(),
-                    });
+                // Broadcast heartbeat event, through the supervisor's sampling if supervised
+                let event = JobEvent::WorkerHeartbeat {
+                    worker_id: state.worker_id.clone(),
+                    queue_id: state
+                        .current_job
+                        .as_ref()
+                        .map_or(state.queue_id, |j| j.queue_id),
+                    current_job: state.current_job.as_ref().map(|j| j.id),
+                    timestamp: Utc::now(),
+                };
+                if let Some(ref supervisor) = state.supervisor {
+                    let _ = supervisor.send_message(SupervisorMessage::BroadcastEvent { event });
+                } else if let Some(ref tx) = state.event_tx {
+                    let _ = tx.send(event);
                 }
+                state.report_heartbeat(Duration::ZERO);
             }
         }
 
@@ -0,0 +1,2510 @@
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use actors::{
+    Actor, ActorRef, ActorRegistry, EnqueueError, FnHandler, HandlerFuture, HeartbeatSampling,
+    JobContext, JobError, JobHandlerRegistry, QUEUE_NOT_FOUND, QueueActor, QueueActorState,
+    QueueMessage, SupervisorArgs, SupervisorMessage, WorkerActor, WorkerArgs, WorkerMessage,
+};
+use db::repositories::{JobFilter, JobRepository, QueueRepository};
+use queue_core::{
+    CancellationReason, Job, JobEvent, JobLogLevel, JobOverrides, JobResult, JobSource, JobStatus,
+    JobTypeInfo, Priority, Queue, QueueBuilder, QueueConfig, QueueId, QueueState,
+};
+use serde_json::json;
+use tokio::sync::broadcast;
+
+use common::TestResult;
+
+#[tokio::test]
+async fn test_stats_rollover() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("rollover")).await?;
+
+    common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+
+    let supervisor_ref = &supervisor;
+    let queue_id = queue.id;
+    let completed = common::wait_for(Duration::from_secs(5), || async move {
+        let queue = common::get_queue(supervisor_ref, queue_id).await?;
+        Ok(queue.stats.completed == 1)
+    })
+    .await?;
+    assert!(completed);
+
+    supervisor
+        .send_message(SupervisorMessage::RolloverStats)
+        .map_err(|e| e.to_string())?;
+    let live = common::get_queue(&supervisor, queue.id).await?;
+    assert_eq!(live.stats.completed, 0);
+    assert_eq!(live.stats.failed, 0);
+
+    let history = QueueRepository::stats_history(queue.id).await?;
+    assert_eq!(history.len(), 1);
+    let snapshot = history.first().ok_or("snapshot missing")?;
+    assert_eq!(snapshot.stats.completed, 1);
+    assert!(snapshot.window_start <= snapshot.window_end);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_status_change_event_on_start() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("status-events")).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let job = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+
+    let event = common::wait_for_event(&mut events, Duration::from_secs(5), |event| {
+        matches!(
+            event,
+            JobEvent::JobStatusChanged { job_id, new_status: JobStatus::Running { .. }, .. }
+                if *job_id == job.id
+        )
+    })
+    .await?;
+    let JobEvent::JobStatusChanged { old_status, .. } = event else {
+        return Err("unexpected event".into());
+    };
+    assert_eq!(old_status, JobStatus::Pending);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_bulk_cancel_pending_jobs() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 0,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("bulk-cancel").with_config(config)).await?;
+
+    for _ in 0..3 {
+        common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    }
+    assert_eq!(
+        common::get_queue(&supervisor, queue.id)
+            .await?
+            .stats
+            .pending,
+        3
+    );
+
+    let queue_id = queue.id;
+    let cancelled = common::call(&supervisor, |reply| SupervisorMessage::CancelJobs {
+        queue_id,
+        status_filter: Some("pending".into()),
+        reason: Some("bulk".into()),
+        reply,
+    })
+    .await??;
+    assert_eq!(cancelled, 3);
+
+    let live = common::get_queue(&supervisor, queue.id).await?;
+    assert_eq!(live.stats.pending, 0);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_cancel_and_retry_jobs_by_tag() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 0,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("tag-bulk").with_config(config)).await?;
+
+    let tagged = |tags: &[&str]| {
+        Job::new(queue.id, "noop", json!({}))
+            .with_tags(tags.iter().map(|t| t.to_string()).collect())
+    };
+    let bad = [
+        common::enqueue(&supervisor, tagged(&["deploy:bad"])).await?,
+        common::enqueue(&supervisor, tagged(&["deploy:bad", "team:a"])).await?,
+    ];
+    let others = [
+        common::enqueue(&supervisor, tagged(&["deploy:good"])).await?,
+        common::enqueue(&supervisor, tagged(&[])).await?,
+    ];
+
+    let queue_id = queue.id;
+    let cancelled = common::call(&supervisor, |reply| SupervisorMessage::CancelJobsByTag {
+        queue_id,
+        tag: "deploy:bad".into(),
+        reason: Some("bad deploy".into()),
+        reply,
+    })
+    .await??;
+    assert_eq!(cancelled, 2);
+    for job in &bad {
+        let status = JobRepository::get(job.id).await?.status;
+        assert!(
+            matches!(status, JobStatus::Cancelled { .. }),
+            "{:?}",
+            status
+        );
+    }
+    for job in &others {
+        assert_eq!(JobRepository::get(job.id).await?.status, JobStatus::Pending);
+    }
+    assert_eq!(
+        common::get_queue(&supervisor, queue.id)
+            .await?
+            .stats
+            .pending,
+        2
+    );
+
+    let retried = common::call(&supervisor, |reply| SupervisorMessage::RetryJobsByTag {
+        queue_id,
+        tag: "deploy:bad".into(),
+        reply,
+    })
+    .await??;
+    assert_eq!(retried, 2);
+    for job in bad.iter().chain(&others) {
+        assert_eq!(JobRepository::get(job.id).await?.status, JobStatus::Pending);
+    }
+    assert_eq!(
+        common::get_queue(&supervisor, queue.id)
+            .await?
+            .stats
+            .pending,
+        4
+    );
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_enqueue_event_precedes_stats_update() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 0,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("event-order").with_config(config)).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let job = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+
+    let is_enqueue_or_stats = |event: &JobEvent| match event {
+        JobEvent::JobEnqueued { job: enqueued, .. } => enqueued.id == job.id,
+        JobEvent::QueueStatsUpdated {
+            queue_id, stats, ..
+        } => *queue_id == queue.id && stats.pending == 1,
+        _ => false,
+    };
+
+    let first =
+        common::wait_for_event(&mut events, Duration::from_secs(5), is_enqueue_or_stats).await?;
+    assert!(matches!(first, JobEvent::JobEnqueued { .. }));
+    let second =
+        common::wait_for_event(&mut events, Duration::from_secs(5), is_enqueue_or_stats).await?;
+    assert!(matches!(second, JobEvent::QueueStatsUpdated { .. }));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_drain_stops_once_running_job_finishes() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("drain")).await?;
+    let queue_id = queue.id;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let running = common::enqueue(&supervisor, Job::new(queue_id, "slow", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == running.id),
+    )
+    .await?;
+
+    common::call(&supervisor, |reply| SupervisorMessage::DrainQueue {
+        queue_id,
+        reply,
+    })
+    .await??;
+    let draining = common::get_queue(&supervisor, queue_id).await?;
+    assert_eq!(draining.state, QueueState::Draining);
+
+    // New work is refused while draining
+    let rejected = common::enqueue(&supervisor, Job::new(queue_id, "noop", json!({}))).await;
+    assert!(rejected.is_err());
+
+    // The queue only stops after its running job has finished
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == running.id),
+    )
+    .await?;
+    let stopped = common::wait_for_event(&mut events, Duration::from_secs(5), |event| {
+        matches!(
+            event,
+            JobEvent::QueueStateChanged {
+                new_state: QueueState::Stopped,
+                ..
+            }
+        )
+    })
+    .await?;
+    assert!(matches!(
+        stopped,
+        JobEvent::QueueStateChanged {
+            old_state: QueueState::Draining,
+            ..
+        }
+    ));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_reprioritized_job_dispatches_first() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 1,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("reprioritize").with_config(config)).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    // Occupy the only worker so everything else waits in the heap
+    let blocker = common::enqueue(&supervisor, Job::new(queue.id, "slow", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == blocker.id),
+    )
+    .await?;
+
+    for _ in 0..2 {
+        common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    }
+    let low = common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "noop", json!({})).with_priority(Priority::Low),
+    )
+    .await?;
+
+    let running = common::call(&supervisor, |reply| SupervisorMessage::ReprioritizeJob {
+        job_id: blocker.id,
+        priority: Priority::Critical,
+        reply,
+    })
+    .await?;
+    assert!(running.is_err());
+
+    let bumped = common::call(&supervisor, |reply| SupervisorMessage::ReprioritizeJob {
+        job_id: low.id,
+        priority: Priority::Critical,
+        reply,
+    })
+    .await??;
+    assert_eq!(bumped.priority, Priority::Critical);
+    assert_eq!(
+        JobRepository::get(low.id).await?.priority,
+        Priority::Critical
+    );
+
+    let next = common::wait_for_event(
+        &mut events,
+        Duration::from_secs(10),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id != blocker.id),
+    )
+    .await?;
+    assert_eq!(next.job_id(), Some(low.id));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_pause_and_resume_queues_by_tag() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let batch = [
+        common::register_queue(
+            &supervisor,
+            Queue::new("nightly").with_tags(vec!["batch".into()]),
+        )
+        .await?,
+        common::register_queue(
+            &supervisor,
+            Queue::new("reports").with_tags(vec!["batch".into(), "finance".into()]),
+        )
+        .await?,
+    ];
+    let other = common::register_queue(
+        &supervisor,
+        Queue::new("interactive").with_tags(vec!["web".into()]),
+    )
+    .await?;
+
+    let paused = common::call(&supervisor, |reply| SupervisorMessage::PauseQueuesByTag {
+        tag: "batch".into(),
+        reply,
+    })
+    .await?;
+    assert_eq!(paused, 2);
+    for queue in &batch {
+        let live = common::get_queue(&supervisor, queue.id).await?;
+        assert_eq!(live.state, QueueState::Paused);
+    }
+    let untouched = common::get_queue(&supervisor, other.id).await?;
+    assert_eq!(untouched.state, QueueState::Running);
+
+    let resumed = common::call(&supervisor, |reply| SupervisorMessage::ResumeQueuesByTag {
+        tag: "batch".into(),
+        reply,
+    })
+    .await?;
+    assert_eq!(resumed, 2);
+    for queue in &batch {
+        let live = common::get_queue(&supervisor, queue.id).await?;
+        assert_eq!(live.state, QueueState::Running);
+    }
+
+    let none = common::call(&supervisor, |reply| SupervisorMessage::PauseQueuesByTag {
+        tag: "missing".into(),
+        reply,
+    })
+    .await?;
+    assert_eq!(none, 0);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_missing_handler_fails_without_retry() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    // Only a lenient queue lets the job through to a worker
+    let queue = common::register_queue(
+        &supervisor,
+        QueueBuilder::new("no-handler")
+            .accept_unknown_job_types()
+            .build(),
+    )
+    .await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let job = common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "unregistered", json!({})).with_max_retries(3),
+    )
+    .await?;
+
+    let failed = common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobFailed { job_id, .. } if *job_id == job.id),
+    )
+    .await?;
+    let JobEvent::JobFailed {
+        attempts,
+        will_retry,
+        ..
+    } = failed
+    else {
+        return Err("unexpected event".into());
+    };
+    assert_eq!(attempts, 1);
+    assert!(!will_retry);
+
+    // Nothing is queued for another attempt
+    let retried = common::wait_for_event(
+        &mut events,
+        Duration::from_millis(500),
+        |event| matches!(event, JobEvent::JobRetrying { job_id, .. } if *job_id == job.id),
+    )
+    .await;
+    assert!(retried.is_err());
+    let live = common::get_queue(&supervisor, queue.id).await?;
+    assert_eq!(live.stats.pending, 0);
+    assert_eq!(live.stats.failed, 1);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_strict_queue_rejects_unknown_job_types_at_enqueue() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let strict = common::register_queue(&supervisor, Queue::new("strict")).await?;
+    let lenient = common::register_queue(
+        &supervisor,
+        QueueBuilder::new("lenient")
+            .accept_unknown_job_types()
+            .build(),
+    )
+    .await?;
+
+    let rejected = common::call(&supervisor, |reply| SupervisorMessage::EnqueueJob {
+        queue_id: strict.id,
+        job: Job::new(strict.id, "unregistered", json!({})),
+        overrides: JobOverrides::default(),
+        reply,
+    })
+    .await?;
+    assert_eq!(
+        rejected.err(),
+        Some(EnqueueError::UnknownJobType("unregistered".into()))
+    );
+    assert_eq!(
+        common::get_queue(&supervisor, strict.id)
+            .await?
+            .stats
+            .pending,
+        0
+    );
+
+    // Known types are unaffected, and a lenient queue takes anything
+    common::enqueue(&supervisor, Job::new(strict.id, "noop", json!({}))).await?;
+    let accepted =
+        common::enqueue(&supervisor, Job::new(lenient.id, "unregistered", json!({}))).await?;
+    assert_eq!(accepted.job_type, "unregistered");
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_attempts_log_records_each_run() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let runs = Arc::new(AtomicUsize::new(0));
+    let mut handlers = common::handlers();
+    let handler_runs = runs.clone();
+    handlers.register(FnHandler::new(
+        "flaky",
+        move |_job: &Job, _ctx: JobContext| {
+            let run = handler_runs.fetch_add(1, Ordering::SeqCst) + 1;
+            Box::pin(async move {
+                if run < 3 {
+                    Err(format!("failure {}", run).into())
+                } else {
+                    Ok(JobResult::new("ok"))
+                }
+            })
+        },
+    ));
+
+    let (supervisor, handle) = common::start(handlers).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("flaky")).await?;
+    let job = common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "flaky", json!({})).with_max_retries(3),
+    )
+    .await?;
+
+    // Completed jobs are archived, keeping their attempt log
+    let job_id = job.id;
+    let archived = common::wait_for(Duration::from_secs(5), || async move {
+        Ok(JobRepository::get_history(job_id).await.is_ok())
+    })
+    .await?;
+    assert!(archived);
+
+    let attempts = JobRepository::get_history(job.id)
+        .await?
+        .record
+        .attempts_log;
+    let numbers: Vec<_> = attempts.iter().map(|a| a.attempt).collect();
+    assert_eq!(numbers, [1, 2, 3]);
+    let errors: Vec<_> = attempts.iter().map(|a| a.error.as_deref()).collect();
+    assert_eq!(errors, [Some("failure 1"), Some("failure 2"), None]);
+    for attempt in &attempts {
+        let finished_at = attempt.finished_at.ok_or("attempt left open")?;
+        assert!(finished_at >= attempt.started_at);
+        assert!(attempt.worker_id.starts_with("worker-"));
+    }
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_permanent_error_fails_without_retry() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let mut handlers = common::handlers();
+    handlers.register(FnHandler::new(
+        "validate",
+        |_job: &Job, _ctx: JobContext| {
+            Box::pin(async { Err(JobError::permanent("missing field: email")) })
+        },
+    ));
+    let (supervisor, handle) = common::start(handlers).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("permanent")).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let job = common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "validate", json!({})).with_max_retries(5),
+    )
+    .await?;
+
+    let failed = common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobFailed { job_id, .. } if *job_id == job.id),
+    )
+    .await?;
+    let JobEvent::JobFailed {
+        error,
+        attempts,
+        will_retry,
+        ..
+    } = failed
+    else {
+        return Err("unexpected event".into());
+    };
+    assert_eq!(error, "missing field: email");
+    assert_eq!(attempts, 1);
+    assert!(!will_retry);
+
+    let retried = common::wait_for_event(
+        &mut events,
+        Duration::from_millis(500),
+        |event| matches!(event, JobEvent::JobRetrying { job_id, .. } if *job_id == job.id),
+    )
+    .await;
+    assert!(retried.is_err());
+    let live = common::get_queue(&supervisor, queue.id).await?;
+    assert_eq!(live.stats.pending, 0);
+    assert_eq!(live.stats.failed, 1);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_purge_keeps_running_jobs() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 1,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("purge").with_config(config)).await?;
+
+    let running = common::enqueue(&supervisor, Job::new(queue.id, "slow", json!({}))).await?;
+    let supervisor_ref = &supervisor;
+    let queue_id = queue.id;
+    let started = common::wait_for(Duration::from_secs(5), || async move {
+        let queue = common::get_queue(supervisor_ref, queue_id).await?;
+        Ok(queue.stats.running == 1)
+    })
+    .await?;
+    assert!(started);
+
+    for _ in 0..2 {
+        common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    }
+
+    let purged = common::call(&supervisor, |reply| SupervisorMessage::PurgeQueue {
+        queue_id,
+        reply,
+    })
+    .await??;
+    assert_eq!(purged, 2);
+
+    let live = common::get_queue(&supervisor, queue.id).await?;
+    assert_eq!(live.stats.pending, 0);
+    assert_eq!(live.stats.running, 1);
+
+    let remaining = JobRepository::list(JobFilter {
+        queue_id: Some(queue.id),
+        ..Default::default()
+    })
+    .await?;
+    assert_eq!(remaining.len(), 1);
+    assert!(remaining.iter().all(|job| job.id == running.id));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_enqueue_rejects_payload_failing_schema() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let mut handlers = common::handlers();
+    handlers.register(
+        FnHandler::new("greet", |_job: &Job, _ctx: JobContext| {
+            Box::pin(async { Ok(JobResult::new("ok")) })
+        })
+        .with_schema(json!({
+            "type": "object",
+            "required": ["message"],
+            "properties": { "message": { "type": "string" } }
+        })),
+    );
+    let (supervisor, handle) = common::start(handlers).await?;
+    let config = QueueConfig {
+        concurrency: 0,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("schema").with_config(config)).await?;
+
+    let rejected = common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "greet", json!({ "message": 42 })),
+    )
+    .await;
+    let err = rejected.err().ok_or("bad payload was accepted")?;
+    assert!(err.to_string().contains("/message"));
+
+    common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "greet", json!({ "message": "hi" })),
+    )
+    .await?;
+    assert_eq!(
+        common::get_queue(&supervisor, queue.id)
+            .await?
+            .stats
+            .pending,
+        1
+    );
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_enqueue_inherits_queue_defaults() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 0,
+        default_timeout_secs: 60,
+        default_max_retries: 7,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("defaults").with_config(config)).await?;
+
+    let job = common::enqueue_with(
+        &supervisor,
+        Job::new(queue.id, "noop", json!({})),
+        JobOverrides::default(),
+    )
+    .await?;
+    assert_eq!(job.timeout_secs, 60);
+    assert_eq!(job.max_retries, 7);
+
+    let pinned = common::enqueue_with(
+        &supervisor,
+        Job::new(queue.id, "noop", json!({})),
+        JobOverrides {
+            timeout_secs: Some(5),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(pinned.timeout_secs, 5);
+    assert_eq!(pinned.max_retries, 7);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_wait_for_supervisor_parks_until_registered() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let registry = Arc::new(ActorRegistry::new());
+
+    assert!(
+        registry
+            .wait_for_supervisor(Duration::from_millis(50))
+            .await
+            .is_none()
+    );
+
+    let waiter = tokio::spawn({
+        let registry = registry.clone();
+        async move { registry.wait_for_supervisor(Duration::from_secs(5)).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!waiter.is_finished());
+
+    registry.register_supervisor(supervisor.clone());
+    let found = waiter.await?.ok_or("waiter gave up before registration")?;
+    assert_eq!(found.get_id(), supervisor.get_id());
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_worker_shutdown_ack() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 0,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("worker-ack").with_config(config)).await?;
+    let queue_ref: ActorRef<QueueMessage> =
+        ractor::registry::where_is(format!("queue-{}", queue.id))
+            .ok_or("queue actor not registered")?
+            .into();
+
+    let args = WorkerArgs {
+        worker_id: "ack-worker".into(),
+        queue_id: queue.id,
+        queue: queue_ref,
+        handlers: Arc::new(common::handlers()),
+        event_tx: None,
+        supervisor: None,
+        job_permits: None,
+        poll_interval: Duration::from_millis(100),
+    };
+    let (worker, worker_handle) = Actor::spawn(None, WorkerActor, args).await?;
+
+    common::call(&worker, |reply| WorkerMessage::Shutdown { reply }).await?;
+    tokio::time::timeout(Duration::from_secs(5), worker_handle).await??;
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_per_queue_handler_override() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+
+    let mut overrides = JobHandlerRegistry::new();
+    overrides.register(FnHandler::new("noop", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async { Ok(JobResult::new("override")) })
+    }));
+    common::call(&supervisor, |reply| SupervisorMessage::SetQueueHandlers {
+        queue_name: "custom".into(),
+        handlers: overrides,
+        reply,
+    })
+    .await??;
+
+    let global = common::register_queue(&supervisor, Queue::new("global")).await?;
+    let custom = common::register_queue(&supervisor, Queue::new("custom")).await?;
+
+    let global_job = common::enqueue(&supervisor, Job::new(global.id, "noop", json!({}))).await?;
+    let custom_job = common::enqueue(&supervisor, Job::new(custom.id, "noop", json!({}))).await?;
+
+    let supervisor_ref = &supervisor;
+    let summary_of = |job_id| async move {
+        let job = common::call(supervisor_ref, |reply| SupervisorMessage::GetJob {
+            job_id,
+            reply,
+        })
+        .await?;
+        Ok::<_, Box<dyn std::error::Error>>(match job.map(|j| j.status) {
+            Some(JobStatus::Completed { result, .. }) => Some(result.summary),
+            _ => None,
+        })
+    };
+
+    let done =
+        common::wait_for(Duration::from_secs(5), || async move {
+            Ok(summary_of(global_job.id).await?.is_some()
+                && summary_of(custom_job.id).await?.is_some())
+        })
+        .await?;
+    assert!(done);
+
+    assert_eq!(summary_of(global_job.id).await?.as_deref(), Some("ok"));
+    assert_eq!(
+        summary_of(custom_job.id).await?.as_deref(),
+        Some("override")
+    );
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_list_job_types_for_queue() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+
+    let mut overrides = JobHandlerRegistry::new();
+    overrides.register(FnHandler::new("render", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async { Ok(JobResult::new("rendered")) })
+    }));
+    common::call(&supervisor, |reply| SupervisorMessage::SetQueueHandlers {
+        queue_name: "reports".into(),
+        handlers: overrides,
+        reply,
+    })
+    .await??;
+    let config = QueueConfig {
+        allowed_job_types: Some(vec!["render".into(), "noop".into()]),
+        ..Default::default()
+    };
+    let reports =
+        common::register_queue(&supervisor, Queue::new("reports").with_config(config)).await?;
+
+    let names = |infos: Vec<JobTypeInfo>| -> Vec<String> {
+        infos.into_iter().map(|info| info.job_type).collect()
+    };
+
+    let global = common::call(&supervisor, |reply| SupervisorMessage::ListJobTypes {
+        queue_id: None,
+        reply,
+    })
+    .await??;
+    assert_eq!(names(global), ["noop", "slow"]);
+
+    // The queue's own handler is listed and the global one it doesn't allow isn't
+    let scoped = common::call(&supervisor, |reply| SupervisorMessage::ListJobTypes {
+        queue_id: Some(reports.id),
+        reply,
+    })
+    .await??;
+    assert_eq!(names(scoped), ["noop", "render"]);
+
+    let missing = common::call(&supervisor, |reply| SupervisorMessage::ListJobTypes {
+        queue_id: Some(QueueId::new()),
+        reply,
+    })
+    .await?;
+    assert_eq!(missing.err().as_deref(), Some(QUEUE_NOT_FOUND));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_handler_logs_archived_with_job() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let mut handlers = common::handlers();
+    handlers.register(FnHandler::new("chatty", |_job: &Job, ctx: JobContext| {
+        Box::pin(async move {
+            ctx.info("starting");
+            ctx.warn("almost done");
+            Ok(JobResult::new("ok"))
+        })
+    }));
+    let (supervisor, handle) = common::start(handlers).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("chatty")).await?;
+
+    let job = common::enqueue(&supervisor, Job::new(queue.id, "chatty", json!({}))).await?;
+
+    let job_id = job.id;
+    let archived = common::wait_for(Duration::from_secs(5), || async move {
+        let found = JobRepository::find_anywhere(job_id).await?;
+        Ok(found
+            .first()
+            .is_some_and(|view| view.source == JobSource::History))
+    })
+    .await?;
+    assert!(archived);
+
+    let found = JobRepository::find_anywhere(job.id).await?;
+    let view = found.first().ok_or("archived job not found")?;
+    let lines: Vec<(JobLogLevel, &str)> = view
+        .logs
+        .iter()
+        .map(|line| (line.level, line.message.as_str()))
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            (JobLogLevel::Info, "starting"),
+            (JobLogLevel::Warn, "almost done")
+        ]
+    );
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_middleware_wraps_handler() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let durations = Arc::new(Mutex::new(Vec::new()));
+    let mut handlers = common::handlers();
+
+    let handler_calls = calls.clone();
+    handlers.register(FnHandler::new(
+        "timed",
+        move |_job: &Job, _ctx: JobContext| {
+            let calls = handler_calls.clone();
+            Box::pin(async move {
+                calls.lock().unwrap().push("handler");
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(JobResult::new("ok"))
+            })
+        },
+    ));
+
+    let (middleware_calls, middleware_durations) = (calls.clone(), durations.clone());
+    handlers.register_middleware(move |_job: &Job, next: HandlerFuture| -> HandlerFuture {
+        let (calls, durations) = (middleware_calls.clone(), middleware_durations.clone());
+        Box::pin(async move {
+            calls.lock().unwrap().push("before");
+            let started = std::time::Instant::now();
+            let result = next.await;
+            durations.lock().unwrap().push(started.elapsed());
+            calls.lock().unwrap().push("after");
+            result
+        })
+    });
+
+    let (supervisor, handle) = common::start(handlers).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("timed")).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let job = common::enqueue(&supervisor, Job::new(queue.id, "timed", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == job.id),
+    )
+    .await?;
+
+    assert_eq!(*calls.lock().unwrap(), ["before", "handler", "after"]);
+    let durations = durations.lock().unwrap().clone();
+    assert_eq!(durations.len(), 1);
+    assert!(durations[0] >= Duration::from_millis(50));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_blocking_handler_leaves_async_jobs_running() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let finished = Arc::new(AtomicBool::new(false));
+    let mut handlers = common::handlers();
+
+    // Spins without yielding; inline, it would stall this single-threaded runtime.
+    let spin_finished = finished.clone();
+    handlers.register(
+        FnHandler::new("spin", move |_job: &Job, _ctx: JobContext| {
+            let finished = spin_finished.clone();
+            Box::pin(async move {
+                let started = std::time::Instant::now();
+                while started.elapsed() < Duration::from_secs(2) {
+                    std::hint::spin_loop();
+                }
+                finished.store(true, Ordering::SeqCst);
+                Ok(JobResult::new("spun"))
+            })
+        })
+        .blocking(),
+    );
+
+    let (supervisor, handle) = common::start(handlers).await?;
+    let spin_queue = common::register_queue(&supervisor, Queue::new("spin")).await?;
+    let async_queue = common::register_queue(&supervisor, Queue::new("async")).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let spin = common::enqueue(&supervisor, Job::new(spin_queue.id, "spin", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == spin.id),
+    )
+    .await?;
+
+    let quick = common::enqueue(&supervisor, Job::new(async_queue.id, "noop", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == quick.id),
+    )
+    .await?;
+    assert!(!finished.load(Ordering::SeqCst));
+
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == spin.id),
+    )
+    .await?;
+    assert!(finished.load(Ordering::SeqCst));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_create_queue_applies_config() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 2,
+        max_queue_size: Some(10),
+        ..Default::default()
+    };
+
+    let queue = common::call(&supervisor, |reply| SupervisorMessage::CreateQueue {
+        name: "configured".into(),
+        description: None,
+        config: config.clone(),
+        tags: vec!["batch".into()],
+        tenant_id: None,
+        reply,
+    })
+    .await??;
+    assert_eq!(queue.config, config);
+
+    let stored = QueueRepository::get(queue.id).await?;
+    assert_eq!(stored.config, config);
+    assert_eq!(stored.tags, vec!["batch".to_string()]);
+
+    let status = common::call(&supervisor, |reply| SupervisorMessage::GetStatus { reply }).await?;
+    assert_eq!(status.worker_count, 2);
+
+    let empty = common::call(&supervisor, |reply| SupervisorMessage::CreateQueue {
+        name: "  ".into(),
+        description: None,
+        config: QueueConfig::default(),
+        tags: Vec::new(),
+        tenant_id: None,
+        reply,
+    })
+    .await?;
+    assert!(empty.is_err());
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_delete_queue_waits_only_when_draining() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let mut handlers = common::handlers();
+    handlers.register(FnHandler::new(
+        "cooperative",
+        |_job: &Job, ctx: JobContext| {
+            Box::pin(async move {
+                tokio::select! {
+                    _ = ctx.cancelled() => Err("cancelled".into()),
+                    _ = tokio::time::sleep(Duration::from_secs(30)) => {
+                        Ok(JobResult::new("finished"))
+                    }
+                }
+            })
+        },
+    ));
+    let (supervisor, handle) = common::start(handlers).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    // Delete each queue while its job runs: the drained one lets its
+    // two-second job finish, the other cancels its job instead of waiting
+    let cases = [
+        ("delete-drained", "slow", Some(Duration::from_secs(4))),
+        ("delete-now", "cooperative", None),
+    ];
+    let mut deleted = Vec::new();
+    for (name, job_type, drain) in cases {
+        let queue = common::register_queue(&supervisor, Queue::new(name)).await?;
+        let job = common::enqueue(&supervisor, Job::new(queue.id, job_type, json!({}))).await?;
+        common::wait_for_event(
+            &mut events,
+            Duration::from_secs(5),
+            |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == job.id),
+        )
+        .await?;
+
+        let started = std::time::Instant::now();
+        common::call(&supervisor, |reply| SupervisorMessage::DeleteQueue {
+            queue_id: queue.id,
+            drain,
+            reply,
+        })
+        .await??;
+        deleted.push((job.id, started.elapsed()));
+    }
+
+    let (drained_job, waited) = deleted[0];
+    assert!(
+        waited >= Duration::from_secs(1),
+        "returned after {:?}",
+        waited
+    );
+    let archived = JobRepository::get_history(drained_job).await?;
+    assert_eq!(archived.record.status, "completed");
+
+    let (cancelled_job, waited) = deleted[1];
+    assert!(
+        waited < Duration::from_secs(2),
+        "returned after {:?}",
+        waited
+    );
+    assert!(JobRepository::get_history(cancelled_job).await.is_err());
+
+    let queues = common::call(&supervisor, |reply| SupervisorMessage::ListQueues { reply }).await?;
+    assert!(queues.is_empty());
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_cancel_lets_handler_clean_up() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let cleaned_up = Arc::new(AtomicBool::new(false));
+    let flag = cleaned_up.clone();
+    let mut handlers = common::handlers();
+    handlers.register(FnHandler::new(
+        "cooperative",
+        move |_job: &Job, ctx: JobContext| {
+            let flag = flag.clone();
+            Box::pin(async move {
+                tokio::select! {
+                    _ = ctx.cancelled() => {
+                        ctx.info("cleaning up");
+                        flag.store(true, Ordering::SeqCst);
+                        Err("cancelled".into())
+                    }
+                    _ = tokio::time::sleep(Duration::from_secs(30)) => {
+                        Ok(JobResult::new("finished"))
+                    }
+                }
+            })
+        },
+    ));
+    let (supervisor, handle) = common::start(handlers).await?;
+    let config = QueueConfig {
+        concurrency: 1,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("cooperative").with_config(config)).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let job = common::enqueue(&supervisor, Job::new(queue.id, "cooperative", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == job.id),
+    )
+    .await?;
+
+    common::call(&supervisor, |reply| SupervisorMessage::CancelJob {
+        job_id: job.id,
+        reason: Some("shutting down".into()),
+        reply,
+    })
+    .await??;
+
+    let flag = &cleaned_up;
+    let cleaned = common::wait_for(Duration::from_secs(2), || async move {
+        Ok(flag.load(Ordering::SeqCst))
+    })
+    .await?;
+    assert!(cleaned);
+
+    // The only worker is free again long before the handler's 30s sleep ends
+    let next = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == next.id),
+    )
+    .await?;
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_get_stats_reflects_pending_priority() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let queue = Queue::new("live-stats");
+    // No workers are attached, so the job stays pending
+    let (queue_ref, queue_handle) =
+        Actor::spawn(None, QueueActor, QueueActorState::new(queue.clone())).await?;
+
+    let job = Job::new(queue.id, "noop", json!({})).with_priority(Priority::High);
+    let overrides = JobOverrides::from_job(&job);
+    common::call(&queue_ref, |reply| QueueMessage::Enqueue {
+        job: Box::new(job),
+        overrides,
+        reply,
+    })
+    .await??;
+
+    let stats = common::call(&queue_ref, |reply| QueueMessage::GetStats { reply }).await?;
+    assert_eq!(stats.pending, 1);
+    assert_eq!(stats.pending_by_priority.get(Priority::High), 1);
+    assert_eq!(stats.pending_by_priority.get(Priority::Normal), 0);
+    assert!(stats.oldest_pending_age_ms.is_some());
+
+    queue_ref
+        .send_message(QueueMessage::Shutdown)
+        .map_err(|e| e.to_string())?;
+    queue_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_priority_boost() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let config = QueueConfig {
+        retry_priority_boost: 1,
+        ..Default::default()
+    };
+    let queue = Queue::new("retry-boost").with_config(config);
+    // No workers are attached; jobs are handed out by explicit requests
+    let (queue_ref, queue_handle) =
+        Actor::spawn(None, QueueActor, QueueActorState::new(queue.clone())).await?;
+    let queue_ref = &queue_ref;
+    let enqueue = |job: Job| async move {
+        let overrides = JobOverrides::from_job(&job);
+        let enqueued = common::call(queue_ref, |reply| QueueMessage::Enqueue {
+            job: Box::new(job),
+            overrides,
+            reply,
+        })
+        .await??;
+        Ok::<_, Box<dyn std::error::Error>>(enqueued)
+    };
+    let next = || async move {
+        common::call(queue_ref, |reply| QueueMessage::RequestJob {
+            worker_id: "worker-1".into(),
+            reply,
+        })
+        .await
+        .map(|next| next.map(|(job, _)| job.id))
+    };
+
+    let flaky = enqueue(Job::new(queue.id, "noop", json!({}))).await?;
+    assert_eq!(next().await?, Some(flaky.id));
+
+    // Newer work arrives while the first attempt runs
+    let urgent =
+        enqueue(Job::new(queue.id, "noop", json!({})).with_priority(Priority::High)).await?;
+    let routine = enqueue(Job::new(queue.id, "noop", json!({}))).await?;
+
+    queue_ref
+        .send_message(QueueMessage::JobFailed {
+            job_id: flaky.id,
+            worker_id: "worker-1".into(),
+            error: "connection reset".into(),
+            logs: Vec::new(),
+            retryable: true,
+        })
+        .map_err(|e| e.to_string())?;
+
+    // The boost stays with the job through a pause and resume
+    let paused = common::call(queue_ref, |reply| QueueMessage::PauseJob {
+        job_id: flaky.id,
+        reply,
+    })
+    .await??;
+    assert_eq!(paused.priority_boost, 1);
+    common::call(queue_ref, |reply| QueueMessage::ResumeJob {
+        job_id: flaky.id,
+        reply,
+    })
+    .await??;
+
+    // Boosted to High, the retry ties with the newer High job and wins as the older one
+    assert_eq!(next().await?, Some(flaky.id));
+    assert_eq!(next().await?, Some(urgent.id));
+    assert_eq!(next().await?, Some(routine.id));
+
+    queue_ref
+        .send_message(QueueMessage::Shutdown)
+        .map_err(|e| e.to_string())?;
+    queue_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reclaim_event_on_lease_expiry() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (event_tx, mut events) = broadcast::channel(64);
+    let queue = Queue::new("lease-expiry");
+    let state = QueueActorState::new(queue.clone())
+        .with_event_tx(event_tx)
+        .with_lease_grace(Duration::ZERO);
+    let (queue_ref, queue_handle) = Actor::spawn(None, QueueActor, state).await?;
+
+    let job = Job::new(queue.id, "noop", json!({})).with_timeout(1);
+    let overrides = JobOverrides::from_job(&job);
+    let job = common::call(&queue_ref, |reply| QueueMessage::Enqueue {
+        job: Box::new(job),
+        overrides,
+        reply,
+    })
+    .await??;
+
+    // Claim the job as a worker that never reports back
+    let (_, cancel) = common::call(&queue_ref, |reply| QueueMessage::RequestJob {
+        worker_id: "dead-worker".into(),
+        reply,
+    })
+    .await?
+    .ok_or("job not handed out")?;
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    queue_ref
+        .send_message(QueueMessage::Tick)
+        .map_err(|e| e.to_string())?;
+
+    let event = common::wait_for_event(&mut events, Duration::from_secs(5), |event| {
+        matches!(event, JobEvent::JobReclaimed { .. })
+    })
+    .await?;
+    let JobEvent::JobReclaimed {
+        job_id, worker_id, ..
+    } = event
+    else {
+        return Err("unexpected event".into());
+    };
+    assert_eq!(job_id, job.id);
+    assert_eq!(worker_id, "dead-worker");
+    assert!(cancel.is_cancelled());
+
+    let reclaimed = common::call(&queue_ref, |reply| QueueMessage::GetJob {
+        job_id: job.id,
+        reply,
+    })
+    .await?
+    .ok_or("job missing")?;
+    assert_eq!(reclaimed.status, JobStatus::Pending);
+
+    // A late report from the dead worker is ignored
+    queue_ref
+        .send_message(QueueMessage::JobCompleted {
+            job_id: job.id,
+            worker_id: "dead-worker".into(),
+            result: JobResult::new("late"),
+            logs: Vec::new(),
+        })
+        .map_err(|e| e.to_string())?;
+    let after = common::call(&queue_ref, |reply| QueueMessage::GetJob {
+        job_id: job.id,
+        reply,
+    })
+    .await?
+    .ok_or("job missing")?;
+    assert_eq!(after.status, JobStatus::Pending);
+
+    queue_ref
+        .send_message(QueueMessage::Shutdown)
+        .map_err(|e| e.to_string())?;
+    queue_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_duplicate_handler_registration() -> TestResult {
+    let mut handlers = JobHandlerRegistry::new();
+    let first = handlers.register(FnHandler::new("dup", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async { Ok(JobResult::new("first")) })
+    }));
+    assert!(first.is_none());
+
+    // `register` replaces (and warns), handing back the shadowed handler
+    let replaced = handlers.register(FnHandler::new("dup", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async { Ok(JobResult::new("second")) })
+    }));
+    assert!(replaced.is_some());
+
+    let job = Job::new(QueueId::new(), "dup", json!({}));
+    let handler = handlers.get("dup").ok_or("handler missing")?;
+    let result = handler.handle(&job, JobContext::new()).await?;
+    assert_eq!(result.summary, "second");
+
+    // `try_register` refuses to shadow an existing handler
+    let duplicate = handlers.try_register(FnHandler::new("dup", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async { Ok(JobResult::new("third")) })
+    }));
+    assert!(duplicate.is_err());
+    let handler = handlers.get("dup").ok_or("handler missing")?;
+    let result = handler.handle(&job, JobContext::new()).await?;
+    assert_eq!(result.summary, "second");
+
+    // Overriding a fallback registry's handler is not a duplicate
+    let mut layered = JobHandlerRegistry::new().layered_over(Arc::new(handlers));
+    layered.try_register(FnHandler::new("dup", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async { Ok(JobResult::new("override")) })
+    }))?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_stalled_worker_is_replaced() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let mut handlers = common::handlers();
+    handlers.register(FnHandler::new("stall", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async {
+            // Block the thread so the worker can neither heartbeat nor time out
+            std::thread::sleep(Duration::from_secs(4));
+            Ok(JobResult::new("unstuck"))
+        })
+    }));
+    let (supervisor, handle) = common::start(handlers).await?;
+    supervisor
+        .send_message(SupervisorMessage::SetWorkerHeartbeatTimeout {
+            timeout: Duration::from_millis(500),
+        })
+        .map_err(|e| e.to_string())?;
+
+    let config = QueueConfig {
+        concurrency: 1,
+        ..Default::default()
+    };
+    let queue = common::register_queue(
+        &supervisor,
+        Queue::new("stalled-worker").with_config(config),
+    )
+    .await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "stall", json!({})).with_timeout(1),
+    )
+    .await?;
+    let started = common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobStarted { queue_id, .. } if *queue_id == queue.id),
+    )
+    .await?;
+    let JobEvent::JobStarted {
+        worker_id: stalled, ..
+    } = started
+    else {
+        return Err("expected JobStarted".into());
+    };
+
+    // Past the job's timeout plus the heartbeat timeout
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    supervisor
+        .send_message(SupervisorMessage::Tick)
+        .map_err(|e| e.to_string())?;
+
+    common::wait_for_event(&mut events, Duration::from_secs(5), |event| {
+        matches!(event, JobEvent::WorkerDisconnected { worker_id, .. } if *worker_id == stalled)
+    })
+    .await?;
+    let connected = common::wait_for_event(&mut events, Duration::from_secs(5), |event| {
+        matches!(event, JobEvent::WorkerConnected { queue_id, .. } if *queue_id == queue.id)
+    })
+    .await?;
+    let JobEvent::WorkerConnected {
+        worker_id: replacement,
+        ..
+    } = connected
+    else {
+        return Err("expected WorkerConnected".into());
+    };
+    assert_ne!(replacement, stalled);
+
+    let status = common::call(&supervisor, |reply| SupervisorMessage::GetStatus { reply }).await?;
+    assert_eq!(status.worker_count, 1);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_worker_heartbeats_are_spread_across_interval() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let workers = 8;
+    let config = QueueConfig {
+        concurrency: workers,
+        ..Default::default()
+    };
+    let queue = common::register_queue(
+        &supervisor,
+        Queue::new("heartbeat-jitter").with_config(config),
+    )
+    .await?;
+
+    // The first heartbeat seen from each worker lands at that worker's phase
+    let mut first_seen = HashMap::new();
+    while first_seen.len() < workers as usize {
+        let event = common::wait_for_event(&mut events, Duration::from_secs(5), |event| {
+            matches!(event, JobEvent::WorkerHeartbeat { queue_id, .. } if *queue_id == queue.id)
+        })
+        .await?;
+        if let JobEvent::WorkerHeartbeat {
+            worker_id,
+            timestamp,
+            ..
+        } = event
+        {
+            first_seen.entry(worker_id).or_insert(timestamp);
+        }
+    }
+
+    // Without jitter all workers tick within a few milliseconds of each other;
+    // eight uniform phases over 100ms span under 20ms with probability ~1e-4.
+    let earliest = first_seen.values().min().ok_or("no heartbeats")?;
+    let latest = first_seen.values().max().ok_or("no heartbeats")?;
+    let spread = (*latest - *earliest).num_milliseconds();
+    assert!(spread >= 20, "heartbeats clustered within {}ms", spread);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_heartbeat_events_are_throttled_per_worker() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start_with(SupervisorArgs {
+        heartbeat_sampling: HeartbeatSampling::Every(Duration::from_millis(400)),
+        ..SupervisorArgs::new(common::handlers())
+    })
+    .await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    // Workers heartbeat every 10ms, so ~100 events a second reach the supervisor
+    let queue = common::register_queue(
+        &supervisor,
+        QueueBuilder::new("heartbeat-sampling")
+            .concurrency(1)
+            .poll_interval(Duration::from_millis(10))
+            .build(),
+    )
+    .await?;
+
+    let window = Duration::from_millis(1000);
+    let deadline = tokio::time::Instant::now() + window;
+    let mut forwarded = 0;
+    while let Ok(event) = tokio::time::timeout_at(deadline, events.recv()).await {
+        if matches!(event?, JobEvent::WorkerHeartbeat { queue_id, .. } if queue_id == queue.id) {
+            forwarded += 1;
+        }
+    }
+
+    // At most one per 400ms window: t=0, ~400ms and ~800ms
+    assert!(
+        (1..=3).contains(&forwarded),
+        "forwarded {} heartbeats in {:?}",
+        forwarded,
+        window
+    );
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_scheduled_job_waits_and_can_be_cancelled() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("scheduled")).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let later = common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "noop", json!({})).with_delay(Duration::from_secs(3600)),
+    )
+    .await?;
+    let soon = common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "noop", json!({})).with_delay(Duration::from_millis(300)),
+    )
+    .await?;
+
+    // The short delay elapses and that job runs; the long one stays scheduled
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == soon.id),
+    )
+    .await?;
+    let waiting = common::call(&supervisor, |reply| SupervisorMessage::GetJob {
+        job_id: later.id,
+        reply,
+    })
+    .await?
+    .ok_or("scheduled job not found")?;
+    assert!(matches!(waiting.status, JobStatus::Scheduled { .. }));
+    let stats = common::get_queue(&supervisor, queue.id).await?.stats;
+    assert_eq!(stats.pending, 0);
+    assert_eq!(stats.scheduled, 1);
+
+    common::call(&supervisor, |reply| SupervisorMessage::CancelJob {
+        job_id: later.id,
+        reason: Some("no longer needed".into()),
+        reply,
+    })
+    .await??;
+    let cancelled = common::call(&supervisor, |reply| SupervisorMessage::GetJob {
+        job_id: later.id,
+        reply,
+    })
+    .await?
+    .ok_or("cancelled job not found")?;
+    assert!(matches!(cancelled.status, JobStatus::Cancelled { .. }));
+    let stats = common::get_queue(&supervisor, queue.id).await?.stats;
+    assert_eq!(stats.pending, 0);
+    assert_eq!(stats.scheduled, 0);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_job_past_deadline_is_cancelled_not_run() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let runs = Arc::new(AtomicUsize::new(0));
+    let mut handlers = common::handlers();
+    let handler_runs = runs.clone();
+    handlers.register(FnHandler::new(
+        "counted",
+        move |_job: &Job, _ctx: JobContext| {
+            handler_runs.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Ok(JobResult::new("ran")) })
+        },
+    ));
+    let (supervisor, handle) = common::start(handlers).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("deadlines")).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let deadline = chrono::Utc::now() - chrono::Duration::minutes(1);
+    let expired = common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "counted", json!({})).with_deadline(deadline),
+    )
+    .await?;
+
+    let cancelled = common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCancelled { job_id, .. } if *job_id == expired.id),
+    )
+    .await?;
+    assert!(matches!(
+        cancelled,
+        JobEvent::JobCancelled {
+            reason: Some(CancellationReason::Expired),
+            ..
+        }
+    ));
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+    let stored = JobRepository::get(expired.id).await?;
+    assert!(matches!(stored.status, JobStatus::Cancelled { .. }));
+    assert_eq!(
+        stored.deadline.map(|d| d.timestamp_millis()),
+        Some(deadline.timestamp_millis())
+    );
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_paused_job_is_skipped_until_resumed() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 1,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("pause-job").with_config(config)).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    // Keep the only worker busy so the next job stays pending
+    let blocker = common::enqueue(&supervisor, Job::new(queue.id, "slow", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == blocker.id),
+    )
+    .await?;
+
+    let held = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    let paused = common::call(&supervisor, |reply| SupervisorMessage::PauseJob {
+        job_id: held.id,
+        reply,
+    })
+    .await??;
+    assert!(matches!(paused.status, JobStatus::Paused));
+
+    // A job enqueued later runs while the paused one is passed over
+    let later = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == later.id),
+    )
+    .await?;
+    let still_paused = common::call(&supervisor, |reply| SupervisorMessage::GetJob {
+        job_id: held.id,
+        reply,
+    })
+    .await?
+    .ok_or("paused job not found")?;
+    assert!(matches!(still_paused.status, JobStatus::Paused));
+
+    let resumed = common::call(&supervisor, |reply| SupervisorMessage::ResumeJob {
+        job_id: held.id,
+        reply,
+    })
+    .await??;
+    assert!(matches!(resumed.status, JobStatus::Pending));
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == held.id),
+    )
+    .await?;
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_queue_default_tags_applied_on_enqueue() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 0,
+        default_tags: vec!["team:payments".into(), "billing".into()],
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("default-tags").with_config(config)).await?;
+
+    let untagged = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    assert_eq!(untagged.tags, ["team:payments", "billing"]);
+    assert_eq!(
+        JobRepository::get(untagged.id).await?.tags,
+        ["team:payments", "billing"]
+    );
+
+    // The job's own tags come first and defaults aren't repeated
+    let tagged = common::enqueue(
+        &supervisor,
+        Job::new(queue.id, "noop", json!({})).with_tags(vec!["urgent".into(), "billing".into()]),
+    )
+    .await?;
+    assert_eq!(tagged.tags, ["urgent", "billing", "team:payments"]);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_graceful_shutdown_waits_for_running_jobs() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 1,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("graceful").with_config(config)).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let running = common::enqueue(&supervisor, Job::new(queue.id, "slow", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == running.id),
+    )
+    .await?;
+    let waiting = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+
+    // The running job finishes; the one behind it is left for the next start
+    let left = common::call(&supervisor, |reply| SupervisorMessage::ShutdownGraceful {
+        timeout: Duration::from_secs(10),
+        reply,
+    })
+    .await?;
+    assert_eq!(left, 1);
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(1),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == running.id),
+    )
+    .await?;
+    handle.await?;
+
+    let pending = JobRepository::list(JobFilter {
+        queue_id: Some(queue.id),
+        status: Some("pending".into()),
+        ..Default::default()
+    })
+    .await?;
+    let pending_ids: Vec<_> = pending.iter().map(|job| job.id).collect();
+    assert_eq!(pending_ids, [waiting.id]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shutdown_cancels_running_jobs() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let mut handlers = common::handlers();
+    let seen = cancelled.clone();
+    handlers.register(FnHandler::new(
+        "hang",
+        move |_job: &Job, ctx: JobContext| {
+            let seen = seen.clone();
+            Box::pin(async move {
+                tokio::select! {
+                    _ = ctx.cancelled() => seen.store(true, Ordering::SeqCst),
+                    _ = tokio::time::sleep(Duration::from_secs(60)) => {}
+                }
+                Ok(JobResult::new("ok"))
+            })
+        },
+    ));
+    let (supervisor, handle) = common::start(handlers).await?;
+    let queue = common::register_queue(&supervisor, Queue::new("hard-stop")).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let running = common::enqueue(&supervisor, Job::new(queue.id, "hang", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == running.id),
+    )
+    .await?;
+
+    // The job is cancelled rather than waited out
+    supervisor
+        .send_message(SupervisorMessage::Shutdown)
+        .map_err(|e| e.to_string())?;
+    tokio::time::timeout(Duration::from_secs(5), handle).await??;
+    assert!(cancelled.load(Ordering::SeqCst));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_queue_reports_counts() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    // No workers, so enqueued jobs stay pending
+    let config = QueueConfig {
+        concurrency: 0,
+        max_queue_size: Some(2),
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("bounded").with_config(config)).await?;
+
+    for _ in 0..2 {
+        common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    }
+    let rejected = common::call(&supervisor, |reply| SupervisorMessage::EnqueueJob {
+        queue_id: queue.id,
+        job: Job::new(queue.id, "noop", json!({})),
+        overrides: JobOverrides::default(),
+        reply,
+    })
+    .await?;
+    assert_eq!(
+        rejected.err(),
+        Some(EnqueueError::QueueFull { current: 2, max: 2 })
+    );
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_oversized_payload_is_rejected() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 0,
+        max_payload_bytes: Some(32),
+        ..Default::default()
+    };
+    let queue = common::register_queue(
+        &supervisor,
+        Queue::new("small-payloads").with_config(config),
+    )
+    .await?;
+
+    let small = json!({ "id": 1 });
+    common::enqueue(&supervisor, Job::new(queue.id, "noop", small)).await?;
+
+    let large = json!({ "body": "x".repeat(64) });
+    let size = serde_json::to_vec(&large)?.len();
+    let rejected = common::call(&supervisor, |reply| SupervisorMessage::EnqueueJob {
+        queue_id: queue.id,
+        job: Job::new(queue.id, "noop", large),
+        overrides: JobOverrides::default(),
+        reply,
+    })
+    .await?;
+    assert_eq!(
+        rejected.err(),
+        Some(EnqueueError::PayloadTooLarge { size, max: 32 })
+    );
+
+    // Nothing was stored for the rejected job
+    let stored = JobRepository::list(JobFilter {
+        queue_id: Some(queue.id),
+        ..Default::default()
+    })
+    .await?;
+    assert_eq!(stored.len(), 1);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_pauses_queue_on_failure_spike() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let mut handlers = common::handlers();
+    handlers.register(FnHandler::new("down", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async { Err(JobError::permanent("dependency unavailable")) })
+    }));
+    let (supervisor, handle) = common::start(handlers).await?;
+    let config = QueueConfig {
+        concurrency: 1,
+        default_max_retries: 0,
+        breaker_threshold: Some(0.5),
+        breaker_cooldown_secs: 1,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("breaker").with_config(config)).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    for _ in 0..5 {
+        common::enqueue(&supervisor, Job::new(queue.id, "down", json!({}))).await?;
+    }
+    let trial = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+
+    common::wait_for_event(&mut events, Duration::from_secs(10), |event| {
+        matches!(
+            event,
+            JobEvent::QueueStateChanged {
+                new_state: QueueState::Paused,
+                ..
+            }
+        )
+    })
+    .await?;
+    let paused = common::get_queue(&supervisor, queue.id).await?;
+    assert_eq!(paused.state, QueueState::Paused);
+    assert!(paused.stats.breaker_open);
+    let waiting = common::call(&supervisor, |reply| SupervisorMessage::GetJob {
+        job_id: trial.id,
+        reply,
+    })
+    .await?;
+    assert!(waiting.is_some_and(|job| matches!(job.status, JobStatus::Pending)));
+
+    // After the cooldown the trial job runs, and its success closes the breaker
+    common::wait_for_event(&mut events, Duration::from_secs(5), |event| {
+        matches!(
+            event,
+            JobEvent::QueueStateChanged {
+                new_state: QueueState::Running,
+                ..
+            }
+        )
+    })
+    .await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == trial.id),
+    )
+    .await?;
+    let recovered = common::get_queue(&supervisor, queue.id).await?;
+    assert_eq!(recovered.state, QueueState::Running);
+    assert!(!recovered.stats.breaker_open);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_global_job_cap_spans_queues() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let mut handlers = common::handlers();
+    let (running_ref, peak_ref) = (running.clone(), peak.clone());
+    handlers.register(FnHandler::new(
+        "tracked",
+        move |_job: &Job, _ctx: JobContext| {
+            let (running, peak) = (running_ref.clone(), peak_ref.clone());
+            Box::pin(async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(JobResult::new("done"))
+            })
+        },
+    ));
+    let (supervisor, handle) = common::start_with(SupervisorArgs {
+        max_concurrent_jobs: Some(2),
+        ..SupervisorArgs::new(handlers)
+    })
+    .await?;
+
+    // Together the queues would run 8 jobs at once
+    let config = QueueConfig {
+        concurrency: 4,
+        ..Default::default()
+    };
+    let mut events = common::subscribe(&supervisor)?;
+    let mut jobs = Vec::new();
+    for name in ["capped-a", "capped-b"] {
+        let queue =
+            common::register_queue(&supervisor, Queue::new(name).with_config(config.clone()))
+                .await?;
+        for _ in 0..4 {
+            jobs.push(
+                common::enqueue(&supervisor, Job::new(queue.id, "tracked", json!({}))).await?,
+            );
+        }
+    }
+
+    let mut remaining: HashSet<_> = jobs.iter().map(|job| job.id).collect();
+    while !remaining.is_empty() {
+        common::wait_for_event(
+            &mut events,
+            Duration::from_secs(10),
+            |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if remaining.remove(job_id)),
+        )
+        .await?;
+    }
+    let peak = peak.load(Ordering::SeqCst);
+    assert!(peak <= 2, "{} jobs ran at once", peak);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_job_cap_lowered_then_raised_while_jobs_run() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let running = Arc::new(AtomicUsize::new(0));
+    let gate = Arc::new(tokio::sync::Semaphore::new(0));
+    let mut handlers = common::handlers();
+    let (running_ref, gate_ref) = (running.clone(), gate.clone());
+    handlers.register(FnHandler::new(
+        "gated",
+        move |_job: &Job, _ctx: JobContext| {
+            let (running, gate) = (running_ref.clone(), gate_ref.clone());
+            Box::pin(async move {
+                running.fetch_add(1, Ordering::SeqCst);
+                if let Ok(pass) = gate.acquire().await {
+                    pass.forget();
+                }
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(JobResult::new("done"))
+            })
+        },
+    ));
+    let (supervisor, handle) = common::start(handlers).await?;
+    supervisor
+        .send_message(SupervisorMessage::SetMaxConcurrentJobs { limit: Some(2) })
+        .map_err(|e| e.to_string())?;
+    let config = QueueConfig {
+        concurrency: 4,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("resized").with_config(config)).await?;
+
+    let running_now = |expected: usize| {
+        let running = running.clone();
+        common::wait_for(Duration::from_secs(5), move || {
+            let running = running.clone();
+            async move { Ok(running.load(Ordering::SeqCst) == expected) }
+        })
+    };
+    let enqueue_gated = |count: usize| {
+        let supervisor = supervisor.clone();
+        async move {
+            for _ in 0..count {
+                common::enqueue(&supervisor, Job::new(queue.id, "gated", json!({}))).await?;
+            }
+            TestResult::Ok(())
+        }
+    };
+
+    enqueue_gated(2).await?;
+    assert!(running_now(2).await?);
+
+    // Both jobs still hold their permits when the cap drops and comes back up
+    for limit in [1, 3] {
+        supervisor
+            .send_message(SupervisorMessage::SetMaxConcurrentJobs { limit: Some(limit) })
+            .map_err(|e| e.to_string())?;
+    }
+    enqueue_gated(2).await?;
+    assert!(running_now(3).await?);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(running.load(Ordering::SeqCst), 3);
+
+    gate.add_permits(4);
+    assert!(running_now(0).await?);
+
+    // Once everything has finished, the raised cap is what's left
+    enqueue_gated(4).await?;
+    assert!(running_now(3).await?);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(running.load(Ordering::SeqCst), 3);
+    gate.add_permits(4);
+    assert!(running_now(0).await?);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_rate_limit_sets_rate_limited_stat() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+
+    // One start every 10 seconds, with workers free to take more
+    let config = QueueConfig {
+        concurrency: 4,
+        rate_limit: Some(0.1),
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("rate-limited").with_config(config)).await?;
+    let mut events = common::subscribe(&supervisor)?;
+    for _ in 0..3 {
+        common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    }
+
+    common::wait_for_event(&mut events, Duration::from_secs(5), |event| {
+        matches!(event, JobEvent::QueueStatsUpdated { queue_id, stats, .. }
+            if *queue_id == queue.id && stats.rate_limited)
+    })
+    .await?;
+
+    let queue = common::get_queue(&supervisor, queue.id).await?;
+    assert!(queue.stats.rate_limited);
+    assert_eq!(queue.stats.pending, 2);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_enqueue_wakes_idle_workers() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+
+    // Left to their own polling, workers would take up to 30s to notice the job
+    let config = QueueConfig {
+        concurrency: 1,
+        poll_interval_ms: 30_000,
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("slow-poll").with_config(config)).await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let job = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(1),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == job.id),
+    )
+    .await?;
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_requeue_running_returns_jobs_to_pending() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let config = QueueConfig {
+        concurrency: 1,
+        ..Default::default()
+    };
+    let queue = common::register_queue(
+        &supervisor,
+        Queue::new("requeue-running").with_config(config),
+    )
+    .await?;
+    let mut events = common::subscribe(&supervisor)?;
+
+    let job = common::enqueue(&supervisor, Job::new(queue.id, "slow", json!({}))).await?;
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobStarted { job_id, .. } if *job_id == job.id),
+    )
+    .await?;
+
+    let requeued = common::call(&supervisor, |reply| SupervisorMessage::RequeueRunning {
+        queue_id: queue.id,
+        reply,
+    })
+    .await??;
+    assert_eq!(requeued, 1);
+    common::wait_for_event(&mut events, Duration::from_secs(5), |event| {
+        matches!(event, JobEvent::JobStatusChanged { job_id, new_status: JobStatus::Pending, .. }
+            if *job_id == job.id)
+    })
+    .await?;
+
+    // The only worker is still winding down the cancelled run
+    let requeued_job = common::call(&supervisor, |reply| SupervisorMessage::GetJob {
+        job_id: job.id,
+        reply,
+    })
+    .await?
+    .ok_or("requeued job not found")?;
+    assert_eq!(requeued_job.status, JobStatus::Pending);
+    assert_eq!(requeued_job.attempts, 1);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_webhook_posts_signed_event_on_completion() -> TestResult {
+    use hmac::{Hmac, Mac};
+
+    let _guard = common::setup_db().await?;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}/hooks/jobs", listener.local_addr()?);
+
+    let (supervisor, handle) = common::start_with(SupervisorArgs {
+        webhook_secret: Some("shared-secret".into()),
+        ..SupervisorArgs::new(common::handlers())
+    })
+    .await?;
+    let config = QueueConfig {
+        webhook_url: Some(url),
+        ..Default::default()
+    };
+    let queue =
+        common::register_queue(&supervisor, Queue::new("webhooks").with_config(config)).await?;
+    let job = common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({}))).await?;
+
+    let (head, body) = tokio::time::timeout(
+        Duration::from_secs(5),
+        common::accept_http_request(&listener),
+    )
+    .await??;
+    assert!(head.starts_with("post /hooks/jobs "));
+
+    let event: JobEvent = serde_json::from_slice(&body)?;
+    assert!(matches!(event, JobEvent::JobCompleted { job_id, .. } if job_id == job.id));
+
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"shared-secret")?;
+    mac.update(&body);
+    let expected = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+    let signature = head
+        .lines()
+        .find_map(|line| line.strip_prefix("x-webhook-signature:"))
+        .map(str::trim);
+    assert_eq!(signature, Some(expected.as_str()));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_declare_queues_skips_registered_names() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let existing = common::register_queue(&supervisor, Queue::new("declared-existing")).await?;
+
+    let created = actors::declare_queues(
+        &supervisor,
+        [
+            QueueBuilder::new("declared-existing").concurrency(9),
+            QueueBuilder::new("declared-new")
+                .concurrency(2)
+                .max_size(10),
+        ],
+    )
+    .await?;
+    let names: Vec<_> = created.iter().map(|queue| queue.name.as_str()).collect();
+    assert_eq!(names, ["declared-new"]);
+
+    let kept = common::get_queue(&supervisor, existing.id).await?;
+    assert_eq!(kept.config.concurrency, existing.config.concurrency);
+    let stored = QueueRepository::get_by_name("declared-new").await?;
+    assert_eq!(stored.config.concurrency, 2);
+    assert_eq!(stored.config.max_queue_size, Some(10));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_declare_queues_keeps_tenant() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+
+    let created = actors::declare_queues(
+        &supervisor,
+        [QueueBuilder::new("declared-tenant").tenant("acme")],
+    )
+    .await?;
+    assert_eq!(created[0].tenant_id.as_deref(), Some("acme"));
+
+    let listed = QueueRepository::list_for_tenant("acme").await?;
+    let names: Vec<_> = listed.iter().map(|queue| queue.name.as_str()).collect();
+    assert_eq!(names, ["declared-tenant"]);
+
+    // Jobs enqueued on the queue inherit its tenant
+    let job = common::enqueue(&supervisor, Job::new(created[0].id, "noop", json!({}))).await?;
+    assert_eq!(job.tenant_id.as_deref(), Some("acme"));
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_start_restores_stored_queues_once() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let empty = QueueRepository::create(&Queue::new("restored-empty")).await?;
+    let busy = QueueRepository::create(&Queue::new("restored-busy")).await?;
+    let pending = JobRepository::create(&Job::new(busy.id, "noop", json!({}))).await?;
+    // A job whose queue row is gone is left alone rather than restoring a queue
+    let orphan = JobRepository::create(&Job::new(QueueId::new(), "noop", json!({}))).await?;
+
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let supervisor_ref = &supervisor;
+
+    let list_ids = || async move {
+        let queues = common::call(supervisor_ref, |reply| SupervisorMessage::ListQueues {
+            reply,
+        })
+        .await?;
+        Ok::<_, Box<dyn std::error::Error>>(queues.iter().map(|queue| queue.id).collect::<Vec<_>>())
+    };
+    let assert_restored = |ids: Vec<QueueId>| {
+        assert_eq!(ids.len(), 2, "unexpected queues: {:?}", ids);
+        assert!(ids.contains(&empty.id) && ids.contains(&busy.id));
+    };
+    assert_restored(list_ids().await?);
+
+    // The busy queue rehydrated its job and runs it to completion
+    let pending_id = pending.id;
+    let completed = common::wait_for(Duration::from_secs(5), || async move {
+        Ok(JobRepository::get_history(pending_id)
+            .await
+            .is_ok_and(|archived| archived.record.status == "completed"))
+    })
+    .await?;
+    assert!(completed);
+    assert_eq!(
+        JobRepository::get(orphan.id).await?.status,
+        JobStatus::Pending
+    );
+
+    // Restoring again doesn't register anything twice
+    let restored = actors::restore_queues(&supervisor).await?;
+    assert!(restored.is_empty());
+    assert_restored(list_ids().await?);
+
+    common::stop(supervisor, handle).await
+}
+
+#[tokio::test]
+async fn test_try_enqueue_reports_position_in_dispatch_order() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let queue = Queue::new("try-enqueue");
+    // No workers are attached; jobs are handed out by explicit requests
+    let (queue_ref, queue_handle) =
+        Actor::spawn(None, QueueActor, QueueActorState::new(queue.clone())).await?;
+    let queue_ref = &queue_ref;
+    let try_enqueue = |priority: Priority| async move {
+        let job = Job::new(queue.id, "noop", json!({})).with_priority(priority);
+        let receipt = common::call(queue_ref, |reply| QueueMessage::TryEnqueue {
+            job: Box::new(job),
+            overrides: JobOverrides::default(),
+            reply,
+        })
+        .await??;
+        Ok::<_, Box<dyn std::error::Error>>(receipt)
+    };
+
+    let low = try_enqueue(Priority::Low).await?;
+    let normal = try_enqueue(Priority::Normal).await?;
+    let high = try_enqueue(Priority::High).await?;
+    let later_normal = try_enqueue(Priority::Normal).await?;
+
+    // Each job is placed behind everything it can't overtake
+    let positions: Vec<_> = [&low, &normal, &high, &later_normal]
+        .iter()
+        .map(|receipt| (receipt.position, receipt.pending_total))
+        .collect();
+    assert_eq!(positions, [(0, 1), (0, 2), (0, 3), (2, 4)]);
+
+    let mut dispatched = Vec::new();
+    while let Some((job, _)) = common::call(queue_ref, |reply| QueueMessage::RequestJob {
+        worker_id: "worker-1".into(),
+        reply,
+    })
+    .await?
+    {
+        dispatched.push(job.id);
+    }
+    assert_eq!(
+        dispatched,
+        [high.job.id, normal.job.id, later_normal.job.id, low.job.id]
+    );
+    assert_eq!(dispatched[later_normal.position], later_normal.job.id);
+
+    queue_ref
+        .send_message(QueueMessage::Shutdown)
+        .map_err(|e| e.to_string())?;
+    queue_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unpersisted_job_runs_once_the_database_recovers() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let db_conn = db::get_db()?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+    let strict = common::register_queue(&supervisor, Queue::new("persist-strict")).await?;
+    let lenient = common::register_queue(
+        &supervisor,
+        QueueBuilder::new("persist-lenient")
+            .accept_unpersisted()
+            .build(),
+    )
+    .await?;
+
+    // A unique index plus an existing noop job makes creating noop jobs fail
+    JobRepository::create(&Job::new(QueueId::new(), "noop", json!({}))).await?;
+    db_conn
+        .query("DEFINE INDEX noop_once ON job FIELDS job_type UNIQUE")
+        .await?
+        .check()?;
+
+    let rejected = common::enqueue(&supervisor, Job::new(strict.id, "noop", json!({}))).await;
+    assert!(rejected.is_err(), "strict queue accepted an unstored job");
+
+    let mut events = common::subscribe(&supervisor)?;
+    let job = common::enqueue(&supervisor, Job::new(lenient.id, "noop", json!({}))).await?;
+    assert!(JobRepository::get(job.id).await.is_err());
+
+    db_conn
+        .query("REMOVE INDEX noop_once ON job")
+        .await?
+        .check()?;
+
+    common::wait_for_event(
+        &mut events,
+        Duration::from_secs(5),
+        |event| matches!(event, JobEvent::JobCompleted { job_id, .. } if *job_id == job.id),
+    )
+    .await?;
+
+    let job_id = job.id;
+    let archived = common::wait_for(Duration::from_secs(5), || async move {
+        Ok(JobRepository::get_history(job_id).await.is_ok())
+    })
+    .await?;
+    assert!(archived, "job was never persisted");
+
+    common::stop(supervisor, handle).await
+}
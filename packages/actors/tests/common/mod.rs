@@ -0,0 +1,248 @@
+// Shared by several test crates; each one only uses some of the helpers.
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::sync::{LazyLock, OnceLock};
+use std::time::Duration;
+
+use actors::{
+    ActorRef, FnHandler, JobContext, JobHandlerRegistry, RpcReplyPort, SupervisorArgs,
+    SupervisorMessage,
+};
+use db::DbConfig;
+use queue_core::{Job, JobEvent, JobOverrides, JobResult, Queue, QueueId};
+use ractor::rpc::CallResult;
+use tokio::runtime::Runtime;
+use tokio::sync::{Mutex, MutexGuard, broadcast};
+use tokio::task::JoinHandle;
+
+static TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// Runtime the in-memory database connects on.
+///
+/// The engine's background task lives on the runtime that connected, and each
+/// `#[tokio::test]` gets its own, so the connection has to outlive them all.
+static DB_RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+const CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub type TestResult = Result<(), Box<dyn Error>>;
+
+pub async fn setup_db() -> Result<MutexGuard<'static, ()>, Box<dyn Error>> {
+    let guard = TEST_LOCK.lock().await;
+    // Only the lock holder sets the runtime, so `set` can't fail
+    if DB_RUNTIME.get().is_none() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()?;
+        let _ = DB_RUNTIME.set(runtime);
+    }
+    let runtime = DB_RUNTIME.get().ok_or("database runtime not started")?;
+    runtime.spawn(db::init(DbConfig::memory())).await??;
+    let db_conn = db::get_db()?;
+    db_conn
+        .query(
+            "DELETE event_log; DELETE stats_history; DELETE job_history; DELETE job; DELETE queue;",
+        )
+        .await?;
+    Ok(guard)
+}
+
+/// Registry with a `noop` handler that completes immediately and a `slow`
+/// handler that holds its worker for a couple of seconds.
+pub fn handlers() -> JobHandlerRegistry {
+    let mut handlers = JobHandlerRegistry::new();
+    handlers.register(FnHandler::new("noop", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async { Ok(JobResult::new("ok")) })
+    }));
+    handlers.register(FnHandler::new("slow", |_job: &Job, _ctx: JobContext| {
+        Box::pin(async {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            Ok(JobResult::new("ok"))
+        })
+    }));
+    handlers
+}
+
+pub async fn start(
+    handlers: JobHandlerRegistry,
+) -> Result<(ActorRef<SupervisorMessage>, JoinHandle<()>), Box<dyn Error>> {
+    start_with(SupervisorArgs::new(handlers)).await
+}
+
+pub async fn start_with(
+    args: SupervisorArgs,
+) -> Result<(ActorRef<SupervisorMessage>, JoinHandle<()>), Box<dyn Error>> {
+    Ok(actors::start_supervisor(args).await?)
+}
+
+pub async fn stop(supervisor: ActorRef<SupervisorMessage>, handle: JoinHandle<()>) -> TestResult {
+    supervisor
+        .send_message(SupervisorMessage::Shutdown)
+        .map_err(|e| e.to_string())?;
+    handle.await?;
+    Ok(())
+}
+
+/// Send a request and wait for its reply.
+pub async fn call<M, T, F>(actor: &ActorRef<M>, build: F) -> Result<T, Box<dyn Error>>
+where
+    M: ractor::Message,
+    T: Send + 'static,
+    F: FnOnce(RpcReplyPort<T>) -> M,
+{
+    match ractor::rpc::call(actor, build, Some(CALL_TIMEOUT))
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        CallResult::Success(value) => Ok(value),
+        CallResult::Timeout => Err("call timed out".into()),
+        CallResult::SenderError => Err("reply dropped".into()),
+    }
+}
+
+/// Register a queue with the supervisor and return it.
+pub async fn register_queue(
+    supervisor: &ActorRef<SupervisorMessage>,
+    queue: Queue,
+) -> Result<Queue, Box<dyn Error>> {
+    Ok(call(supervisor, |reply| SupervisorMessage::RegisterQueue {
+        queue,
+        reply,
+    })
+    .await??)
+}
+
+/// Enqueue a job through the supervisor, keeping the job's own settings.
+pub async fn enqueue(
+    supervisor: &ActorRef<SupervisorMessage>,
+    job: Job,
+) -> Result<Job, Box<dyn Error>> {
+    let overrides = JobOverrides::from_job(&job);
+    enqueue_with(supervisor, job, overrides).await
+}
+
+/// Enqueue a job through the supervisor with explicit overrides.
+pub async fn enqueue_with(
+    supervisor: &ActorRef<SupervisorMessage>,
+    job: Job,
+    overrides: JobOverrides,
+) -> Result<Job, Box<dyn Error>> {
+    let queue_id = job.queue_id;
+    Ok(call(supervisor, |reply| SupervisorMessage::EnqueueJob {
+        queue_id,
+        job,
+        overrides,
+        reply,
+    })
+    .await??)
+}
+
+/// Fetch a queue's current info through the supervisor.
+pub async fn get_queue(
+    supervisor: &ActorRef<SupervisorMessage>,
+    queue_id: QueueId,
+) -> Result<Queue, Box<dyn Error>> {
+    call(supervisor, |reply| SupervisorMessage::GetQueue {
+        queue_id,
+        reply,
+    })
+    .await?
+    .ok_or_else(|| "queue not found".into())
+}
+
+/// Subscribe to the supervisor's event stream.
+pub fn subscribe(
+    supervisor: &ActorRef<SupervisorMessage>,
+) -> Result<broadcast::Receiver<JobEvent>, Box<dyn Error>> {
+    let (sender, rx) = broadcast::channel(1024);
+    supervisor
+        .send_message(SupervisorMessage::Subscribe { sender })
+        .map_err(|e| e.to_string())?;
+    Ok(rx)
+}
+
+/// Wait for the next event matching `matches`, skipping everything else.
+pub async fn wait_for_event<F>(
+    rx: &mut broadcast::Receiver<JobEvent>,
+    timeout: Duration,
+    mut matches: F,
+) -> Result<JobEvent, Box<dyn Error>>
+where
+    F: FnMut(&JobEvent) -> bool,
+{
+    let found = tokio::time::timeout(timeout, async {
+        loop {
+            match rx.recv().await {
+                Ok(event) if matches(&event) => return Ok(event),
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    })
+    .await
+    .map_err(|_| "timed out waiting for event")??;
+    Ok(found)
+}
+
+/// Poll `check` until it returns true or the timeout elapses.
+pub async fn wait_for<F, Fut>(timeout: Duration, mut check: F) -> Result<bool, Box<dyn Error>>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<bool, Box<dyn Error>>>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    while tokio::time::Instant::now() < deadline {
+        if check().await? {
+            return Ok(true);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    Ok(false)
+}
+
+/// Accept one HTTP request on `listener`, answer 200, and return its
+/// lowercased header block and its body.
+pub async fn accept_http_request(
+    listener: &tokio::net::TcpListener,
+) -> Result<(String, Vec<u8>), Box<dyn Error>> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (mut stream, _) = listener.accept().await?;
+    let mut request = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let (head, body_start) = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err("connection closed before headers ended".into());
+        }
+        request.extend_from_slice(chunk.get(..read).unwrap_or_default());
+        if let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(request.get(..end).unwrap_or_default());
+            break (head.to_lowercase(), end + 4);
+        }
+    };
+
+    let content_length = head
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .and_then(|len| len.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while request.len() < body_start + content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err("connection closed before body ended".into());
+        }
+        request.extend_from_slice(chunk.get(..read).unwrap_or_default());
+    }
+
+    stream
+        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+        .await?;
+    let body = request
+        .get(body_start..body_start + content_length)
+        .unwrap_or_default()
+        .to_vec();
+    Ok((head, body))
+}
@@ -0,0 +1,115 @@
+//! Enqueue throughput and enqueue-to-start latency benchmark.
+//!
+//! Runs against the in-memory database with a no-op handler so the numbers
+//! reflect actor and persistence overhead rather than handler work. Only built
+//! with the `bench` feature:
+//!
+//! ```text
+//! cargo test -p actors --features bench --release --test enqueue_bench -- --nocapture
+//! ```
+
+#![cfg(feature = "bench")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use queue_core::{Job, JobEvent, JobId, Queue, QueueConfig};
+use serde_json::json;
+
+use common::TestResult;
+
+/// Jobs enqueued per concurrency setting.
+const JOBS_PER_RUN: usize = 200;
+
+/// Worker counts to measure.
+const CONCURRENCY_SETTINGS: &[u32] = &[1, 4, 8];
+
+/// Latency at the given percentile (0-100) of an ascending-sorted sample.
+fn percentile(sorted: &[i64], pct: usize) -> i64 {
+    let index = (sorted.len().saturating_sub(1) * pct) / 100;
+    sorted.get(index).copied().unwrap_or_default()
+}
+
+#[tokio::test]
+async fn bench_enqueue_throughput_and_latency() -> TestResult {
+    let _guard = common::setup_db().await?;
+    let (supervisor, handle) = common::start(common::handlers()).await?;
+
+    println!(
+        "{:>11} {:>6} {:>12} {:>9} {:>9} {:>9}",
+        "concurrency", "jobs", "enqueue/s", "p50 ms", "p95 ms", "max ms"
+    );
+
+    for &concurrency in CONCURRENCY_SETTINGS {
+        let config = QueueConfig {
+            concurrency,
+            ..QueueConfig::default()
+        };
+        let queue = common::register_queue(
+            &supervisor,
+            Queue::new(format!("bench-{}", concurrency)).with_config(config),
+        )
+        .await?;
+        let mut events = common::subscribe(&supervisor)?;
+
+        // Collect start events while enqueueing so the broadcast channel
+        // doesn't lag and drop them under load.
+        let queue_id = queue.id;
+        let collector = tokio::spawn(async move {
+            let mut starts = HashMap::with_capacity(JOBS_PER_RUN);
+            while starts.len() < JOBS_PER_RUN {
+                let event = common::wait_for_event(&mut events, Duration::from_secs(30), |event| {
+                    matches!(event, JobEvent::JobStarted { queue_id: id, .. } if *id == queue_id)
+                })
+                .await
+                .map_err(|e| e.to_string())?;
+                if let JobEvent::JobStarted {
+                    job_id, timestamp, ..
+                } = event
+                {
+                    starts.insert(job_id, timestamp);
+                }
+            }
+            Ok::<_, String>(starts)
+        });
+
+        let mut created_at = HashMap::with_capacity(JOBS_PER_RUN);
+        let started = Instant::now();
+        for i in 0..JOBS_PER_RUN {
+            let job =
+                common::enqueue(&supervisor, Job::new(queue.id, "noop", json!({ "i": i }))).await?;
+            created_at.insert(job.id, job.created_at);
+        }
+        let enqueue_elapsed = started.elapsed();
+
+        // Enqueue-to-start latency uses the event timestamps, so it isn't
+        // skewed by when the collector gets around to draining the channel.
+        let starts: HashMap<JobId, _> = collector.await??;
+        let mut latencies_ms: Vec<i64> = created_at
+            .iter()
+            .filter_map(|(job_id, created)| {
+                let start = starts.get(job_id)?;
+                Some((*start - *created).num_milliseconds().max(0))
+            })
+            .collect();
+        latencies_ms.sort_unstable();
+
+        let throughput = JOBS_PER_RUN as f64 / enqueue_elapsed.as_secs_f64().max(f64::EPSILON);
+        println!(
+            "{:>11} {:>6} {:>12.1} {:>9} {:>9} {:>9}",
+            concurrency,
+            JOBS_PER_RUN,
+            throughput,
+            percentile(&latencies_ms, 50),
+            percentile(&latencies_ms, 95),
+            latencies_ms.last().copied().unwrap_or_default(),
+        );
+
+        assert_eq!(latencies_ms.len(), JOBS_PER_RUN);
+    }
+
+    common::stop(supervisor, handle).await
+}
@@ -1,5 +1,6 @@
-//! Simple echo server function for testing.
+//! Simple echo server functions for testing.
 
+use chrono::{DateTime, Utc};
 use dioxus::prelude::*;
 
 /// Echo the user input on the server.
@@ -7,3 +8,28 @@ use dioxus::prelude::*;
 pub async fn echo(input: String) -> Result<String, ServerFnError> {
     Ok(input)
 }
+
+/// A JSON value sent back by [`echo_json`], with details of the server that answered.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct JsonEcho {
+    /// The value as it was received.
+    pub value: serde_json::Value,
+    /// When the server handled the request.
+    pub timestamp: DateTime<Utc>,
+    /// Whether the answering build has the `server` feature, and so the job queue.
+    pub server_feature: bool,
+}
+
+/// Echo a JSON value unchanged, as a cheap connectivity check for SDKs.
+///
+/// Round-trips the value through the request and response encoding, so
+/// nested objects and arrays come back exactly as sent.
+#[post("/api/echo/json")]
+pub async fn echo_json(value: serde_json::Value) -> Result<JsonEcho, ServerFnError> {
+    Ok(JsonEcho {
+        value,
+        timestamp: Utc::now(),
+        server_feature: cfg!(feature = "server"),
+    })
+}
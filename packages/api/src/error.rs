@@ -0,0 +1,166 @@
+//! Typed errors for the job and queue server functions.
+
+use std::fmt;
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Error returned by the job and queue server functions.
+///
+/// Server functions still return [`ServerFnError`]; this converts into one
+/// whose status code and details carry the error code, and
+/// [`JobApiError::from_server_fn_error`] recovers it on the client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum JobApiError {
+    /// The job or queue does not exist.
+    NotFound(String),
+    /// The queue is at its maximum size, holding `current` of `max` pending jobs.
+    QueueFull {
+        message: String,
+        current: usize,
+        max: usize,
+    },
+    /// The job's payload is `size` bytes, over the queue's limit of `max`.
+    PayloadTooLarge {
+        message: String,
+        size: usize,
+        max: usize,
+    },
+    /// The database or supervisor isn't ready yet.
+    NotInitialized(String),
+    /// The request can't be applied (bad ID, job already finished, queue not accepting jobs).
+    InvalidRequest(String),
+    /// Anything else that went wrong on the server.
+    Internal(String),
+}
+
+impl JobApiError {
+    /// Stable machine-readable code, as serialized.
+    pub fn code(&self) -> &'static str {
+        match self {
+            JobApiError::NotFound(_) => "not_found",
+            JobApiError::QueueFull { .. } => "queue_full",
+            JobApiError::PayloadTooLarge { .. } => "payload_too_large",
+            JobApiError::NotInitialized(_) => "not_initialized",
+            JobApiError::InvalidRequest(_) => "invalid_request",
+            JobApiError::Internal(_) => "internal",
+        }
+    }
+
+    /// HTTP status code reported for this error.
+    pub fn status(&self) -> u16 {
+        match self {
+            JobApiError::NotFound(_) => 404,
+            JobApiError::QueueFull { .. } => 429,
+            JobApiError::PayloadTooLarge { .. } => 413,
+            JobApiError::NotInitialized(_) => 503,
+            JobApiError::InvalidRequest(_) => 400,
+            JobApiError::Internal(_) => 500,
+        }
+    }
+
+    /// Human-readable description.
+    pub fn message(&self) -> &str {
+        match self {
+            JobApiError::NotFound(message)
+            | JobApiError::QueueFull { message, .. }
+            | JobApiError::PayloadTooLarge { message, .. }
+            | JobApiError::NotInitialized(message)
+            | JobApiError::InvalidRequest(message)
+            | JobApiError::Internal(message) => message,
+        }
+    }
+
+    /// Whether the same request may succeed if tried again later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            JobApiError::QueueFull { .. } | JobApiError::NotInitialized(_)
+        )
+    }
+
+    /// Recover the typed error from a server function failure.
+    ///
+    /// Errors that didn't originate from a `JobApiError` (transport failures,
+    /// other server functions) become `Internal`.
+    pub fn from_server_fn_error(error: &ServerFnError) -> Self {
+        match error {
+            ServerFnError::ServerError {
+                details: Some(details),
+                ..
+            } => serde_json::from_value(details.clone()).ok(),
+            _ => None,
+        }
+        .unwrap_or_else(|| JobApiError::Internal(error.to_string()))
+    }
+
+    /// Classify an error reply from the supervisor or a queue actor.
+    #[cfg(feature = "server")]
+    pub(crate) fn from_reply(reply: String) -> Self {
+        match reply.as_str() {
+            actors::JOB_NOT_FOUND | actors::QUEUE_NOT_FOUND => JobApiError::NotFound(reply),
+            _ => JobApiError::Internal(reply),
+        }
+    }
+
+    /// Classify a rejected enqueue, keeping a full queue's counts and payload sizes.
+    ///
+    /// Rejections of the request itself (queue not accepting, disallowed or
+    /// unknown job type, payload failing its schema) are `InvalidRequest`.
+    #[cfg(feature = "server")]
+    pub(crate) fn from_enqueue(error: actors::EnqueueError) -> Self {
+        match error {
+            actors::EnqueueError::QueueFull { current, max } => JobApiError::QueueFull {
+                message: error.to_string(),
+                current,
+                max,
+            },
+            actors::EnqueueError::PayloadTooLarge { size, max } => JobApiError::PayloadTooLarge {
+                message: error.to_string(),
+                size,
+                max,
+            },
+            actors::EnqueueError::QueueNotFound => JobApiError::NotFound(error.to_string()),
+            actors::EnqueueError::NotAccepting
+            | actors::EnqueueError::JobTypeNotAllowed(_)
+            | actors::EnqueueError::UnknownJobType(_)
+            | actors::EnqueueError::InvalidPayload(_) => {
+                JobApiError::InvalidRequest(error.to_string())
+            }
+            actors::EnqueueError::Other(reply) => JobApiError::from_reply(reply),
+        }
+    }
+
+    /// Prefix the message with `context`, keeping the code and details.
+    #[cfg(feature = "server")]
+    pub(crate) fn with_context(mut self, context: impl fmt::Display) -> Self {
+        let (JobApiError::NotFound(message)
+        | JobApiError::QueueFull { message, .. }
+        | JobApiError::PayloadTooLarge { message, .. }
+        | JobApiError::NotInitialized(message)
+        | JobApiError::InvalidRequest(message)
+        | JobApiError::Internal(message)) = &mut self;
+        *message = format!("{}: {}", context, message);
+        self
+    }
+}
+
+impl fmt::Display for JobApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for JobApiError {}
+
+impl From<JobApiError> for ServerFnError {
+    fn from(error: JobApiError) -> Self {
+        ServerFnError::ServerError {
+            message: error.message().to_string(),
+            code: error.status(),
+            details: serde_json::to_value(&error).ok(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Event history server functions.
+
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use queue_core::JobEvent;
+
+#[cfg(feature = "server")]
+use crate::JobApiError;
+#[cfg(feature = "server")]
+use queue_core::QueueId;
+
+/// Logged events for a queue at or after `since`, oldest first.
+///
+/// Lets a reconnecting client backfill what it missed before switching to the
+/// live event stream.
+#[post("/api/queues/:queue_id/events")]
+pub async fn list_events(
+    queue_id: String,
+    since: DateTime<Utc>,
+    limit: usize,
+) -> Result<Vec<JobEvent>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use db::repositories::EventRepository;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&queue_id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        EventRepository::list(queue_id, since, limit)
+            .await
+            .map_err(|e| JobApiError::Internal(format!("Database error: {}", e)).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
@@ -0,0 +1,61 @@
+//! Health check server function for readiness probes.
+
+use dioxus::prelude::*;
+
+/// Status of the job queue subsystems.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HealthStatus {
+    /// Whether the database connection is initialized.
+    pub db_ready: bool,
+    /// Whether the supervisor actor is registered.
+    pub supervisor_ready: bool,
+    /// Number of live queues.
+    pub queue_count: usize,
+    /// Pending jobs across all queues.
+    pub total_pending: u64,
+}
+
+impl HealthStatus {
+    /// Check if the system can serve requests.
+    pub fn is_ready(&self) -> bool {
+        self.db_ready && self.supervisor_ready
+    }
+}
+
+/// Report subsystem status without triggering initialization.
+#[get("/api/health")]
+pub async fn health() -> Result<HealthStatus, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        let db_ready = db::try_get_db().is_some();
+        let supervisor = global_registry().get_supervisor();
+
+        let queues = match &supervisor {
+            Some(supervisor) => {
+                let (tx, rx) = actors::concurrency::oneshot();
+                supervisor
+                    .send_message(SupervisorMessage::ListQueues { reply: tx.into() })
+                    .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+                rx.await
+                    .map_err(|_| ServerFnError::new("Failed to receive response"))?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(HealthStatus {
+            db_ready,
+            supervisor_ready: supervisor.is_some(),
+            queue_count: queues.len(),
+            total_pending: queues.iter().map(|q| q.stats.pending).sum(),
+        })
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
@@ -1,27 +1,184 @@
 //! Server initialization for the job queue system.
 
+use std::sync::OnceLock;
+use std::time::Duration;
+
 use actors::global_registry;
-use actors::{FnHandler, JobHandlerRegistry, start_supervisor};
-use db::{DbConfig, init as init_db};
-use queue_core::{Job, JobResult};
+use actors::{SupervisorArgs, declare_queues, start_supervisor};
+use db::init as init_db;
+use queue_core::{Job, JobResult, QueueBuilder};
+use serde_json::{Map, Value};
 use tokio::sync::OnceCell;
 
+pub use actors::{
+    FnHandler, HandlerFuture, HeartbeatSampling, JobContext, JobHandler, JobHandlerRegistry,
+};
+pub use db::DbConfig;
+
+/// How long server functions wait for the supervisor to be registered
+/// before reporting the system as not initialized.
+pub(crate) const SUPERVISOR_WAIT: Duration = Duration::from_secs(5);
+
 /// Global initialization cell - ensures init happens exactly once.
 static INIT: OnceCell<Result<(), String>> = OnceCell::const_new();
 
+/// Builds the handlers and config used when initialization happens lazily.
+type InitBuilder = Box<dyn Fn() -> (JobHandlerRegistry, InitConfig) + Send + Sync>;
+
+/// Builder injected with [`set_init_builder`]; defaults are used without one.
+static INIT_BUILDER: OnceLock<InitBuilder> = OnceLock::new();
+
+/// Startup settings for the job queue system.
+#[derive(Debug, Clone)]
+pub struct InitConfig {
+    /// Database to connect to.
+    pub db: DbConfig,
+    /// Queues declared in code, created at startup unless already persisted.
+    pub queues: Vec<QueueBuilder>,
+    /// Also declare the `demo` queue.
+    pub create_demo_queue: bool,
+    /// Add the demo handlers (see [`default_handlers`]) for any of their job
+    /// types the given registry doesn't handle.
+    pub register_demo_handlers: bool,
+    /// Cap on jobs running at once across all queues; `None` means no cap.
+    pub max_concurrent_jobs: Option<u32>,
+    /// How long archived jobs and logged events are kept; `None` keeps them forever.
+    pub history_retention: Option<Duration>,
+    /// How many worker heartbeat events reach subscribers.
+    pub heartbeat_sampling: HeartbeatSampling,
+    /// Secret queue webhooks are signed with; `None` sends them unsigned.
+    pub webhook_secret: Option<String>,
+}
+
+impl Default for InitConfig {
+    fn default() -> Self {
+        let db = if std::env::var("RAILWAY_ENVIRONMENT").is_ok() {
+            // Railway deployment - use file-based storage
+            DbConfig::file("./data/surrealdb")
+        } else {
+            // Local development - use in-memory
+            DbConfig::memory()
+        };
+
+        Self {
+            db,
+            queues: Vec::new(),
+            create_demo_queue: true,
+            register_demo_handlers: true,
+            max_concurrent_jobs: None,
+            history_retention: None,
+            heartbeat_sampling: HeartbeatSampling::All,
+            webhook_secret: None,
+        }
+    }
+}
+
+/// Environment variable capping how many jobs run at once across all queues.
+const MAX_CONCURRENT_JOBS_ENV: &str = "MAX_CONCURRENT_JOBS";
+
+/// Environment variable holding the job history retention window in days.
+const HISTORY_RETENTION_ENV: &str = "JOB_HISTORY_RETENTION_DAYS";
+
+/// Environment variable limiting heartbeat events to one per worker per this many ms.
+const HEARTBEAT_EVENT_INTERVAL_ENV: &str = "HEARTBEAT_EVENT_INTERVAL_MS";
+
+/// Environment variable holding the secret webhook bodies are signed with.
+const WEBHOOK_SECRET_ENV: &str = "WEBHOOK_SECRET";
+
+impl InitConfig {
+    /// The defaults, with runtime settings taken from the environment.
+    pub fn from_env() -> Result<Self, String> {
+        Self::default().with_env()
+    }
+
+    /// Override runtime settings with any given in the environment.
+    ///
+    /// Reads `MAX_CONCURRENT_JOBS`, `JOB_HISTORY_RETENTION_DAYS`,
+    /// `HEARTBEAT_EVENT_INTERVAL_MS` (`off` drops heartbeat events) and
+    /// `WEBHOOK_SECRET`. Unset variables keep the configured value; a value
+    /// that doesn't parse is an error rather than being ignored.
+    pub fn with_env(mut self) -> Result<Self, String> {
+        self.max_concurrent_jobs = env_setting(
+            MAX_CONCURRENT_JOBS_ENV,
+            "a positive number of jobs",
+            |raw| raw.parse::<u32>().ok().filter(|limit| *limit > 0),
+        )?
+        .or(self.max_concurrent_jobs);
+        self.history_retention =
+            env_setting(HISTORY_RETENTION_ENV, "a positive number of days", |raw| {
+                raw.parse::<u64>()
+                    .ok()
+                    .filter(|days| *days > 0)
+                    .map(|days| Duration::from_secs(days.saturating_mul(24 * 60 * 60)))
+            })?
+            .or(self.history_retention);
+        if let Some(sampling) = env_setting(
+            HEARTBEAT_EVENT_INTERVAL_ENV,
+            "a positive number of milliseconds or \"off\"",
+            |raw| {
+                if raw.eq_ignore_ascii_case("off") {
+                    return Some(HeartbeatSampling::Off);
+                }
+                raw.parse::<u64>()
+                    .ok()
+                    .filter(|ms| *ms > 0)
+                    .map(|ms| HeartbeatSampling::Every(Duration::from_millis(ms)))
+            },
+        )? {
+            self.heartbeat_sampling = sampling;
+        }
+        self.webhook_secret = std::env::var(WEBHOOK_SECRET_ENV)
+            .ok()
+            .filter(|secret| !secret.is_empty())
+            .or(self.webhook_secret);
+        Ok(self)
+    }
+}
+
+/// Parse an environment variable with `parse`; `None` if it isn't set.
+fn env_setting<T>(
+    name: &str,
+    expected: &str,
+    parse: impl FnOnce(&str) -> Option<T>,
+) -> Result<Option<T>, String> {
+    let Ok(raw) = std::env::var(name) else {
+        return Ok(None);
+    };
+    parse(raw.trim())
+        .map(Some)
+        .ok_or_else(|| format!("Invalid {}={:?}: expected {}", name, raw, expected))
+}
+
+/// Set how handlers and config are built when the system initializes lazily.
+///
+/// Call this before the server starts so the first server function to run
+/// initializes with your handlers. Returns `false` if a builder was already set.
+pub fn set_init_builder(
+    builder: impl Fn() -> (JobHandlerRegistry, InitConfig) + Send + Sync + 'static,
+) -> bool {
+    INIT_BUILDER.set(Box::new(builder)).is_ok()
+}
+
 /// Ensure the job queue system is initialized.
 ///
 /// This can be called from any API endpoint - it will initialize on first call
-/// and return immediately on subsequent calls.
+/// and return immediately on subsequent calls. Uses the builder given to
+/// [`set_init_builder`], or [`default_handlers`] and [`InitConfig::default`];
+/// either way the environment is applied with [`InitConfig::with_env`].
 pub async fn ensure_initialized() -> Result<(), String> {
     INIT.get_or_init(|| async {
-        match init_job_queue_inner().await {
-            Ok(()) => Ok(()),
+        let (handlers, config) = match INIT_BUILDER.get() {
+            Some(builder) => builder(),
+            None => (default_handlers(), InitConfig::default()),
+        };
+        let config = match config.with_env() {
+            Ok(config) => config,
             Err(e) => {
                 tracing::error!("Failed to initialize job queue: {}", e);
-                Err(e.to_string())
+                return Err(e);
             }
-        }
+        };
+        run_init(handlers, config).await
     })
     .await
     .clone()
@@ -34,110 +191,159 @@ pub async fn init_job_queue() -> Result<(), Box<dyn std::error::Error>> {
     ensure_initialized().await.map_err(|e| e.into())
 }
 
-/// Internal initialization logic.
-async fn init_job_queue_inner() -> Result<(), Box<dyn std::error::Error>> {
-    tracing::info!("Initializing job queue system...");
+/// Initialize the job queue system with your own handlers.
+///
+/// Register domain handlers on `registry` before calling this; the demo set
+/// is added underneath unless [`InitConfig::register_demo_handlers`] is off.
+/// Initialization happens once; if it already ran, the handlers are ignored
+/// and its result is returned. `config` is used as given; apply
+/// [`InitConfig::with_env`] first to honour the environment settings.
+pub async fn init_with_handlers(
+    registry: JobHandlerRegistry,
+    config: InitConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if INIT.initialized() {
+        tracing::warn!("Job queue already initialized; ignoring handlers");
+    }
 
-    // Initialize database
-    let db_config = if std::env::var("RAILWAY_ENVIRONMENT").is_ok() {
-        // Railway deployment - use file-based storage
-        DbConfig::file("./data/surrealdb")
-    } else {
-        // Local development - use in-memory
-        DbConfig::memory()
-    };
+    INIT.get_or_init(|| run_init(registry, config))
+        .await
+        .clone()
+        .map_err(|e| e.into())
+}
 
-    init_db(db_config).await?;
+/// Run initialization, logging a failure.
+async fn run_init(handlers: JobHandlerRegistry, config: InitConfig) -> Result<(), String> {
+    match init_job_queue_inner(handlers, config).await {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            tracing::error!("Failed to initialize job queue: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
 
-    // Create handler registry with demo handlers
+/// The demo handlers: `echo`, `sleep` and `fail`.
+pub fn default_handlers() -> JobHandlerRegistry {
     let mut handlers = JobHandlerRegistry::new();
+    add_demo_handlers(&mut handlers);
+    handlers
+}
+
+/// The queue the demo handlers' jobs go to by default.
+fn demo_queue() -> QueueBuilder {
+    QueueBuilder::new("demo").description("Demo queue for testing")
+}
+
+/// Register a handler unless its job type is already handled.
+fn register_missing<H: JobHandler>(handlers: &mut JobHandlerRegistry, handler: H) {
+    if !handlers.has_handler(handler.job_type()) {
+        handlers.register(handler);
+    }
+}
+
+/// A one-field object payload for a demo handler's test fire.
+fn sample_payload(key: &str, value: impl Into<Value>) -> Value {
+    let mut fields = Map::new();
+    fields.insert(key.to_string(), value.into());
+    Value::Object(fields)
+}
 
+/// Add each demo handler whose job type `handlers` doesn't handle yet.
+fn add_demo_handlers(handlers: &mut JobHandlerRegistry) {
     // Demo: Echo handler
-    handlers.register(FnHandler::new("echo", |job: &Job| {
-        let payload = job.payload.clone();
-        Box::pin(async move {
-            tracing::info!("Echo job: {:?}", payload);
-            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-            Ok(JobResult::with_output("Echo completed", payload))
+    register_missing(
+        handlers,
+        FnHandler::new("echo", |job: &Job, ctx: JobContext| {
+            let payload = job.payload.clone();
+            Box::pin(async move {
+                tracing::info!("Echo job: {:?}", payload);
+                ctx.info(format!("Echoing payload: {}", payload));
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                Ok(JobResult::with_output("Echo completed", payload))
+            })
         })
-    }));
+        .with_sample_payload(sample_payload("message", "Hello, world!")),
+    );
 
     // Demo: Sleep handler
-    handlers.register(FnHandler::new("sleep", |job: &Job| {
-        let seconds = job
-            .payload
-            .get("seconds")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(5);
-        Box::pin(async move {
-            tracing::info!("Sleeping for {} seconds", seconds);
-            tokio::time::sleep(std::time::Duration::from_secs(seconds)).await;
-            Ok(JobResult::new(format!("Slept for {} seconds", seconds)))
+    register_missing(
+        handlers,
+        FnHandler::new("sleep", |job: &Job, ctx: JobContext| {
+            let seconds = job
+                .payload
+                .get("seconds")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(5);
+            Box::pin(async move {
+                tracing::info!("Sleeping for {} seconds", seconds);
+                ctx.info(format!("Sleeping for {} seconds", seconds));
+                tokio::time::sleep(std::time::Duration::from_secs(seconds)).await;
+                Ok(JobResult::new(format!("Slept for {} seconds", seconds)))
+            })
         })
-    }));
+        .with_sample_payload(sample_payload("seconds", 5)),
+    );
 
     // Demo: Failing handler (for testing retries)
-    handlers.register(FnHandler::new("fail", |job: &Job| {
-        let should_fail = job
-            .payload
-            .get("fail")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(true);
-        Box::pin(async move {
-            if should_fail {
-                Err("Intentional failure".into())
-            } else {
-                Ok(JobResult::new("Success"))
-            }
+    register_missing(
+        handlers,
+        FnHandler::new("fail", |job: &Job, ctx: JobContext| {
+            let should_fail = job
+                .payload
+                .get("fail")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            Box::pin(async move {
+                if should_fail {
+                    ctx.error("Failing on purpose");
+                    Err("Intentional failure".into())
+                } else {
+                    Ok(JobResult::new("Success"))
+                }
+            })
         })
-    }));
+        .with_sample_payload(sample_payload("fail", true)),
+    );
+}
+
+/// Internal initialization logic.
+async fn init_job_queue_inner(
+    mut handlers: JobHandlerRegistry,
+    mut config: InitConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    tracing::info!("Initializing job queue system...");
+
+    if config.register_demo_handlers {
+        add_demo_handlers(&mut handlers);
+    }
+    if config.create_demo_queue {
+        config.queues.push(demo_queue());
+    }
+
+    init_db(config.db).await?;
 
-    // Start supervisor
-    let (supervisor, _handle) = start_supervisor(handlers).await?;
+    // Start supervisor, restoring queues from persistence
+    let (supervisor, _handle) = start_supervisor(SupervisorArgs {
+        max_concurrent_jobs: config.max_concurrent_jobs,
+        history_retention: config.history_retention,
+        heartbeat_sampling: config.heartbeat_sampling,
+        webhook_secret: config.webhook_secret,
+        ..SupervisorArgs::new(handlers)
+    })
+    .await?;
 
     // Register globally
     global_registry().register_supervisor(supervisor.clone());
 
-    // Rehydrate queues from persistence (or create demo queue if none exist)
-    let queues = db::repositories::QueueRepository::list()
-        .await
-        .unwrap_or_default();
-    if queues.is_empty() {
-        tracing::info!("Creating demo queue...");
-        let (tx, rx) = actors::concurrency::oneshot();
-        supervisor.send_message(actors::SupervisorMessage::CreateQueue {
-            name: "demo".to_string(),
-            description: Some("Demo queue for testing".to_string()),
-            reply: tx.into(),
-        })?;
-
-        match rx.await {
-            Ok(Ok(queue)) => tracing::info!("Created demo queue: {}", queue.id),
-            Ok(Err(e)) => tracing::warn!("Failed to create demo queue: {}", e),
-            Err(_) => tracing::warn!("Timeout creating demo queue"),
-        }
-    } else {
-        for queue in queues {
-            let (tx, rx) = actors::concurrency::oneshot();
-            if supervisor
-                .send_message(actors::SupervisorMessage::RegisterQueue {
-                    queue: queue.clone(),
-                    reply: tx.into(),
-                })
-                .is_err()
-            {
-                tracing::warn!("Failed to register queue {}", queue.id);
-                continue;
-            }
-
-            match rx.await {
-                Ok(Ok(_)) => {
-                    tracing::info!("Registered queue: {}", queue.id);
-                }
-                Ok(Err(e)) => tracing::warn!("Failed to register queue {}: {}", queue.id, e),
-                Err(_) => tracing::warn!("Timeout registering queue {}", queue.id),
+    // Create declared queues that weren't restored from the database
+    match declare_queues(&supervisor, config.queues).await {
+        Ok(created) => {
+            for queue in created {
+                tracing::info!("Created queue {}: {}", queue.name, queue.id);
             }
         }
+        Err(e) => tracing::warn!("Failed to create declared queues: {}", e),
     }
 
     tracing::info!("Job queue system initialized");
@@ -1,13 +1,17 @@
 //! Job management server functions.
 
 use dioxus::prelude::*;
-use queue_core::Job;
+use queue_core::{CancellationReason, EnqueueReceipt, Job, JobRecordView, Priority};
 #[cfg(feature = "server")]
-use queue_core::{JobId, Priority, QueueId};
+use queue_core::{JobId, QueueId};
 use serde_json::Value as JsonValue;
 
+#[cfg(feature = "server")]
+use crate::JobApiError;
+
 /// Request type for creating a job.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreateJobRequest {
     pub queue_id: String,
     pub job_type: String,
@@ -20,6 +24,9 @@ pub struct CreateJobRequest {
     pub timeout_secs: Option<u64>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Hold the job back for this many seconds before it may run.
+    #[serde(default)]
+    pub delay_secs: Option<u64>,
 }
 
 /// Enqueue a new job.
@@ -27,54 +34,55 @@ pub struct CreateJobRequest {
 pub async fn enqueue_job(request: CreateJobRequest) -> Result<Job, ServerFnError> {
     #[cfg(feature = "server")]
     {
-        use actors::SupervisorMessage;
-        use actors::global_registry;
-
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
-        let queue_id = QueueId::parse(&request.queue_id)
-            .map_err(|e| ServerFnError::new(format!("Invalid queue ID: {}", e)))?;
+        Ok(enqueue_request(request).await?)
+    }
 
-        let priority = request
-            .priority
-            .as_deref()
-            .map(|p| match p {
-                "low" => Priority::Low,
-                "high" => Priority::High,
-                "critical" => Priority::Critical,
-                _ => Priority::Normal,
-            })
-            .unwrap_or(Priority::Normal);
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
 
-        let mut job = Job::new(queue_id, &request.job_type, request.payload.clone())
-            .with_priority(priority)
-            .with_tags(request.tags);
+/// Enqueue a new job without waiting for it, reporting its place in line.
+///
+/// The position counts the pending jobs that workers will pick up first, by
+/// priority and then age.
+#[post("/api/jobs/try-enqueue")]
+pub async fn try_enqueue(request: CreateJobRequest) -> Result<EnqueueReceipt, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
 
-        if let Some(max_retries) = request.max_retries {
-            job = job.with_max_retries(max_retries);
-        }
-        if let Some(timeout) = request.timeout_secs {
-            job = job.with_timeout(timeout);
-        }
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let (queue_id, job, overrides) = job_from_request(request)?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
-            .send_message(SupervisorMessage::EnqueueJob {
+            .send_message(SupervisorMessage::TryEnqueueJob {
                 queue_id,
                 job,
+                overrides,
                 reply: tx.into(),
             })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
-        rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))?
-            .map_err(ServerFnError::new)
+        Ok(rx
+            .await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(JobApiError::from_enqueue)?)
     }
 
     #[cfg(not(feature = "server"))]
@@ -83,24 +91,154 @@ pub async fn enqueue_job(request: CreateJobRequest) -> Result<Job, ServerFnError
     }
 }
 
+/// Enqueue a job and wait up to `timeout_secs` for it to finish.
+///
+/// Returns the job once it completes, fails without a retry left, or is
+/// cancelled. If it is still going when the timeout passes, its current state
+/// is returned and the job is left running.
+#[post("/api/jobs/enqueue-and-wait")]
+pub async fn enqueue_and_wait(
+    request: CreateJobRequest,
+    timeout_secs: u64,
+) -> Result<Job, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+        use tokio::sync::broadcast::{self, error::RecvError};
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        // Subscribe before enqueueing so a fast job can't finish unseen
+        let (events_tx, mut events) = broadcast::channel(1024);
+        supervisor
+            .send_message(SupervisorMessage::Subscribe { sender: events_tx })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        let job = enqueue_request(request).await?;
+
+        let finished = async {
+            loop {
+                match events.recv().await {
+                    Ok(event) if event.job_id() == Some(job.id) && event.is_job_finished() => {
+                        break;
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        };
+        if tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), finished)
+            .await
+            .is_err()
+        {
+            tracing::debug!("Job {} still running after {}s", job.id, timeout_secs);
+        }
+
+        Ok(get_job(job.id.to_string()).await?.unwrap_or(job))
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Build a job from `request` and hand it to its queue.
+#[cfg(feature = "server")]
+async fn enqueue_request(request: CreateJobRequest) -> Result<Job, JobApiError> {
+    use actors::SupervisorMessage;
+    use actors::global_registry;
+
+    let (queue_id, job, overrides) = job_from_request(request)?;
+
+    let supervisor = global_registry()
+        .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+        .await
+        .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+    let (tx, rx) = actors::concurrency::oneshot();
+    supervisor
+        .send_message(SupervisorMessage::EnqueueJob {
+            queue_id,
+            job,
+            overrides,
+            reply: tx.into(),
+        })
+        .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+    rx.await
+        .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+        .map_err(JobApiError::from_enqueue)
+}
+
+/// The target queue, job and settings overrides a create request describes.
+#[cfg(feature = "server")]
+fn job_from_request(
+    request: CreateJobRequest,
+) -> Result<(QueueId, Job, queue_core::JobOverrides), JobApiError> {
+    let queue_id = QueueId::parse(&request.queue_id)
+        .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+    let priority = request
+        .priority
+        .as_deref()
+        .map(|p| match p {
+            "deferred" => Priority::Deferred,
+            "low" => Priority::Low,
+            "high" => Priority::High,
+            "critical" => Priority::Critical,
+            _ => Priority::Normal,
+        })
+        .unwrap_or(Priority::Normal);
+
+    let mut job = Job::new(queue_id, &request.job_type, request.payload.clone())
+        .with_priority(priority)
+        .with_tags(request.tags);
+    if let Some(delay) = request.delay_secs {
+        job = job.with_delay(std::time::Duration::from_secs(delay));
+    }
+
+    // Unset retries/timeout are filled from the queue's config when enqueued
+    let overrides = queue_core::JobOverrides {
+        max_retries: request.max_retries,
+        timeout_secs: request.timeout_secs,
+    };
+
+    Ok((queue_id, job, overrides))
+}
+
 /// Get a job by ID.
+///
+/// Jobs no queue holds in memory are looked up in the database, then in job
+/// history, so a job that just finished is still returned.
 #[get("/api/jobs/:id")]
 pub async fn get_job(id: String) -> Result<Option<Job>, ServerFnError> {
     #[cfg(feature = "server")]
     {
         use actors::SupervisorMessage;
         use actors::global_registry;
+        use db::DbError;
+        use db::repositories::JobRepository;
 
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
-        let job_id =
-            JobId::parse(&id).map_err(|e| ServerFnError::new(format!("Invalid job ID: {}", e)))?;
+        let job_id = JobId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid job ID: {}", e)))?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
@@ -108,10 +246,87 @@ pub async fn get_job(id: String) -> Result<Option<Job>, ServerFnError> {
                 job_id,
                 reply: tx.into(),
             })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
-        rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))
+        if let Some(job) = rx
+            .await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+        {
+            return Ok(Some(job));
+        }
+
+        let stored = match JobRepository::get(job_id).await {
+            Err(DbError::NotFound(_)) => JobRepository::get_history(job_id)
+                .await
+                .map(|archived| archived.into_job()),
+            stored => stored,
+        };
+        match stored {
+            Ok(job) => Ok(Some(job)),
+            Err(DbError::NotFound(_)) => Ok(None),
+            Err(e) => Err(JobApiError::Internal(format!("Database error: {}", e)).into()),
+        }
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Find a job by ID in active jobs or job history.
+///
+/// Used as a fallback when `get_job` no longer sees the job in memory.
+#[get("/api/jobs/:id/find")]
+pub async fn find_job(id: String) -> Result<Option<JobRecordView>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use db::repositories::JobRepository;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let job_id = JobId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid job ID: {}", e)))?;
+
+        JobRepository::find_anywhere(job_id)
+            .await
+            .map(|views| views.into_iter().next())
+            .map_err(|e| JobApiError::Internal(format!("Database error: {}", e)).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Search jobs across all queues, including archived history.
+#[post("/api/jobs/find")]
+pub async fn find_jobs(
+    status: Option<String>,
+    job_type: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<JobRecordView>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use db::repositories::JobRepository;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let filter = db::repositories::JobFilter {
+            status,
+            job_type,
+            limit: Some(limit.unwrap_or(100)),
+            ..Default::default()
+        };
+
+        JobRepository::find_anywhere(filter)
+            .await
+            .map_err(|e| JobApiError::Internal(format!("Database error: {}", e)).into())
     }
 
     #[cfg(not(feature = "server"))]
@@ -122,7 +337,10 @@ pub async fn get_job(id: String) -> Result<Option<Job>, ServerFnError> {
 
 /// Cancel a job.
 #[post("/api/jobs/:id/cancel")]
-pub async fn cancel_job(id: String, reason: Option<String>) -> Result<(), ServerFnError> {
+pub async fn cancel_job(
+    id: String,
+    reason: Option<CancellationReason>,
+) -> Result<(), ServerFnError> {
     #[cfg(feature = "server")]
     {
         use actors::SupervisorMessage;
@@ -130,14 +348,15 @@ pub async fn cancel_job(id: String, reason: Option<String>) -> Result<(), Server
 
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
-        let job_id =
-            JobId::parse(&id).map_err(|e| ServerFnError::new(format!("Invalid job ID: {}", e)))?;
+        let job_id = JobId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid job ID: {}", e)))?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
@@ -146,11 +365,68 @@ pub async fn cancel_job(id: String, reason: Option<String>) -> Result<(), Server
                 reason,
                 reply: tx.into(),
             })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        match rx
+            .await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+        {
+            Err(e) if e == actors::JOB_NOT_FOUND => {
+                // Jobs archived before the last restart are only in history
+                let archived = db::repositories::JobRepository::find_anywhere(job_id)
+                    .await
+                    .map_err(|e| JobApiError::Internal(format!("Failed to look up job: {}", e)))?;
+                Err(match archived.first() {
+                    Some(view) => JobApiError::InvalidRequest(format!(
+                        "Job is already {} and cannot be cancelled",
+                        view.status
+                    )),
+                    None => JobApiError::NotFound(format!("Job not found: {}", job_id)),
+                }
+                .into())
+            }
+            result => result.map_err(|e| JobApiError::from_reply(e).into()),
+        }
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Change the priority of a pending job.
+#[post("/api/jobs/:id/priority")]
+pub async fn reprioritize_job(id: String, priority: Priority) -> Result<Job, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let job_id = JobId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid job ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::ReprioritizeJob {
+                job_id,
+                priority,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
         rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))?
-            .map_err(ServerFnError::new)
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
     }
 
     #[cfg(not(feature = "server"))]
@@ -159,6 +435,454 @@ pub async fn cancel_job(id: String, reason: Option<String>) -> Result<(), Server
     }
 }
 
+/// Hold a pending job back from workers until it is resumed.
+#[post("/api/jobs/:id/pause")]
+pub async fn pause_job(id: String) -> Result<Job, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let job_id = JobId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid job ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::PauseJob {
+                job_id,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Return a paused job to the pending queue.
+#[post("/api/jobs/:id/resume")]
+pub async fn resume_job(id: String) -> Result<Job, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let job_id = JobId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid job ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::ResumeJob {
+                job_id,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Enqueue a fresh copy of an archived job, for replaying it while debugging.
+///
+/// `history_id` is the ID the job had while active. The copy gets a new ID and
+/// keeps the original queue, job type, payload, priority and tags.
+#[post("/api/jobs/history/:history_id/replay")]
+pub async fn replay_job(history_id: String) -> Result<Job, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+        use db::DbError;
+        use db::repositories::JobRepository;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let job_id = JobId::parse(&history_id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid job ID: {}", e)))?;
+
+        let archived = JobRepository::get_history(job_id)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound(message) => JobApiError::NotFound(message),
+                e => JobApiError::Internal(format!("Database error: {}", e)),
+            })?;
+        let job = archived.replay().ok_or_else(|| {
+            JobApiError::InvalidRequest(
+                "Job was archived without its payload and can't be replayed".into(),
+            )
+        })?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::EnqueueJob {
+                queue_id: job.queue_id,
+                job,
+                overrides: queue_core::JobOverrides::default(),
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_enqueue(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Enqueue fresh copies of every failed job of a type archived from a queue,
+/// e.g. after fixing a handler bug. Returns how many were requeued.
+///
+/// Failures archived without their payload are skipped. The archived rows are
+/// kept, so calling this twice requeues the same failures again. If a job is
+/// rejected partway, the ones before it stay enqueued and the error says how
+/// many there were.
+#[post("/api/queues/:queue_id/requeue-failed")]
+pub async fn requeue_failed(queue_id: String, job_type: String) -> Result<u64, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+        use db::repositories::JobRepository;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&queue_id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let jobs: Vec<Job> = JobRepository::failed_history(queue_id, &job_type)
+            .await
+            .map_err(|e| JobApiError::Internal(format!("Database error: {}", e)))?
+            .iter()
+            .filter_map(|archived| archived.replay())
+            .collect();
+        if jobs.is_empty() {
+            return Ok(0);
+        }
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let total = jobs.len();
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::EnqueueJobs {
+                queue_id,
+                jobs,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map(|enqueued| enqueued.len() as u64)
+            .map_err(|e| {
+                let error = JobApiError::from_enqueue(e.error);
+                if e.enqueued.is_empty() {
+                    return error.into();
+                }
+                // Those already requeued stay pending; say so, or a retry duplicates them
+                error
+                    .with_context(format!(
+                        "Requeued {} of {} failed jobs before one was rejected",
+                        e.enqueued.len(),
+                        total
+                    ))
+                    .into()
+            })
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Cancel every pending or running job in a queue, optionally filtered by status.
+///
+/// Returns the number of jobs cancelled.
+#[post("/api/queues/:queue_id/jobs/cancel")]
+pub async fn cancel_jobs(
+    queue_id: String,
+    status: Option<String>,
+    reason: Option<CancellationReason>,
+) -> Result<usize, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&queue_id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::CancelJobs {
+                queue_id,
+                status_filter: status,
+                reason,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Cancel every pending or running job in a queue carrying `tag`.
+///
+/// Returns the number of jobs cancelled.
+#[post("/api/queues/:queue_id/jobs/cancel-by-tag")]
+pub async fn cancel_jobs_by_tag(
+    queue_id: String,
+    tag: String,
+    reason: Option<CancellationReason>,
+) -> Result<usize, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&queue_id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::CancelJobsByTag {
+                queue_id,
+                tag,
+                reason,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Retry every failed or cancelled job in a queue carrying `tag`.
+///
+/// Returns the number of jobs put back in line.
+#[post("/api/queues/:queue_id/jobs/retry-by-tag")]
+pub async fn retry_jobs_by_tag(queue_id: String, tag: String) -> Result<usize, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&queue_id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::RetryJobsByTag {
+                queue_id,
+                tag,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Cancel each of the given jobs in a queue that isn't finished yet.
+///
+/// Jobs that already finished or aren't in the queue are skipped. Returns the
+/// number of jobs cancelled.
+#[post("/api/queues/:queue_id/jobs/cancel-by-id")]
+pub async fn cancel_jobs_by_id(
+    queue_id: String,
+    ids: Vec<String>,
+    reason: Option<CancellationReason>,
+) -> Result<usize, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&queue_id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+        let job_ids = parse_job_ids(&ids)?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::CancelJobsById {
+                queue_id,
+                job_ids,
+                reason,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Retry each of the given jobs in a queue that failed or was cancelled.
+///
+/// Jobs that can't be retried are skipped. Returns the number of jobs put
+/// back in line.
+#[post("/api/queues/:queue_id/jobs/retry-by-id")]
+pub async fn retry_jobs_by_id(queue_id: String, ids: Vec<String>) -> Result<usize, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&queue_id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+        let job_ids = parse_job_ids(&ids)?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::RetryJobsById {
+                queue_id,
+                job_ids,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Parse job IDs, rejecting the request if any is malformed.
+#[cfg(feature = "server")]
+fn parse_job_ids(ids: &[String]) -> Result<Vec<JobId>, JobApiError> {
+    ids.iter()
+        .map(|id| {
+            JobId::parse(id)
+                .map_err(|e| JobApiError::InvalidRequest(format!("Invalid job ID {}: {}", id, e)))
+        })
+        .collect()
+}
+
 /// List jobs in a queue.
 #[post("/api/queues/:queue_id/jobs")]
 pub async fn list_queue_jobs(
@@ -172,10 +896,10 @@ pub async fn list_queue_jobs(
 
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
         let queue_id = QueueId::parse(&queue_id)
-            .map_err(|e| ServerFnError::new(format!("Invalid queue ID: {}", e)))?;
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
 
         let filter = db::repositories::JobFilter {
             queue_id: Some(queue_id),
@@ -186,7 +910,7 @@ pub async fn list_queue_jobs(
 
         JobRepository::list(filter)
             .await
-            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))
+            .map_err(|e| JobApiError::Internal(format!("Database error: {}", e)).into())
     }
 
     #[cfg(not(feature = "server"))]
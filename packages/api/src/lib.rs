@@ -1,15 +1,29 @@
 //! Server API functions for the job queue system.
 //!
 //! This crate contains all shared fullstack server functions for:
-//! - Queue management (create, list, pause, resume)
+//! - Queue management (create, list, pause, resume, drain, stop)
 //! - Job management (enqueue, get, cancel, retry)
-//! - Real-time events (SSE streaming)
+//! - Worker status (live workers per queue)
+//! - Typed errors (`JobApiError`) carried inside `ServerFnError`
+//! - Real-time events (SSE streaming) and the stored event history
+//! - Health checks for readiness probes
+//! - Aggregate system status for the overview dashboard
+//! - OpenAPI spec (`openapi` feature)
 
 mod echo;
+mod error;
+mod events;
+mod health;
 mod jobs;
 mod queues;
+mod status;
+mod workers;
 
-pub use echo::echo;
+pub use echo::{JsonEcho, echo, echo_json};
+pub use error::JobApiError;
+pub use events::list_events;
+pub use health::{HealthStatus, health};
+pub use status::{SystemStatus, system_status};
 
 #[cfg(feature = "server")]
 mod init;
@@ -17,9 +31,13 @@ mod init;
 #[cfg(feature = "server")]
 mod realtime;
 
+#[cfg(feature = "openapi")]
+mod openapi;
+
 // Re-export all server functions
 pub use jobs::*;
 pub use queues::*;
+pub use workers::*;
 
 #[cfg(feature = "server")]
 pub use init::*;
@@ -27,7 +45,12 @@ pub use init::*;
 #[cfg(feature = "server")]
 pub use realtime::*;
 
+#[cfg(feature = "openapi")]
+pub use openapi::*;
+
 // Re-export core types for convenience
 pub use queue_core::{
-    Job, JobEvent, JobId, JobStatus, Priority, Queue, QueueId, QueueState, QueueStats,
+    CancellationReason, EnqueueReceipt, HandlerSource, Job, JobEvent, JobId, JobRecordView,
+    JobSource, JobStatus, JobTypeConfig, JobTypeInfo, Priority, Queue, QueueId, QueueState,
+    QueueStats, ThroughputBucket, WorkerInfo,
 };
@@ -0,0 +1,603 @@
+//! OpenAPI description of the server functions.
+//!
+//! Dioxus server functions don't carry route metadata we can introspect, so the
+//! endpoint table below mirrors the `#[get]`/`#[post]` attributes in this crate
+//! and must be kept in sync when server functions are added or renamed.
+
+use dioxus::prelude::*;
+use serde_json::Value as JsonValue;
+use utoipa::OpenApi;
+use utoipa::openapi::path::{HttpMethod, OperationBuilder, ParameterBuilder, ParameterIn};
+use utoipa::openapi::request_body::RequestBodyBuilder;
+use utoipa::openapi::{
+    ArrayBuilder, ContentBuilder, ObjectBuilder, PathItem, PathsBuilder, Ref, RefOr, Required,
+    ResponseBuilder, Schema, Type,
+};
+
+use crate::{
+    CreateJobRequest, HealthStatus, JobApiError, JsonEcho, QueueDescription, QueueJobCounts,
+    SystemStatus,
+};
+use queue_core::{
+    EnqueueReceipt, HandlerSource, Job, JobEvent, JobRecordView, JobTypeConfig, JobTypeInfo, Queue,
+    ThroughputBucket, WorkerInfo,
+};
+
+/// Shape of a server function's response body.
+enum Returns {
+    Unit,
+    Text,
+    Count,
+    Json,
+    One(&'static str),
+    Optional(&'static str),
+    Many(&'static str),
+}
+
+/// Shape of a server function's request body, or its optional query parameters.
+enum Accepts {
+    Nothing,
+    Schema(&'static str),
+    Fields(&'static [(&'static str, Type)]),
+    Query(&'static [(&'static str, Type)]),
+}
+
+struct Endpoint {
+    method: HttpMethod,
+    path: &'static str,
+    operation_id: &'static str,
+    summary: &'static str,
+    tag: &'static str,
+    accepts: Accepts,
+    returns: Returns,
+}
+
+const ENDPOINTS: &[Endpoint] = &[
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/jobs/enqueue",
+        operation_id: "enqueue_job",
+        summary: "Enqueue a new job.",
+        tag: "jobs",
+        accepts: Accepts::Schema("CreateJobRequest"),
+        returns: Returns::One("Job"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/jobs/try-enqueue",
+        operation_id: "try_enqueue",
+        summary: "Enqueue a new job without waiting for it, reporting its place in line.",
+        tag: "jobs",
+        accepts: Accepts::Schema("CreateJobRequest"),
+        returns: Returns::One("EnqueueReceipt"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/jobs/enqueue-and-wait",
+        operation_id: "enqueue_and_wait",
+        summary: "Enqueue a job and wait up to `timeout_secs` for it to finish.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("request", Type::Object), ("timeout_secs", Type::Integer)]),
+        returns: Returns::One("Job"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/jobs/{id}",
+        operation_id: "get_job",
+        summary: "Get a job by ID.",
+        tag: "jobs",
+        accepts: Accepts::Nothing,
+        returns: Returns::Optional("Job"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/jobs/{id}/find",
+        operation_id: "find_job",
+        summary: "Find a job by ID in active jobs or job history.",
+        tag: "jobs",
+        accepts: Accepts::Nothing,
+        returns: Returns::Optional("JobRecordView"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/jobs/find",
+        operation_id: "find_jobs",
+        summary: "Search jobs across all queues, including archived history.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[
+            ("status", Type::String),
+            ("job_type", Type::String),
+            ("limit", Type::Integer),
+        ]),
+        returns: Returns::Many("JobRecordView"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/jobs/{id}/cancel",
+        operation_id: "cancel_job",
+        summary: "Cancel a job.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("reason", Type::String)]),
+        returns: Returns::Unit,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/jobs/{id}/priority",
+        operation_id: "reprioritize_job",
+        summary: "Change the priority of a pending job.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("priority", Type::String)]),
+        returns: Returns::One("Job"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/jobs/{id}/pause",
+        operation_id: "pause_job",
+        summary: "Hold a pending job back from workers until it is resumed.",
+        tag: "jobs",
+        accepts: Accepts::Nothing,
+        returns: Returns::One("Job"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/jobs/{id}/resume",
+        operation_id: "resume_job",
+        summary: "Return a paused job to the pending queue.",
+        tag: "jobs",
+        accepts: Accepts::Nothing,
+        returns: Returns::One("Job"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/jobs/history/{history_id}/replay",
+        operation_id: "replay_job",
+        summary: "Enqueue a fresh copy of an archived job.",
+        tag: "jobs",
+        accepts: Accepts::Nothing,
+        returns: Returns::One("Job"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{queue_id}/requeue-failed",
+        operation_id: "requeue_failed",
+        summary: "Enqueue fresh copies of every archived failure of a job type.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("job_type", Type::String)]),
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{queue_id}/jobs/cancel",
+        operation_id: "cancel_jobs",
+        summary: "Cancel every pending or running job in a queue.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("status", Type::String), ("reason", Type::String)]),
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{queue_id}/jobs/cancel-by-tag",
+        operation_id: "cancel_jobs_by_tag",
+        summary: "Cancel every pending or running job in a queue carrying a tag.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("tag", Type::String), ("reason", Type::String)]),
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{queue_id}/jobs/retry-by-tag",
+        operation_id: "retry_jobs_by_tag",
+        summary: "Retry every failed or cancelled job in a queue carrying a tag.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("tag", Type::String)]),
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{queue_id}/jobs/cancel-by-id",
+        operation_id: "cancel_jobs_by_id",
+        summary: "Cancel each of the given jobs in a queue that isn't finished yet.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("ids", Type::Array), ("reason", Type::String)]),
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{queue_id}/jobs/retry-by-id",
+        operation_id: "retry_jobs_by_id",
+        summary: "Retry each of the given jobs in a queue that failed or was cancelled.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("ids", Type::Array)]),
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{queue_id}/jobs",
+        operation_id: "list_queue_jobs",
+        summary: "List jobs in a queue.",
+        tag: "jobs",
+        accepts: Accepts::Fields(&[("status", Type::String), ("limit", Type::Integer)]),
+        returns: Returns::Many("Job"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/create",
+        operation_id: "create_queue",
+        summary: "Create a new queue, using the default config when none is given.",
+        tag: "queues",
+        accepts: Accepts::Fields(&[
+            ("name", Type::String),
+            ("description", Type::String),
+            ("config", Type::Object),
+            ("tags", Type::Array),
+        ]),
+        returns: Returns::One("Queue"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/queues",
+        operation_id: "list_queues",
+        summary: "List all queues.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Many("Queue"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/queues/stats",
+        operation_id: "all_queue_stats",
+        summary: "Job counts by status for every queue, read from the database.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Many("QueueJobCounts"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/queues/{id}",
+        operation_id: "get_queue",
+        summary: "Get a queue by ID.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Optional("Queue"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/queues/by-name/{name}",
+        operation_id: "get_queue_by_name",
+        summary: "Get a queue by name.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Optional("Queue"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/queues/{id}/describe",
+        operation_id: "describe_queue",
+        summary: "Describe a queue: its config, live and stored job counts, and workers.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::One("QueueDescription"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{id}/pause",
+        operation_id: "pause_queue",
+        summary: "Pause a queue.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Unit,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{id}/resume",
+        operation_id: "resume_queue",
+        summary: "Resume a paused queue, or start a stopped one.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Unit,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/by-tag/{tag}/pause",
+        operation_id: "pause_queues_by_tag",
+        summary: "Pause every queue carrying a tag, returning how many matched.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/by-tag/{tag}/resume",
+        operation_id: "resume_queues_by_tag",
+        summary: "Resume every queue carrying a tag, returning how many matched.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{id}/drain",
+        operation_id: "drain_queue",
+        summary: "Finish a queue's queued jobs without accepting new ones, then stop it.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Unit,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{id}/stop",
+        operation_id: "stop_queue",
+        summary: "Stop a queue.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Unit,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{queue_id}/throughput",
+        operation_id: "queue_throughput",
+        summary: "Completed and failed job counts per time bucket over a trailing window.",
+        tag: "queues",
+        accepts: Accepts::Fields(&[
+            ("bucket_secs", Type::Integer),
+            ("window_secs", Type::Integer),
+        ]),
+        returns: Returns::Many("ThroughputBucket"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/queues/{id}/job-types/{job_type}/config",
+        operation_id: "job_type_config",
+        summary: "Resolved handler, timeout and retries for a job type in a queue.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::One("JobTypeConfig"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/job-types",
+        operation_id: "list_job_types",
+        summary: "List the job types with a registered handler, or those `queue_id` can run.",
+        tag: "jobs",
+        accepts: Accepts::Query(&[("queue_id", Type::String)]),
+        returns: Returns::Many("JobTypeInfo"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{id}/delete",
+        operation_id: "delete_queue",
+        summary: "Delete a queue, optionally draining it for up to `drain_timeout_secs` first.",
+        tag: "queues",
+        accepts: Accepts::Fields(&[("drain_timeout_secs", Type::Integer)]),
+        returns: Returns::Unit,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{id}/purge",
+        operation_id: "purge_queue",
+        summary: "Drop all pending jobs from a queue.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{id}/requeue-running",
+        operation_id: "requeue_running",
+        summary: "Return a queue's running jobs to pending.",
+        tag: "queues",
+        accepts: Accepts::Nothing,
+        returns: Returns::Count,
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/workers",
+        operation_id: "list_workers",
+        summary: "List live workers with their current job and last heartbeat.",
+        tag: "workers",
+        accepts: Accepts::Nothing,
+        returns: Returns::Many("WorkerInfo"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/queues/{queue_id}/events",
+        operation_id: "list_events",
+        summary: "Logged events for a queue since a timestamp, oldest first.",
+        tag: "events",
+        accepts: Accepts::Fields(&[("since", Type::String), ("limit", Type::Integer)]),
+        returns: Returns::Many("JobEvent"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/health",
+        operation_id: "health",
+        summary: "Report subsystem status without triggering initialization.",
+        tag: "misc",
+        accepts: Accepts::Nothing,
+        returns: Returns::One("HealthStatus"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/status",
+        operation_id: "system_status",
+        summary: "Get aggregate status across all queues.",
+        tag: "misc",
+        accepts: Accepts::Nothing,
+        returns: Returns::One("SystemStatus"),
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/echo",
+        operation_id: "echo",
+        summary: "Echo the user input on the server.",
+        tag: "misc",
+        accepts: Accepts::Fields(&[("input", Type::String)]),
+        returns: Returns::Text,
+    },
+    Endpoint {
+        method: HttpMethod::Post,
+        path: "/api/echo/json",
+        operation_id: "echo_json",
+        summary: "Echo a JSON value unchanged, with the server's time and features.",
+        tag: "misc",
+        accepts: Accepts::Fields(&[("value", Type::Object)]),
+        returns: Returns::One("JsonEcho"),
+    },
+    Endpoint {
+        method: HttpMethod::Get,
+        path: "/api/openapi.json",
+        operation_id: "openapi_json",
+        summary: "Serve this OpenAPI document.",
+        tag: "misc",
+        accepts: Accepts::Nothing,
+        returns: Returns::Json,
+    },
+];
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Job Queue API",
+        description = "Queue and job management server functions."
+    ),
+    components(schemas(
+        Job,
+        EnqueueReceipt,
+        JobRecordView,
+        Queue,
+        JobEvent,
+        CreateJobRequest,
+        HealthStatus,
+        JsonEcho,
+        SystemStatus,
+        JobApiError,
+        ThroughputBucket,
+        QueueJobCounts,
+        QueueDescription,
+        JobTypeConfig,
+        HandlerSource,
+        JobTypeInfo,
+        WorkerInfo
+    ))
+)]
+struct ApiDoc;
+
+fn schema_ref(name: &str) -> RefOr<Schema> {
+    Ref::from_schema_name(name).into()
+}
+
+impl Returns {
+    fn schema(&self) -> Option<RefOr<Schema>> {
+        match self {
+            Returns::Unit => None,
+            Returns::Text => Some(ObjectBuilder::new().schema_type(Type::String).into()),
+            Returns::Count => Some(ObjectBuilder::new().schema_type(Type::Integer).into()),
+            Returns::Json => Some(ObjectBuilder::new().into()),
+            Returns::One(name) => Some(schema_ref(name)),
+            Returns::Optional(name) => Some(
+                utoipa::openapi::schema::OneOfBuilder::new()
+                    .item(schema_ref(name))
+                    .item(ObjectBuilder::new().schema_type(Type::Null))
+                    .into(),
+            ),
+            Returns::Many(name) => Some(ArrayBuilder::new().items(schema_ref(name)).into()),
+        }
+    }
+}
+
+impl Accepts {
+    fn schema(&self) -> Option<RefOr<Schema>> {
+        match self {
+            Accepts::Nothing | Accepts::Query(_) => None,
+            Accepts::Schema(name) => Some(schema_ref(name)),
+            Accepts::Fields(fields) => Some(
+                fields
+                    .iter()
+                    .fold(ObjectBuilder::new(), |object, (field, ty)| {
+                        object.property(*field, ObjectBuilder::new().schema_type(ty.clone()))
+                    })
+                    .into(),
+            ),
+        }
+    }
+}
+
+/// Path parameter names in an OpenAPI path template (`/api/jobs/{id}` -> `id`).
+fn path_params(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+}
+
+/// Build the OpenAPI document for every server function in this crate.
+pub fn openapi_spec() -> utoipa::openapi::OpenApi {
+    let paths = ENDPOINTS
+        .iter()
+        .fold(PathsBuilder::new(), |paths, endpoint| {
+            let mut operation = OperationBuilder::new()
+                .operation_id(Some(endpoint.operation_id))
+                .summary(Some(endpoint.summary))
+                .tag(endpoint.tag);
+
+            for name in path_params(endpoint.path) {
+                operation = operation.parameter(
+                    ParameterBuilder::new()
+                        .name(name)
+                        .parameter_in(ParameterIn::Path)
+                        .required(Required::True)
+                        .schema(Some(ObjectBuilder::new().schema_type(Type::String))),
+                );
+            }
+
+            if let Accepts::Query(fields) = &endpoint.accepts {
+                for (name, ty) in fields.iter() {
+                    operation = operation.parameter(
+                        ParameterBuilder::new()
+                            .name(*name)
+                            .parameter_in(ParameterIn::Query)
+                            .required(Required::False)
+                            .schema(Some(ObjectBuilder::new().schema_type(ty.clone()))),
+                    );
+                }
+            }
+
+            if let Some(schema) = endpoint.accepts.schema() {
+                operation = operation.request_body(Some(
+                    RequestBodyBuilder::new()
+                        .content(
+                            "application/json",
+                            ContentBuilder::new().schema(Some(schema)).build(),
+                        )
+                        .required(Some(Required::True))
+                        .build(),
+                ));
+            }
+
+            let mut response = ResponseBuilder::new().description("Success");
+            if let Some(schema) = endpoint.returns.schema() {
+                response = response.content(
+                    "application/json",
+                    ContentBuilder::new().schema(Some(schema)).build(),
+                );
+            }
+            operation = operation.response("200", response).response(
+                "500",
+                ResponseBuilder::new().description("Server function error"),
+            );
+
+            paths.path(
+                endpoint.path,
+                PathItem::new(endpoint.method.clone(), operation),
+            )
+        });
+
+    let mut spec = ApiDoc::openapi();
+    spec.paths = paths.build();
+    spec
+}
+
+/// Serve the OpenAPI document describing the server functions.
+#[get("/api/openapi.json")]
+pub async fn openapi_json() -> Result<JsonValue, ServerFnError> {
+    serde_json::to_value(openapi_spec())
+        .map_err(|e| ServerFnError::new(format!("Failed to serialize OpenAPI spec: {}", e)))
+}
@@ -1,15 +1,21 @@
 //! Queue management server functions.
 
 use dioxus::prelude::*;
-use queue_core::Queue;
+use queue_core::{
+    JobTypeConfig, JobTypeInfo, Queue, QueueConfig, QueueId, QueueState, QueueStats,
+    ThroughputBucket,
+};
+
 #[cfg(feature = "server")]
-use queue_core::QueueId;
+use crate::JobApiError;
 
-/// Create a new queue.
+/// Create a new queue, using the default config when none is given.
 #[post("/api/queues/create")]
 pub async fn create_queue(
     name: String,
     description: Option<String>,
+    config: Option<QueueConfig>,
+    tags: Option<Vec<String>>,
 ) -> Result<Queue, ServerFnError> {
     #[cfg(feature = "server")]
     {
@@ -18,24 +24,28 @@ pub async fn create_queue(
 
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
             .send_message(SupervisorMessage::CreateQueue {
                 name,
                 description,
+                config: config.unwrap_or_default(),
+                tags: tags.unwrap_or_default(),
+                tenant_id: None,
                 reply: tx.into(),
             })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
         rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))?
-            .map_err(ServerFnError::new)
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
     }
 
     #[cfg(not(feature = "server"))]
@@ -55,19 +65,62 @@ pub async fn list_queues() -> Result<Vec<Queue>, ServerFnError> {
         // Ensure job queue is initialized before accessing supervisor
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
             .send_message(SupervisorMessage::ListQueues { reply: tx.into() })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
         rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// A queue's job counts as stored in the database.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct QueueJobCounts {
+    /// The queue counted.
+    pub queue_id: QueueId,
+    /// Jobs by status; figures only the queue's actor tracks, like ages and
+    /// rates, are left unset.
+    pub stats: QueueStats,
+}
+
+/// Job counts for every queue, from one aggregate query over the database.
+///
+/// Unlike the stats on [`list_queues`], which the queue actors keep in memory,
+/// these are authoritative and survive restarts. Queues without any jobs are
+/// left out.
+#[get("/api/queues/stats")]
+pub async fn all_queue_stats() -> Result<Vec<QueueJobCounts>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use db::repositories::JobRepository;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let mut counts: Vec<QueueJobCounts> = JobRepository::get_all_queue_stats()
+            .await
+            .map_err(|e| JobApiError::Internal(format!("Database error: {}", e)))?
+            .into_iter()
+            .map(|(queue_id, stats)| QueueJobCounts { queue_id, stats })
+            .collect();
+        counts.sort_by_key(|c| c.queue_id.0);
+        Ok(counts)
     }
 
     #[cfg(not(feature = "server"))]
@@ -86,14 +139,15 @@ pub async fn get_queue(id: String) -> Result<Option<Queue>, ServerFnError> {
 
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
         let queue_id = QueueId::parse(&id)
-            .map_err(|e| ServerFnError::new(format!("Invalid queue ID: {}", e)))?;
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
@@ -101,10 +155,98 @@ pub async fn get_queue(id: String) -> Result<Option<Queue>, ServerFnError> {
                 queue_id,
                 reply: tx.into(),
             })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
         rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// A queue's config alongside both its live and its stored job counts.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct QueueDescription {
+    /// The queue described.
+    pub queue_id: QueueId,
+    /// The queue's name.
+    pub name: String,
+    /// Whether the queue is running, paused, draining or stopped.
+    pub state: QueueState,
+    /// The queue's current config.
+    pub config: QueueConfig,
+    /// Stats as the queue's actor tracks them in memory.
+    pub live_stats: QueueStats,
+    /// Job counts by status from the database, which survive restarts.
+    pub stored_stats: QueueStats,
+    /// Workers currently reporting in for the queue.
+    pub worker_count: usize,
+}
+
+/// Describe a queue for operators: its config, live and stored stats, and workers.
+///
+/// The live stats come from the queue's actor and the stored ones from the
+/// database, so the two can be compared when they disagree.
+#[get("/api/queues/:id/describe")]
+pub async fn describe_queue(id: String) -> Result<QueueDescription, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+        use db::repositories::JobRepository;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::GetQueue {
+                queue_id,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+        let queue = rx
+            .await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .ok_or_else(|| JobApiError::NotFound(actors::QUEUE_NOT_FOUND.into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::ListWorkers { reply: tx.into() })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+        let worker_count = rx
+            .await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .iter()
+            .filter(|worker| worker.queue_id == queue_id)
+            .count();
+
+        let stored_stats = JobRepository::get_queue_stats(queue_id)
+            .await
+            .map_err(|e| JobApiError::Internal(format!("Database error: {}", e)))?;
+
+        Ok(QueueDescription {
+            queue_id,
+            name: queue.name,
+            state: queue.state,
+            config: queue.config,
+            live_stats: queue.stats,
+            stored_stats,
+            worker_count,
+        })
     }
 
     #[cfg(not(feature = "server"))]
@@ -123,11 +265,12 @@ pub async fn get_queue_by_name(name: String) -> Result<Option<Queue>, ServerFnEr
 
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
@@ -135,10 +278,10 @@ pub async fn get_queue_by_name(name: String) -> Result<Option<Queue>, ServerFnEr
                 name,
                 reply: tx.into(),
             })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
         rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()).into())
     }
 
     #[cfg(not(feature = "server"))]
@@ -157,14 +300,15 @@ pub async fn pause_queue(id: String) -> Result<(), ServerFnError> {
 
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
         let queue_id = QueueId::parse(&id)
-            .map_err(|e| ServerFnError::new(format!("Invalid queue ID: {}", e)))?;
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
@@ -172,11 +316,11 @@ pub async fn pause_queue(id: String) -> Result<(), ServerFnError> {
                 queue_id,
                 reply: tx.into(),
             })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
         rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))?
-            .map_err(ServerFnError::new)
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
     }
 
     #[cfg(not(feature = "server"))]
@@ -185,7 +329,7 @@ pub async fn pause_queue(id: String) -> Result<(), ServerFnError> {
     }
 }
 
-/// Resume a paused queue.
+/// Resume a paused queue, or start a stopped one.
 #[post("/api/queues/:id/resume")]
 pub async fn resume_queue(id: String) -> Result<(), ServerFnError> {
     #[cfg(feature = "server")]
@@ -195,14 +339,15 @@ pub async fn resume_queue(id: String) -> Result<(), ServerFnError> {
 
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
         let queue_id = QueueId::parse(&id)
-            .map_err(|e| ServerFnError::new(format!("Invalid queue ID: {}", e)))?;
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
@@ -210,11 +355,159 @@ pub async fn resume_queue(id: String) -> Result<(), ServerFnError> {
                 queue_id,
                 reply: tx.into(),
             })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Pause every queue carrying a tag, returning how many matched.
+#[post("/api/queues/by-tag/:tag/pause")]
+pub async fn pause_queues_by_tag(tag: String) -> Result<usize, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::PauseQueuesByTag {
+                tag,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Resume every queue carrying a tag, returning how many matched.
+#[post("/api/queues/by-tag/:tag/resume")]
+pub async fn resume_queues_by_tag(tag: String) -> Result<usize, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::ResumeQueuesByTag {
+                tag,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Drain a queue: finish its queued jobs without accepting new ones, then stop.
+#[post("/api/queues/:id/drain")]
+pub async fn drain_queue(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::DrainQueue {
+                queue_id,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
         rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))?
-            .map_err(ServerFnError::new)
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Stop a queue. Running jobs still finish.
+#[post("/api/queues/:id/stop")]
+pub async fn stop_queue(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::StopQueue {
+                queue_id,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
     }
 
     #[cfg(not(feature = "server"))]
@@ -224,8 +517,14 @@ pub async fn resume_queue(id: String) -> Result<(), ServerFnError> {
 }
 
 /// Delete a queue.
+///
+/// With `drain_timeout_secs`, the queue first stops taking jobs and finishes
+/// its queued ones for up to that long; otherwise running jobs are cancelled.
 #[post("/api/queues/:id/delete")]
-pub async fn delete_queue(id: String) -> Result<(), ServerFnError> {
+pub async fn delete_queue(
+    id: String,
+    drain_timeout_secs: Option<u64>,
+) -> Result<(), ServerFnError> {
     #[cfg(feature = "server")]
     {
         use actors::SupervisorMessage;
@@ -233,26 +532,234 @@ pub async fn delete_queue(id: String) -> Result<(), ServerFnError> {
 
         crate::ensure_initialized()
             .await
-            .map_err(|e| ServerFnError::new(format!("Initialization failed: {}", e)))?;
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
 
         let queue_id = QueueId::parse(&id)
-            .map_err(|e| ServerFnError::new(format!("Invalid queue ID: {}", e)))?;
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
 
         let supervisor = global_registry()
-            .get_supervisor()
-            .ok_or_else(|| ServerFnError::new("Supervisor not available"))?;
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
 
         let (tx, rx) = actors::concurrency::oneshot();
         supervisor
             .send_message(SupervisorMessage::DeleteQueue {
+                queue_id,
+                drain: drain_timeout_secs.map(std::time::Duration::from_secs),
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Drop all pending jobs from a queue, returning how many were removed.
+#[post("/api/queues/:id/purge")]
+pub async fn purge_queue(id: String) -> Result<u64, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::PurgeQueue {
+                queue_id,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Return a queue's running jobs to pending, returning how many were requeued.
+///
+/// For jobs left stuck in `Running`, e.g. after a crash. Their workers are told
+/// to stop and the jobs are dispatched again without using up an attempt.
+#[post("/api/queues/:id/requeue-running")]
+pub async fn requeue_running(id: String) -> Result<u64, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::RequeueRunning {
+                queue_id,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Completed and failed job counts per time bucket, for charting a queue's throughput.
+///
+/// Covers the trailing `window_secs` in `bucket_secs` buckets, oldest first;
+/// buckets without archived jobs are returned with zero counts.
+#[post("/api/queues/:queue_id/throughput")]
+pub async fn queue_throughput(
+    queue_id: String,
+    bucket_secs: u64,
+    window_secs: u64,
+) -> Result<Vec<ThroughputBucket>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use db::repositories::JobRepository;
+        use std::time::Duration;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&queue_id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+        if bucket_secs == 0 {
+            return Err(JobApiError::InvalidRequest("Bucket size must be positive".into()).into());
+        }
+
+        JobRepository::throughput_buckets(
+            queue_id,
+            Duration::from_secs(bucket_secs),
+            Duration::from_secs(window_secs),
+        )
+        .await
+        .map_err(|e| JobApiError::Internal(format!("Database error: {}", e)).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// Resolved settings for a job type in a queue: which handler runs it and the
+/// timeout and retries it gets from the queue's defaults.
+#[get("/api/queues/:id/job-types/:job_type/config")]
+pub async fn job_type_config(id: String, job_type: String) -> Result<JobTypeConfig, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = QueueId::parse(&id)
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::GetJobTypeConfig {
+                queue_id,
+                job_type,
+                reply: tx.into(),
+            })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
+
+/// List the job types with a registered handler, with their sample payloads.
+///
+/// With a `queue_id`, lists the types that queue can run: its own handlers and
+/// the global ones, narrowed to its `allowed_job_types`.
+#[get("/api/job-types?queue_id")]
+pub async fn list_job_types(queue_id: Option<String>) -> Result<Vec<JobTypeInfo>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let queue_id = queue_id
+            .map(|id| QueueId::parse(&id))
+            .transpose()
+            .map_err(|e| JobApiError::InvalidRequest(format!("Invalid queue ID: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::ListJobTypes {
                 queue_id,
                 reply: tx.into(),
             })
-            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
 
         rx.await
-            .map_err(|_| ServerFnError::new("Failed to receive response"))?
-            .map_err(ServerFnError::new)
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()))?
+            .map_err(|e| JobApiError::from_reply(e).into())
     }
 
     #[cfg(not(feature = "server"))]
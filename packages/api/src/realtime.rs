@@ -3,6 +3,9 @@
 use queue_core::JobEvent;
 use tokio::sync::broadcast;
 
+/// Content type of a MessagePack event stream.
+pub const MSGPACK_CONTENT_TYPE: &str = "application/vnd.msgpack";
+
 /// Global event broadcaster.
 static EVENT_TX: std::sync::LazyLock<broadcast::Sender<JobEvent>> =
     std::sync::LazyLock::new(|| {
@@ -22,10 +25,111 @@ pub fn subscribe_events() -> broadcast::Receiver<JobEvent> {
 
 // Note: SSE endpoint would typically be implemented as a custom Axum route
 // or using Dioxus's streaming capabilities. For now, we provide the
-// subscription mechanism that can be used by the web server.
+// subscription mechanism that can be used by the web server, along with
+// `EventEncoding` to pick and write the stream's format.
 
 /// Helper to format an event for SSE.
 pub fn format_sse_event(event: &JobEvent) -> String {
     let json = serde_json::to_string(event).unwrap_or_else(|_| "{}".to_string());
     format!("data: {}\n\n", json)
 }
+
+/// Wire format of an event stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EventEncoding {
+    /// Server-Sent Events carrying one JSON event per `data:` line.
+    #[default]
+    Json,
+    /// MessagePack events, each prefixed with its length as a big-endian `u32`.
+    ///
+    /// Much smaller than JSON for frequent events like heartbeats and stats
+    /// updates. SSE can't carry binary, so this is a plain byte stream.
+    MessagePack,
+}
+
+impl EventEncoding {
+    /// Pick the encoding from a `format` query parameter, else the `Accept` header.
+    ///
+    /// `format=msgpack` or an `Accept` of `application/vnd.msgpack` (or the
+    /// older `application/msgpack` / `application/x-msgpack`) selects
+    /// MessagePack; anything else gets JSON.
+    pub fn negotiate(format: Option<&str>, accept: Option<&str>) -> Self {
+        if let Some(format) = format {
+            return match format.trim().to_ascii_lowercase().as_str() {
+                "msgpack" | "messagepack" => EventEncoding::MessagePack,
+                _ => EventEncoding::Json,
+            };
+        }
+
+        let wants_msgpack = accept.is_some_and(|accept| {
+            accept.split(',').any(|range| {
+                let media_type = range.split(';').next().unwrap_or_default().trim();
+                [
+                    MSGPACK_CONTENT_TYPE,
+                    "application/msgpack",
+                    "application/x-msgpack",
+                ]
+                .iter()
+                .any(|known| media_type.eq_ignore_ascii_case(known))
+            })
+        });
+        if wants_msgpack {
+            EventEncoding::MessagePack
+        } else {
+            EventEncoding::Json
+        }
+    }
+
+    /// Content type to send the stream with.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            EventEncoding::Json => "text/event-stream",
+            EventEncoding::MessagePack => MSGPACK_CONTENT_TYPE,
+        }
+    }
+
+    /// Encode an event as one frame of the stream.
+    pub fn encode(self, event: &JobEvent) -> Result<Vec<u8>, String> {
+        match self {
+            EventEncoding::Json => {
+                let json = serde_json::to_string(event)
+                    .map_err(|e| format!("Failed to encode event: {}", e))?;
+                Ok(format!("data: {}\n\n", json).into_bytes())
+            }
+            EventEncoding::MessagePack => {
+                // Named fields, since the tagged event enum can't be read back from arrays
+                let body = rmp_serde::to_vec_named(event)
+                    .map_err(|e| format!("Failed to encode event: {}", e))?;
+                let len = u32::try_from(body.len())
+                    .map_err(|_| format!("Event too large to frame: {} bytes", body.len()))?;
+                let mut frame = Vec::with_capacity(4 + body.len());
+                frame.extend_from_slice(&len.to_be_bytes());
+                frame.extend_from_slice(&body);
+                Ok(frame)
+            }
+        }
+    }
+
+    /// Decode one frame written by [`EventEncoding::encode`].
+    pub fn decode(self, frame: &[u8]) -> Result<JobEvent, String> {
+        match self {
+            EventEncoding::Json => {
+                let data = std::str::from_utf8(frame)
+                    .ok()
+                    .and_then(|text| text.strip_prefix("data: "))
+                    .and_then(|text| text.strip_suffix("\n\n"))
+                    .ok_or("Malformed SSE frame")?;
+                serde_json::from_str(data).map_err(|e| format!("Failed to decode event: {}", e))
+            }
+            EventEncoding::MessagePack => {
+                let (len, body) = frame
+                    .split_first_chunk::<4>()
+                    .ok_or("Truncated MessagePack frame")?;
+                if u32::from_be_bytes(*len) as usize != body.len() {
+                    return Err("MessagePack frame length doesn't match its body".into());
+                }
+                rmp_serde::from_slice(body).map_err(|e| format!("Failed to decode event: {}", e))
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Aggregate system status server function for the overview dashboard.
+
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use crate::JobApiError;
+
+/// Single-glance summary of the running job queue system.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SystemStatus {
+    /// Number of live queues.
+    pub queue_count: usize,
+    /// Worker actors across all queues.
+    pub worker_count: usize,
+    /// Pending jobs across all queues.
+    pub total_pending: u64,
+    /// Running jobs across all queues.
+    pub total_running: u64,
+    /// Database backend (the endpoint scheme, e.g. `mem` or `file`).
+    pub db_backend: String,
+    /// Seconds since the supervisor started.
+    pub uptime_secs: u64,
+}
+
+/// Get aggregate status across all queues.
+#[get("/api/status")]
+pub async fn system_status() -> Result<SystemStatus, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::GetStatus { reply: tx.into() })
+            .map_err(|e| ServerFnError::new(format!("Failed to send message: {}", e)))?;
+
+        let status = rx
+            .await
+            .map_err(|_| ServerFnError::new("Failed to receive response"))?;
+
+        Ok(SystemStatus {
+            queue_count: status.queues.len(),
+            worker_count: status.worker_count,
+            total_pending: status.queues.iter().map(|q| q.stats.pending).sum(),
+            total_running: status.queues.iter().map(|q| q.stats.running).sum(),
+            db_backend: db::backend().unwrap_or("unknown").to_string(),
+            uptime_secs: status.uptime_secs(),
+        })
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
@@ -0,0 +1,39 @@
+//! Worker status server functions.
+
+use dioxus::prelude::*;
+use queue_core::WorkerInfo;
+
+#[cfg(feature = "server")]
+use crate::JobApiError;
+
+/// List live workers across all queues.
+#[get("/api/workers")]
+pub async fn list_workers() -> Result<Vec<WorkerInfo>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use actors::SupervisorMessage;
+        use actors::global_registry;
+
+        crate::ensure_initialized()
+            .await
+            .map_err(|e| JobApiError::NotInitialized(format!("Initialization failed: {}", e)))?;
+
+        let supervisor = global_registry()
+            .wait_for_supervisor(crate::init::SUPERVISOR_WAIT)
+            .await
+            .ok_or_else(|| JobApiError::NotInitialized("Supervisor not available".into()))?;
+
+        let (tx, rx) = actors::concurrency::oneshot();
+        supervisor
+            .send_message(SupervisorMessage::ListWorkers { reply: tx.into() })
+            .map_err(|e| JobApiError::Internal(format!("Failed to send message: {}", e)))?;
+
+        rx.await
+            .map_err(|_| JobApiError::Internal("Failed to receive response".into()).into())
+    }
+
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server-only function"))
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use api::JobApiError;
+use queue_core::QueueConfig;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_allow_list_rejects_unlisted_job_types() -> Result<(), Box<dyn Error>> {
+    // No workers, so accepted jobs stay pending
+    let config = QueueConfig {
+        concurrency: 0,
+        allowed_job_types: Some(vec!["echo".into()]),
+        ..QueueConfig::default()
+    };
+    let queue = api::create_queue("echo-only".into(), None, Some(config), None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // The allow-list survives a reload from the database
+    let stored = db::repositories::QueueRepository::get(queue.id).await?;
+    assert_eq!(
+        stored.config.allowed_job_types,
+        Some(vec!["echo".to_string()])
+    );
+
+    let payload = json!({ "seconds": 0 });
+    let echo = api::enqueue_job(common::job_request(&queue, "echo", payload.clone()))
+        .await
+        .map_err(|e| e.to_string())?;
+    assert_eq!(echo.job_type, "echo");
+
+    let err = api::enqueue_job(common::job_request(&queue, "sleep", payload))
+        .await
+        .err()
+        .ok_or("sleep job was accepted")?;
+    let rejected = JobApiError::from_server_fn_error(&err);
+    assert_eq!(rejected.code(), "invalid_request");
+    assert!(rejected.message().contains("not allowed"));
+    assert!(rejected.message().contains("sleep"));
+
+    // Only the echo job was persisted
+    let jobs = api::list_queue_jobs(queue.id.to_string(), None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let types: Vec<_> = jobs.iter().map(|job| job.job_type.as_str()).collect();
+    assert_eq!(types, ["echo"]);
+
+    Ok(())
+}
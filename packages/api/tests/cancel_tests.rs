@@ -0,0 +1,50 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+use std::time::Duration;
+
+use queue_core::{JobId, JobStatus};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_cancel_distinguishes_unknown_and_terminal_jobs() -> Result<(), Box<dyn Error>> {
+    let missing = api::cancel_job(JobId::new().to_string(), None).await;
+    let err = missing.err().ok_or("cancelling an unknown job succeeded")?;
+    assert!(err.to_string().contains("Job not found"), "{}", err);
+
+    let queue = api::create_queue("cancel-check".into(), None, None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let job = api::enqueue_job(common::job_request(
+        &queue,
+        "fail",
+        json!({ "fail": false }),
+    ))
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    loop {
+        let current = api::get_job(job.id.to_string())
+            .await
+            .map_err(|e| e.to_string())?;
+        if matches!(current.map(|j| j.status), Some(JobStatus::Completed { .. })) {
+            break;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err("job did not complete".into());
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let completed = api::cancel_job(job.id.to_string(), None).await;
+    let err = completed
+        .err()
+        .ok_or("cancelling a completed job succeeded")?;
+    assert!(err.to_string().contains("already completed"), "{}", err);
+
+    Ok(())
+}
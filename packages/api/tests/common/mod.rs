@@ -0,0 +1,23 @@
+//! Helpers shared by the API integration tests.
+//!
+//! One test per binary: the global supervisor lives on the first test's runtime.
+//! Each file therefore holds a single `#[tokio::test]` and uses only some of these helpers.
+#![allow(dead_code)]
+
+use api::CreateJobRequest;
+use queue_core::Queue;
+use serde_json::Value;
+
+/// Request to enqueue a `job_type` job on `queue`, leaving every other setting to the queue.
+pub fn job_request(queue: &Queue, job_type: &str, payload: Value) -> CreateJobRequest {
+    CreateJobRequest {
+        queue_id: queue.id.to_string(),
+        job_type: job_type.into(),
+        payload,
+        priority: None,
+        max_retries: None,
+        timeout_secs: None,
+        tags: Vec::new(),
+        delay_secs: None,
+    }
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use api::{DbConfig, FnHandler, InitConfig, JobContext, JobHandlerRegistry};
+use queue_core::{Job, JobResult, JobStatus, QueueBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_init_with_custom_handler_runs_its_jobs() -> Result<(), Box<dyn Error>> {
+    let mut handlers = JobHandlerRegistry::new();
+    handlers.register(FnHandler::new("shout", |job: &Job, _ctx: JobContext| {
+        let text = job.payload["text"]
+            .as_str()
+            .unwrap_or_default()
+            .to_uppercase();
+        Box::pin(async move { Ok(JobResult::new(text)) })
+    }));
+    api::init_with_handlers(
+        handlers,
+        InitConfig {
+            db: DbConfig::memory(),
+            queues: vec![QueueBuilder::new("custom-handlers")],
+            create_demo_queue: false,
+            register_demo_handlers: false,
+            ..InitConfig::default()
+        },
+    )
+    .await?;
+
+    let queue = api::list_queues()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|queue| queue.name == "custom-handlers")
+        .ok_or("declared queue was not created")?;
+
+    let request = common::job_request(&queue, "shout", json!({ "text": "hello" }));
+    let job = api::enqueue_and_wait(request, 10)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let JobStatus::Completed { result, .. } = job.status else {
+        return Err(format!("job did not complete: {:?}", job.status).into());
+    };
+    assert_eq!(result.summary, "HELLO");
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use api::CreateJobRequest;
+use queue_core::QueueConfig;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_describe_queue_combines_config_and_stored_counts() -> Result<(), Box<dyn Error>> {
+    // No workers, so enqueued jobs stay where they are
+    let config = QueueConfig {
+        concurrency: 0,
+        default_max_retries: 6,
+        ..QueueConfig::default()
+    };
+    let queue = api::create_queue("describe".into(), None, Some(config.clone()), None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for _ in 0..2 {
+        api::enqueue_job(common::job_request(&queue, "echo", json!({})))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    let delayed = CreateJobRequest {
+        delay_secs: Some(3600),
+        ..common::job_request(&queue, "echo", json!({}))
+    };
+    api::enqueue_job(delayed).await.map_err(|e| e.to_string())?;
+
+    let description = api::describe_queue(queue.id.to_string())
+        .await
+        .map_err(|e| e.to_string())?;
+    assert_eq!(description.queue_id, queue.id);
+    assert_eq!(description.name, "describe");
+    assert_eq!(description.config, config);
+    assert_eq!(description.worker_count, 0);
+
+    assert_eq!(description.stored_stats.pending, 2);
+    assert_eq!(description.stored_stats.scheduled, 1);
+    assert_eq!(description.live_stats.pending, 2);
+    assert_eq!(description.live_stats.scheduled, 1);
+
+    let missing = api::describe_queue(queue_core::QueueId::new().to_string()).await;
+    assert!(missing.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+use std::error::Error;
+
+use serde_json::json;
+
+#[tokio::test]
+async fn test_echo_json_round_trips_nested_objects() -> Result<(), Box<dyn Error>> {
+    let value = json!({
+        "queue": { "name": "emails", "tags": ["team:growth", "tier:1"] },
+        "retries": 3,
+        "nested": { "deeper": { "flag": true, "nothing": null } },
+    });
+
+    let before = chrono::Utc::now();
+    let echoed = api::echo_json(value.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    assert_eq!(echoed.value, value);
+    assert!(echoed.server_feature);
+    assert!(echoed.timestamp >= before);
+
+    Ok(())
+}
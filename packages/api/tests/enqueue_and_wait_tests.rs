@@ -0,0 +1,29 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use queue_core::JobStatus;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_enqueue_and_wait_returns_finished_job() -> Result<(), Box<dyn Error>> {
+    let queue = api::create_queue("enqueue-and-wait".into(), None, None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let request = common::job_request(&queue, "echo", json!({ "hello": "world" }));
+    let job = api::enqueue_and_wait(request, 10)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let JobStatus::Completed { result, .. } = job.status else {
+        return Err(format!("job did not complete: {:?}", job.status).into());
+    };
+    assert_eq!(result.summary, "Echo completed");
+    assert_eq!(result.output, Some(json!({ "hello": "world" })));
+
+    Ok(())
+}
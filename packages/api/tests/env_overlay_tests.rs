@@ -0,0 +1,70 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use api::{DbConfig, FnHandler, InitConfig, JobContext, JobHandlerRegistry};
+use queue_core::{Job, JobResult, JobStatus, QueueBuilder};
+use serde_json::json;
+
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// One test per binary: the builder and environment must be in place before
+// anything initializes the global system.
+#[tokio::test]
+async fn test_env_settings_apply_over_init_builder_config() -> Result<(), Box<dyn Error>> {
+    // SAFETY: set before the system starts, while this test is the only thread reading it
+    unsafe { std::env::set_var("MAX_CONCURRENT_JOBS", "1") };
+    api::set_init_builder(|| {
+        let mut handlers = JobHandlerRegistry::new();
+        handlers.register(FnHandler::new("hold", |_job: &Job, _ctx: JobContext| {
+            Box::pin(async {
+                let running = RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+                PEAK.fetch_max(running, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                RUNNING.fetch_sub(1, Ordering::SeqCst);
+                Ok(JobResult::new("held"))
+            })
+        }));
+        let config = InitConfig {
+            db: DbConfig::memory(),
+            queues: vec![QueueBuilder::new("env-capped").concurrency(4)],
+            create_demo_queue: false,
+            register_demo_handlers: false,
+            ..InitConfig::default()
+        };
+        (handlers, config)
+    });
+
+    let queue = api::list_queues()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|queue| queue.name == "env-capped")
+        .ok_or("declared queue was not created")?;
+
+    let waits: Vec<_> = (0..3)
+        .map(|_| {
+            let request = common::job_request(&queue, "hold", json!({}));
+            tokio::spawn(api::enqueue_and_wait(request, 10))
+        })
+        .collect();
+    for wait in waits {
+        let job = wait.await?.map_err(|e| e.to_string())?;
+        assert!(
+            matches!(job.status, JobStatus::Completed { .. }),
+            "job did not complete: {:?}",
+            job.status
+        );
+    }
+
+    // The queue allows four at once; only the global cap from the environment holds it to one
+    assert_eq!(PEAK.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use api::JobApiError;
+use dioxus::prelude::ServerFnError;
+use queue_core::{JobId, QueueConfig, QueueId};
+use serde_json::json;
+
+/// Typed error of a call that was expected to fail.
+fn api_error<T>(result: Result<T, ServerFnError>) -> Result<JobApiError, Box<dyn Error>> {
+    let err = result.err().ok_or("call unexpectedly succeeded")?;
+    Ok(JobApiError::from_server_fn_error(&err))
+}
+
+#[tokio::test]
+async fn test_operations_map_to_error_codes() -> Result<(), Box<dyn Error>> {
+    let missing_job = api_error(api::cancel_job(JobId::new().to_string(), None).await)?;
+    assert_eq!(missing_job.code(), "not_found");
+    assert!(!missing_job.is_retryable());
+
+    let missing_queue = api_error(api::pause_queue(QueueId::new().to_string()).await)?;
+    assert_eq!(missing_queue.code(), "not_found");
+
+    let bad_id = api_error(api::purge_queue("not-a-queue-id".into()).await)?;
+    assert_eq!(bad_id.code(), "invalid_request");
+
+    let config = QueueConfig {
+        max_queue_size: Some(0),
+        ..QueueConfig::default()
+    };
+    let queue = api::create_queue("error-codes".into(), None, Some(config), None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let full = api_error(api::enqueue_job(common::job_request(&queue, "echo", json!({}))).await)?;
+    assert_eq!(full.code(), "queue_full");
+    assert!(full.is_retryable());
+    assert!(matches!(
+        full,
+        JobApiError::QueueFull {
+            current: 0,
+            max: 0,
+            ..
+        }
+    ));
+
+    Ok(())
+}
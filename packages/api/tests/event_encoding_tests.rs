@@ -0,0 +1,79 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+use std::error::Error;
+
+use api::EventEncoding;
+use chrono::Utc;
+use queue_core::{Job, JobEvent, JobId, PriorityCounts, QueueId, QueueStats};
+use serde_json::json;
+
+#[test]
+fn test_events_round_trip_in_both_encodings() -> Result<(), Box<dyn Error>> {
+    let queue_id = QueueId::new();
+    let events = [
+        JobEvent::WorkerHeartbeat {
+            worker_id: "worker-1".into(),
+            queue_id,
+            current_job: Some(JobId::new()),
+            timestamp: Utc::now(),
+        },
+        JobEvent::QueueStatsUpdated {
+            queue_id,
+            stats: QueueStats {
+                pending: 12,
+                running: 3,
+                avg_duration_ms: Some(41.5),
+                pending_by_priority: PriorityCounts {
+                    normal: 10,
+                    high: 2,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            timestamp: Utc::now(),
+        },
+        JobEvent::JobEnqueued {
+            job: Job::new(queue_id, "report", json!({ "rows": [1, 2, 3] })),
+            timestamp: Utc::now(),
+        },
+    ];
+
+    for event in events {
+        let json = EventEncoding::Json.encode(&event)?;
+        let msgpack = EventEncoding::MessagePack.encode(&event)?;
+        assert!(json.starts_with(b"data: "));
+        assert!(msgpack.len() < json.len());
+
+        assert_eq!(EventEncoding::Json.decode(&json)?, event);
+        assert_eq!(EventEncoding::MessagePack.decode(&msgpack)?, event);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_encoding_negotiation() {
+    assert_eq!(EventEncoding::negotiate(None, None), EventEncoding::Json);
+    assert_eq!(
+        EventEncoding::negotiate(Some("msgpack"), None),
+        EventEncoding::MessagePack
+    );
+    assert_eq!(
+        EventEncoding::negotiate(
+            None,
+            Some("application/vnd.msgpack;q=0.9, text/event-stream")
+        ),
+        EventEncoding::MessagePack
+    );
+    // The query parameter wins over the header
+    assert_eq!(
+        EventEncoding::negotiate(Some("json"), Some("application/vnd.msgpack")),
+        EventEncoding::Json
+    );
+    assert_eq!(
+        EventEncoding::MessagePack.content_type(),
+        api::MSGPACK_CONTENT_TYPE
+    );
+    assert_eq!(EventEncoding::Json.content_type(), "text/event-stream");
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use db::repositories::JobRepository;
+use queue_core::{Job, JobId, JobResult, JobStatus, QueueConfig};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_get_job_returns_archived_jobs() -> Result<(), Box<dyn Error>> {
+    let config = QueueConfig {
+        concurrency: 0,
+        ..QueueConfig::default()
+    };
+    let queue = api::create_queue("get-archived".into(), None, Some(config), None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Complete and archive a job outside the queue, as if it just finished
+    let mut job = JobRepository::create(&Job::new(queue.id, "echo", json!({ "n": 7 }))).await?;
+    job.status = JobStatus::Completed {
+        started_at: job.created_at,
+        completed_at: job.created_at,
+        result: JobResult::new("echoed"),
+    };
+    JobRepository::archive(&job).await?;
+
+    let found = api::get_job(job.id.to_string())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("archived job not returned")?;
+    assert_eq!(found.id, job.id);
+    assert_eq!(found.queue_id, queue.id);
+    assert_eq!(found.payload, json!({ "n": 7 }));
+    let JobStatus::Completed { result, .. } = found.status else {
+        return Err("archived job is not completed".into());
+    };
+    assert_eq!(result.summary, "echoed");
+
+    let missing = api::get_job(JobId::new().to_string())
+        .await
+        .map_err(|e| e.to_string())?;
+    assert!(missing.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+use std::error::Error;
+
+#[tokio::test]
+async fn test_health_before_and_after_init() -> Result<(), Box<dyn Error>> {
+    let before = api::health().await.map_err(|e| e.to_string())?;
+    assert!(!before.db_ready);
+    assert!(!before.supervisor_ready);
+    assert!(!before.is_ready());
+    assert_eq!(before.queue_count, 0);
+
+    api::init_job_queue().await?;
+
+    let after = api::health().await.map_err(|e| e.to_string())?;
+    assert!(after.db_ready);
+    assert!(after.supervisor_ready);
+    assert!(after.is_ready());
+
+    Ok(())
+}
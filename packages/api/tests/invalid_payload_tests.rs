@@ -0,0 +1,54 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use api::{DbConfig, FnHandler, InitConfig, JobApiError, JobContext, JobHandlerRegistry};
+use queue_core::{Job, JobResult, QueueBuilder};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_schema_violating_payload_is_invalid_request() -> Result<(), Box<dyn Error>> {
+    let mut handlers = JobHandlerRegistry::new();
+    handlers.register(
+        FnHandler::new("greet", |_job: &Job, _ctx: JobContext| {
+            Box::pin(async { Ok(JobResult::new("hi")) })
+        })
+        .with_schema(json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"]
+        })),
+    );
+    api::init_with_handlers(
+        handlers,
+        InitConfig {
+            db: DbConfig::memory(),
+            queues: vec![QueueBuilder::new("schema-checked")],
+            create_demo_queue: false,
+            register_demo_handlers: false,
+            ..InitConfig::default()
+        },
+    )
+    .await?;
+
+    let queue = api::list_queues()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|queue| queue.name == "schema-checked")
+        .ok_or("declared queue was not created")?;
+
+    let request = common::job_request(&queue, "greet", json!({ "name": 5 }));
+    let err = api::enqueue_job(request)
+        .await
+        .err()
+        .ok_or("schema-violating payload was accepted")?;
+    let error = JobApiError::from_server_fn_error(&err);
+    assert!(matches!(error, JobApiError::InvalidRequest(_)), "{error:?}");
+    assert_eq!(error.status(), 400);
+
+    Ok(())
+}
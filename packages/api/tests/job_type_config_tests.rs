@@ -0,0 +1,36 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use api::HandlerSource;
+use queue_core::QueueConfig;
+
+#[tokio::test]
+async fn test_job_type_config_uses_queue_defaults() -> Result<(), Box<dyn Error>> {
+    let config = QueueConfig {
+        default_timeout_secs: 42,
+        default_max_retries: 7,
+        ..QueueConfig::default()
+    };
+    let queue = api::create_queue("job-type-config".into(), None, Some(config), None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let echo = api::job_type_config(queue.id.to_string(), "echo".into())
+        .await
+        .map_err(|e| e.to_string())?;
+    assert_eq!(echo.handler, Some(HandlerSource::Global));
+    assert_eq!(echo.timeout_secs, 42);
+    assert_eq!(echo.max_retries, 7);
+
+    let unknown = api::job_type_config(queue.id.to_string(), "no-such-type".into())
+        .await
+        .map_err(|e| e.to_string())?;
+    assert_eq!(unknown.handler, None);
+    assert_eq!(unknown.timeout_secs, 42);
+
+    Ok(())
+}
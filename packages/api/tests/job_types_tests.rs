@@ -0,0 +1,27 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use serde_json::json;
+
+#[tokio::test]
+async fn test_list_job_types_returns_registered_handlers() -> Result<(), Box<dyn Error>> {
+    let job_types = api::list_job_types(None).await.map_err(|e| e.to_string())?;
+
+    let names: Vec<&str> = job_types
+        .iter()
+        .map(|info| info.job_type.as_str())
+        .collect();
+    assert_eq!(names, vec!["echo", "fail", "sleep"]);
+
+    let sleep = job_types
+        .iter()
+        .find(|info| info.job_type == "sleep")
+        .ok_or("sleep is not listed")?;
+    assert_eq!(sleep.sample_payload, Some(json!({ "seconds": 5 })));
+
+    Ok(())
+}
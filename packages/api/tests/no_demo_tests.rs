@@ -0,0 +1,34 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use api::{DbConfig, InitConfig, JobHandlerRegistry};
+
+#[tokio::test]
+async fn test_init_without_demo_starts_clean() -> Result<(), Box<dyn Error>> {
+    api::init_with_handlers(
+        JobHandlerRegistry::new(),
+        InitConfig {
+            db: DbConfig::memory(),
+            queues: Vec::new(),
+            create_demo_queue: false,
+            register_demo_handlers: false,
+            ..InitConfig::default()
+        },
+    )
+    .await?;
+
+    let queues = api::list_queues().await.map_err(|e| e.to_string())?;
+    assert!(
+        queues.iter().all(|queue| queue.name != "demo"),
+        "demo queue was created"
+    );
+
+    let job_types = api::list_job_types(None).await.map_err(|e| e.to_string())?;
+    assert!(job_types.is_empty(), "unexpected handlers: {:?}", job_types);
+
+    Ok(())
+}
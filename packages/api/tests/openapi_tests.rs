@@ -0,0 +1,23 @@
+#![cfg(feature = "openapi")]
+
+#[test]
+fn test_spec_describes_job_endpoints() {
+    let spec = api::openapi_spec();
+    let paths = &spec.paths.paths;
+
+    for path in [
+        "/api/jobs/enqueue",
+        "/api/jobs/{id}",
+        "/api/jobs/{id}/cancel",
+        "/api/queues/{queue_id}/jobs",
+    ] {
+        assert!(paths.contains_key(path), "spec is missing {path}");
+    }
+
+    let schemas = spec
+        .components
+        .as_ref()
+        .map(|components| &components.schemas);
+    assert!(schemas.is_some_and(|schemas| schemas.contains_key("Job")));
+    assert!(schemas.is_some_and(|schemas| schemas.contains_key("CreateJobRequest")));
+}
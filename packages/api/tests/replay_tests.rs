@@ -0,0 +1,55 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+use std::time::Duration;
+
+use api::CreateJobRequest;
+use queue_core::JobStatus;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_replay_enqueues_copy_of_archived_job() -> Result<(), Box<dyn Error>> {
+    let queue = api::create_queue("replay-check".into(), None, None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let payload = json!({ "message": "again" });
+    let original = api::enqueue_job(CreateJobRequest {
+        priority: Some("high".into()),
+        tags: vec!["replayable".into()],
+        ..common::job_request(&queue, "echo", payload.clone())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    loop {
+        let found = api::find_job(original.id.to_string())
+            .await
+            .map_err(|e| e.to_string())?;
+        if found.is_some_and(|view| view.is_archived()) {
+            break;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err("job was not archived".into());
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    // A paused queue would reject the copy, so check the job as enqueued
+    let replayed = api::replay_job(original.id.to_string())
+        .await
+        .map_err(|e| e.to_string())?;
+    assert_ne!(replayed.id, original.id);
+    assert!(matches!(replayed.status, JobStatus::Pending));
+    assert_eq!(replayed.queue_id, queue.id);
+    assert_eq!(replayed.job_type, "echo");
+    assert_eq!(replayed.payload, payload);
+    assert_eq!(replayed.priority, original.priority);
+    assert_eq!(replayed.tags, vec!["replayable".to_string()]);
+    assert_eq!(replayed.attempts, 0);
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+use std::error::Error;
+
+use api::JobApiError;
+use db::repositories::JobRepository;
+use queue_core::{Job, JobStatus, QueueConfig};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_requeue_failed_reports_jobs_requeued_before_a_rejection() -> Result<(), Box<dyn Error>>
+{
+    // No workers and room for one pending job, so the second requeue is rejected
+    let config = QueueConfig {
+        concurrency: 0,
+        max_queue_size: Some(1),
+        ..QueueConfig::default()
+    };
+    let queue = api::create_queue("requeue-partial".into(), None, Some(config), None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for n in 1..=2 {
+        let mut job = JobRepository::create(&Job::new(queue.id, "fail", json!({ "n": n }))).await?;
+        job.status = JobStatus::Failed {
+            started_at: job.created_at,
+            failed_at: job.created_at,
+            error: "handler bug".into(),
+            attempts: 1,
+        };
+        JobRepository::archive(&job).await?;
+    }
+
+    let err = api::requeue_failed(queue.id.to_string(), "fail".into())
+        .await
+        .err()
+        .ok_or("requeue past the queue's size was accepted")?;
+    let error = JobApiError::from_server_fn_error(&err);
+    assert!(matches!(error, JobApiError::QueueFull { .. }), "{error:?}");
+    assert!(
+        error.message().starts_with("Requeued 1 of 2 failed jobs"),
+        "{}",
+        error.message()
+    );
+
+    let pending = api::list_queue_jobs(queue.id.to_string(), Some("pending".into()), None)
+        .await
+        .map_err(|e| e.to_string())?;
+    assert_eq!(pending.len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::error::Error;
+
+use db::repositories::JobRepository;
+use queue_core::{Job, JobResult, JobStatus, QueueConfig};
+use serde_json::json;
+
+/// Store a job that failed on its first attempt and archive it.
+async fn archive_failed(job: Job) -> Result<(), Box<dyn Error>> {
+    let mut job = JobRepository::create(&job).await?;
+    job.status = JobStatus::Failed {
+        started_at: job.created_at,
+        failed_at: job.created_at,
+        error: "handler bug".into(),
+        attempts: 1,
+    };
+    JobRepository::archive(&job).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_requeue_failed_enqueues_archived_failures() -> Result<(), Box<dyn Error>> {
+    // No workers, so requeued jobs stay pending
+    let config = QueueConfig {
+        concurrency: 0,
+        ..QueueConfig::default()
+    };
+    let queue = api::create_queue("requeue-failed".into(), None, Some(config), None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    archive_failed(Job::new(queue.id, "fail", json!({ "n": 1 }))).await?;
+    archive_failed(Job::new(queue.id, "fail", json!({ "n": 2 }))).await?;
+
+    // Neither a success nor a failure of another type is requeued
+    let mut succeeded =
+        JobRepository::create(&Job::new(queue.id, "fail", json!({ "n": 3 }))).await?;
+    succeeded.status = JobStatus::Completed {
+        started_at: succeeded.created_at,
+        completed_at: succeeded.created_at,
+        result: JobResult::new("done"),
+    };
+    JobRepository::archive(&succeeded).await?;
+    archive_failed(Job::new(queue.id, "echo", json!({}))).await?;
+
+    let requeued = api::requeue_failed(queue.id.to_string(), "fail".into())
+        .await
+        .map_err(|e| e.to_string())?;
+    assert_eq!(requeued, 2);
+
+    let pending = api::list_queue_jobs(queue.id.to_string(), Some("pending".into()), None)
+        .await
+        .map_err(|e| e.to_string())?;
+    assert_eq!(pending.len(), 2);
+    assert!(pending.iter().all(|job| job.job_type == "fail"));
+    let mut payloads: Vec<_> = pending
+        .iter()
+        .filter_map(|job| job.payload.get("n").and_then(|n| n.as_u64()))
+        .collect();
+    payloads.sort();
+    assert_eq!(payloads, [1, 2]);
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+use std::error::Error;
+
+// One test per binary: it has to be the first thing to touch the global system.
+#[tokio::test]
+async fn test_calls_racing_initialization_succeed() -> Result<(), Box<dyn Error>> {
+    // Spawned before initialization, so they run while it is still in progress
+    let early: Vec<_> = (0..4).map(|_| tokio::spawn(api::list_queues())).collect();
+    let concurrent: Vec<_> = (0..4).map(|_| tokio::spawn(api::system_status())).collect();
+    api::init_job_queue().await.map_err(|e| e.to_string())?;
+
+    for call in early {
+        call.await?.map_err(|e| e.to_string())?;
+    }
+    for call in concurrent {
+        call.await?.map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+#![cfg(feature = "server")]
+#![allow(clippy::disallowed_methods)]
+
+use std::error::Error;
+
+#[tokio::test]
+async fn test_system_status_for_running_system() -> Result<(), Box<dyn Error>> {
+    let queue = api::create_queue("status-check".into(), None, None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = api::system_status().await.map_err(|e| e.to_string())?;
+    assert!(status.queue_count >= 1);
+    assert!(status.worker_count >= queue.config.concurrency as usize);
+    assert_eq!(status.db_backend, "mem");
+    assert_eq!(status.total_running, 0);
+
+    Ok(())
+}
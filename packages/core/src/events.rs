@@ -3,10 +3,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::{Job, JobId, JobStatus, Queue, QueueId, QueueState, QueueStats};
+use crate::{CancellationReason, Job, JobId, JobStatus, Queue, QueueId, QueueState, QueueStats};
 
 /// Events emitted by the job queue system for real-time updates.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(tag = "event", rename_all = "snake_case")]
 pub enum JobEvent {
     // Queue events
@@ -72,7 +73,8 @@ pub enum JobEvent {
     JobCancelled {
         job_id: JobId,
         queue_id: QueueId,
-        reason: Option<String>,
+        #[cfg_attr(feature = "openapi", schema(value_type = Option<String>))]
+        reason: Option<CancellationReason>,
         timestamp: DateTime<Utc>,
     },
     /// A job is being retried.
@@ -82,6 +84,14 @@ pub enum JobEvent {
         attempt: u32,
         timestamp: DateTime<Utc>,
     },
+    /// A running job was returned to pending because its worker stopped responding.
+    JobReclaimed {
+        job_id: JobId,
+        queue_id: QueueId,
+        worker_id: String,
+        reason: String,
+        timestamp: DateTime<Utc>,
+    },
 
     // Worker events
     /// A worker connected to a queue.
@@ -120,6 +130,7 @@ impl JobEvent {
             JobEvent::JobStatusChanged { timestamp, .. } => *timestamp,
             JobEvent::JobCancelled { timestamp, .. } => *timestamp,
             JobEvent::JobRetrying { timestamp, .. } => *timestamp,
+            JobEvent::JobReclaimed { timestamp, .. } => *timestamp,
             JobEvent::WorkerConnected { timestamp, .. } => *timestamp,
             JobEvent::WorkerDisconnected { timestamp, .. } => *timestamp,
             JobEvent::WorkerHeartbeat { timestamp, .. } => *timestamp,
@@ -140,6 +151,7 @@ impl JobEvent {
             JobEvent::JobStatusChanged { queue_id, .. } => Some(*queue_id),
             JobEvent::JobCancelled { queue_id, .. } => Some(*queue_id),
             JobEvent::JobRetrying { queue_id, .. } => Some(*queue_id),
+            JobEvent::JobReclaimed { queue_id, .. } => Some(*queue_id),
             JobEvent::WorkerConnected { queue_id, .. } => Some(*queue_id),
             JobEvent::WorkerDisconnected { queue_id, .. } => Some(*queue_id),
             JobEvent::WorkerHeartbeat { queue_id, .. } => Some(*queue_id),
@@ -156,11 +168,26 @@ impl JobEvent {
             JobEvent::JobStatusChanged { job_id, .. } => Some(*job_id),
             JobEvent::JobCancelled { job_id, .. } => Some(*job_id),
             JobEvent::JobRetrying { job_id, .. } => Some(*job_id),
+            JobEvent::JobReclaimed { job_id, .. } => Some(*job_id),
             JobEvent::WorkerHeartbeat { current_job, .. } => *current_job,
             _ => None,
         }
     }
 
+    /// Whether this event marks a job reaching its final state: completed,
+    /// failed with no retry left, or cancelled.
+    pub fn is_job_finished(&self) -> bool {
+        matches!(
+            self,
+            JobEvent::JobCompleted { .. }
+                | JobEvent::JobFailed {
+                    will_retry: false,
+                    ..
+                }
+                | JobEvent::JobCancelled { .. }
+        )
+    }
+
     /// Get a short description of this event for logging.
     pub fn description(&self) -> String {
         match self {
@@ -197,15 +224,21 @@ impl JobEvent {
             JobEvent::JobStatusChanged {
                 job_id, new_status, ..
             } => format!("Job {} -> {}", job_id, new_status.as_str()),
-            JobEvent::JobCancelled { job_id, reason, .. } => {
-                let reason = reason.as_deref().unwrap_or("no reason");
-                format!("Job {} cancelled: {}", job_id, reason)
-            }
+            JobEvent::JobCancelled { job_id, reason, .. } => match reason {
+                Some(reason) => format!("Job {} cancelled: {}", job_id, reason),
+                None => format!("Job {} cancelled: no reason", job_id),
+            },
             JobEvent::JobRetrying {
                 job_id, attempt, ..
             } => {
                 format!("Job {} retrying (attempt {})", job_id, attempt)
             }
+            JobEvent::JobReclaimed {
+                job_id,
+                worker_id,
+                reason,
+                ..
+            } => format!("Job {} reclaimed from {}: {}", job_id, worker_id, reason),
             JobEvent::WorkerConnected {
                 worker_id,
                 queue_id,
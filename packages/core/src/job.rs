@@ -1,18 +1,38 @@
 //! Job domain types for work items in the queue.
 
+use std::sync::{Mutex, PoisonError};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use ulid::Ulid;
+use ulid::{Generator, Ulid};
+
+/// Milliseconds from `start` to `end`, clamped to zero.
+///
+/// Wall clocks can step backwards, so `end` may precede `start`; casting the
+/// negative span straight to `u64` would wrap to a huge value.
+pub fn duration_ms(start: DateTime<Utc>, end: DateTime<Utc>) -> u64 {
+    (end - start).num_milliseconds().max(0) as u64
+}
+
+/// Shared by every [`JobId::new`] call so IDs made within one millisecond
+/// still sort in the order they were created.
+static JOB_ID_GENERATOR: Mutex<Generator> = Mutex::new(Generator::new());
 
 /// Unique identifier for a job, using ULID for chronological sorting.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(value_type = String))]
 #[serde(transparent)]
 pub struct JobId(pub Ulid);
 
 impl JobId {
-    /// Create a new unique job ID.
+    /// Create a new unique job ID, greater than any made before it in this process.
     pub fn new() -> Self {
-        Self(Ulid::new())
+        let next = JOB_ID_GENERATOR
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .generate();
+        // Only fails once a millisecond's 2^80 random values run out
+        Self(next.unwrap_or_else(|_| Ulid::new()))
     }
 
     /// Parse a job ID from a string.
@@ -37,18 +57,22 @@ impl std::fmt::Display for JobId {
 #[derive(
     Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
 )]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Priority {
-    Low = 0,
+    /// Best-effort work, run only when nothing else is pending.
+    Deferred = 0,
+    Low = 1,
     #[default]
-    Normal = 1,
-    High = 2,
-    Critical = 3,
+    Normal = 2,
+    High = 3,
+    Critical = 4,
 }
 
 impl std::fmt::Display for Priority {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Priority::Deferred => write!(f, "deferred"),
             Priority::Low => write!(f, "low"),
             Priority::Normal => write!(f, "normal"),
             Priority::High => write!(f, "high"),
@@ -57,8 +81,22 @@ impl std::fmt::Display for Priority {
     }
 }
 
+impl Priority {
+    /// This priority raised by `levels`, capped at `Critical`.
+    pub fn boosted(self, levels: u8) -> Priority {
+        match (self as u8).saturating_add(levels) {
+            0 => Priority::Deferred,
+            1 => Priority::Low,
+            2 => Priority::Normal,
+            3 => Priority::High,
+            _ => Priority::Critical,
+        }
+    }
+}
+
 /// Current status of a job in its lifecycle.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(tag = "status", rename_all = "snake_case")]
 pub enum JobStatus {
     /// Job is waiting to be processed.
@@ -85,10 +123,80 @@ pub enum JobStatus {
     /// Job was cancelled before completion.
     Cancelled {
         cancelled_at: DateTime<Utc>,
-        reason: Option<String>,
+        #[cfg_attr(feature = "openapi", schema(value_type = Option<String>))]
+        reason: Option<CancellationReason>,
     },
     /// Job is paused and won't be picked up.
     Paused,
+    /// Job is waiting for its scheduled time and won't be picked up before `run_at`.
+    Scheduled { run_at: DateTime<Utc> },
+}
+
+/// Prefix of a stored [`CancellationReason::Administrative`] reason.
+const ADMINISTRATIVE_PREFIX: &str = "administrative: ";
+
+/// Why a job was cancelled.
+///
+/// Stored as a string: `user_requested`, `superseded`, `expired`,
+/// `dependency_failed`, or `administrative: <note>`. Any other string, such as
+/// a free-form reason stored before this type existed, reads back as
+/// [`CancellationReason::Administrative`] with the string as its note.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
+pub enum CancellationReason {
+    /// Someone asked for the job to be cancelled.
+    UserRequested,
+    /// Newer work made the job unnecessary.
+    Superseded,
+    /// The job waited past the point it was still useful.
+    Expired,
+    /// A job this one depends on failed.
+    DependencyFailed,
+    /// Cancelled by an operator, with their note.
+    Administrative { note: String },
+}
+
+impl std::fmt::Display for CancellationReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CancellationReason::UserRequested => write!(f, "user_requested"),
+            CancellationReason::Superseded => write!(f, "superseded"),
+            CancellationReason::Expired => write!(f, "expired"),
+            CancellationReason::DependencyFailed => write!(f, "dependency_failed"),
+            CancellationReason::Administrative { note } => {
+                write!(f, "{}{}", ADMINISTRATIVE_PREFIX, note)
+            }
+        }
+    }
+}
+
+impl From<&str> for CancellationReason {
+    fn from(reason: &str) -> Self {
+        match reason {
+            "user_requested" => CancellationReason::UserRequested,
+            "superseded" => CancellationReason::Superseded,
+            "expired" => CancellationReason::Expired,
+            "dependency_failed" => CancellationReason::DependencyFailed,
+            other => CancellationReason::Administrative {
+                note: other
+                    .strip_prefix(ADMINISTRATIVE_PREFIX)
+                    .unwrap_or(other)
+                    .to_string(),
+            },
+        }
+    }
+}
+
+impl From<String> for CancellationReason {
+    fn from(reason: String) -> Self {
+        CancellationReason::from(reason.as_str())
+    }
+}
+
+impl From<CancellationReason> for String {
+    fn from(reason: CancellationReason) -> Self {
+        reason.to_string()
+    }
 }
 
 impl JobStatus {
@@ -114,12 +222,14 @@ impl JobStatus {
             JobStatus::Failed { .. } => "failed",
             JobStatus::Cancelled { .. } => "cancelled",
             JobStatus::Paused => "paused",
+            JobStatus::Scheduled { .. } => "scheduled",
         }
     }
 }
 
 /// Result of a completed job.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct JobResult {
     /// Human-readable summary of the result.
     pub summary: String,
@@ -144,8 +254,80 @@ impl JobResult {
     }
 }
 
+/// Severity of a [`JobLogLine`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum JobLogLevel {
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+impl JobLogLevel {
+    /// Get a simple level string for display.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobLogLevel::Debug => "debug",
+            JobLogLevel::Info => "info",
+            JobLogLevel::Warn => "warn",
+            JobLogLevel::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for JobLogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A line logged by a handler while processing a job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct JobLogLine {
+    /// Severity of the line.
+    pub level: JobLogLevel,
+    /// The logged message.
+    pub message: String,
+    /// When the line was logged.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl JobLogLine {
+    /// Create a log line stamped with the current time.
+    pub fn new(level: JobLogLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// One run of a job by a worker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AttemptRecord {
+    /// Attempt number, starting at 1.
+    pub attempt: u32,
+    /// Worker that ran the attempt.
+    pub worker_id: String,
+    /// When the worker picked the job up.
+    pub started_at: DateTime<Utc>,
+    /// When the attempt ended; `None` while it is still running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Why the attempt failed; `None` if it succeeded or is still running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// A job represents a unit of work to be executed by the queue system.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Job {
     /// Unique identifier for this job.
     pub id: JobId,
@@ -157,6 +339,10 @@ pub struct Job {
     pub payload: serde_json::Value,
     /// Execution priority.
     pub priority: Priority,
+    /// Levels `priority` is raised by while the job waits, set from the
+    /// queue's `retry_priority_boost` when it is retried.
+    #[serde(default)]
+    pub priority_boost: u8,
     /// Current status.
     pub status: JobStatus,
     /// Number of attempts so far.
@@ -173,6 +359,24 @@ pub struct Job {
     /// Optional tags for filtering and grouping.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    /// Earliest time the job may run; `None` runs it as soon as a worker is free.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_at: Option<DateTime<Utc>>,
+    /// Time the job must finish by, across all attempts; `None` for no deadline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<DateTime<Utc>>,
+    /// Lines logged by handlers, appended across attempts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logs: Vec<JobLogLine>,
+    /// Every run of the job so far, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts_log: Vec<AttemptRecord>,
+    /// Incremented on every stored update; writes from a stale copy are rejected.
+    #[serde(default)]
+    pub version: u64,
+    /// Tenant owning the job, taken from its queue; `None` for untenanted queues.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
 }
 
 impl Job {
@@ -189,6 +393,7 @@ impl Job {
             job_type: job_type.into(),
             payload,
             priority: Priority::default(),
+            priority_boost: 0,
             status: JobStatus::Pending,
             attempts: 0,
             max_retries: 3,
@@ -196,6 +401,12 @@ impl Job {
             created_at: now,
             updated_at: now,
             tags: Vec::new(),
+            run_at: None,
+            deadline: None,
+            logs: Vec::new(),
+            attempts_log: Vec::new(),
+            version: 0,
+            tenant_id: None,
         }
     }
 
@@ -222,4 +433,241 @@ impl Job {
         self.tags = tags;
         self
     }
+
+    /// Check if the job carries the given tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Hold this job back until `run_at`.
+    pub fn with_run_at(mut self, run_at: DateTime<Utc>) -> Self {
+        self.run_at = Some(run_at);
+        self
+    }
+
+    /// Hold this job back for `delay` after it was created.
+    pub fn with_delay(self, delay: std::time::Duration) -> Self {
+        let run_at = chrono::TimeDelta::from_std(delay)
+            .ok()
+            .and_then(|delay| self.created_at.checked_add_signed(delay))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        self.with_run_at(run_at)
+    }
+
+    /// Require the job to finish by `deadline`, however many attempts it takes.
+    pub fn with_deadline(mut self, deadline: DateTime<Utc>) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Whether the job's deadline, if any, has passed.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.deadline.is_some_and(|deadline| deadline <= now)
+    }
+
+    /// How long an attempt starting at `now` may run: `timeout_secs`, cut
+    /// short if the deadline comes first.
+    pub fn attempt_timeout(&self, now: DateTime<Utc>) -> std::time::Duration {
+        let timeout = std::time::Duration::from_secs(self.timeout_secs);
+        match self.deadline {
+            Some(deadline) => (deadline - now).to_std().unwrap_or_default().min(timeout),
+            None => timeout,
+        }
+    }
+
+    /// Whether the job's scheduled time, if any, has arrived.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.run_at.is_none_or(|run_at| run_at <= now)
+    }
+
+    /// Whether the job is waiting but held back until a later time.
+    pub fn is_scheduled(&self, now: DateTime<Utc>) -> bool {
+        matches!(
+            self.status,
+            JobStatus::Pending | JobStatus::Scheduled { .. }
+        ) && !self.is_due(now)
+    }
+
+    /// Status for a job waiting in line: scheduled until its `run_at`, then pending.
+    pub fn waiting_status(&self, now: DateTime<Utc>) -> JobStatus {
+        match self.run_at {
+            Some(run_at) if run_at > now => JobStatus::Scheduled { run_at },
+            _ => JobStatus::Pending,
+        }
+    }
+
+    /// Record the start of attempt number `attempts` on `worker_id`.
+    pub fn start_attempt(&mut self, worker_id: impl Into<String>, started_at: DateTime<Utc>) {
+        self.attempts_log.push(AttemptRecord {
+            attempt: self.attempts,
+            worker_id: worker_id.into(),
+            started_at,
+            finished_at: None,
+            error: None,
+        });
+    }
+
+    /// Close the running attempt, if any, with the error it failed with.
+    pub fn finish_attempt(&mut self, finished_at: DateTime<Utc>, error: Option<String>) {
+        if let Some(attempt) = self
+            .attempts_log
+            .last_mut()
+            .filter(|attempt| attempt.finished_at.is_none())
+        {
+            attempt.finished_at = Some(finished_at);
+            attempt.error = error;
+        }
+    }
+}
+
+/// Per-job settings that fall back to the target queue's config when unset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobOverrides {
+    /// Maximum number of retry attempts.
+    pub max_retries: Option<u32>,
+    /// Timeout in seconds for job execution.
+    pub timeout_secs: Option<u64>,
+}
+
+impl JobOverrides {
+    /// Pin the job's current settings so queue defaults are ignored.
+    pub fn from_job(job: &Job) -> Self {
+        Self {
+            max_retries: Some(job.max_retries),
+            timeout_secs: Some(job.timeout_secs),
+        }
+    }
+
+    /// Resolve the job's settings against the queue's defaults.
+    ///
+    /// The queue's default tags are added to the job's own, skipping duplicates.
+    pub fn apply(&self, job: &mut Job, config: &super::QueueConfig) {
+        job.max_retries = self.max_retries.unwrap_or(config.default_max_retries);
+        job.timeout_secs = self.timeout_secs.unwrap_or(config.default_timeout_secs);
+        for tag in &config.default_tags {
+            if !job.tags.contains(tag) {
+                job.tags.push(tag.clone());
+            }
+        }
+    }
+}
+
+/// A newly enqueued job and where it landed in line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EnqueueReceipt {
+    /// The job as it was stored.
+    pub job: Job,
+    /// Pending jobs that will be handed out before this one; 0 means it's next.
+    ///
+    /// Scheduled jobs count every job pending when they were enqueued.
+    pub position: usize,
+    /// Jobs pending on the queue, including this one once it is due.
+    pub pending_total: usize,
+}
+
+/// Which table a [`JobRecordView`] was loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JobSource {
+    /// The active `job` table.
+    Active,
+    /// The archived `job_history` table.
+    History,
+}
+
+/// A job as seen by search, whether it is still active or already archived.
+///
+/// Archived jobs no longer carry their payload or configuration, so this view
+/// only holds the fields both tables have in common.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct JobRecordView {
+    /// Unique identifier for this job.
+    pub id: JobId,
+    /// The queue this job belongs to.
+    pub queue_id: super::QueueId,
+    /// Type of job.
+    pub job_type: String,
+    /// Execution priority.
+    pub priority: Priority,
+    /// Simple status string (see [`JobStatus::as_str`]).
+    pub status: String,
+    /// Number of attempts so far.
+    pub attempts: u32,
+    /// Execution time for completed jobs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Error or cancellation reason for failed/cancelled jobs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Result summary for completed jobs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_summary: Option<String>,
+    /// Tags for filtering and grouping.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Lines logged by handlers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logs: Vec<JobLogLine>,
+    /// Every run of the job, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts_log: Vec<AttemptRecord>,
+    /// Tenant owning the job; `None` for untenanted queues.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// When the job was created.
+    pub created_at: DateTime<Utc>,
+    /// When the job was last updated (or archived).
+    pub updated_at: DateTime<Utc>,
+    /// Which table the record came from.
+    pub source: JobSource,
+}
+
+impl JobRecordView {
+    /// Check if the record came from job history.
+    pub fn is_archived(&self) -> bool {
+        self.source == JobSource::History
+    }
+}
+
+impl From<Job> for JobRecordView {
+    fn from(job: Job) -> Self {
+        let (duration_ms, error, result_summary) = match &job.status {
+            JobStatus::Completed {
+                started_at,
+                completed_at,
+                result,
+            } => (
+                Some(duration_ms(*started_at, *completed_at)),
+                None,
+                Some(result.summary.clone()),
+            ),
+            JobStatus::Failed { error, .. } => (None, Some(error.clone()), None),
+            JobStatus::Cancelled { reason, .. } => {
+                (None, reason.as_ref().map(|r| r.to_string()), None)
+            }
+            _ => (None, None, None),
+        };
+
+        Self {
+            id: job.id,
+            queue_id: job.queue_id,
+            job_type: job.job_type,
+            priority: job.priority,
+            status: job.status.as_str().to_string(),
+            attempts: job.attempts,
+            duration_ms,
+            error,
+            result_summary,
+            tags: job.tags,
+            logs: job.logs,
+            attempts_log: job.attempts_log,
+            tenant_id: job.tenant_id,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+            source: JobSource::Active,
+        }
+    }
 }
@@ -4,11 +4,20 @@
 //! - Job and JobStatus for work items
 //! - Queue and QueueState for job containers
 //! - Events for real-time updates
+//! - Worker status snapshots
 
 mod events;
 mod job;
 mod queue;
+mod worker;
 
 pub use events::JobEvent;
-pub use job::{Job, JobId, JobResult, JobStatus, Priority};
-pub use queue::{Queue, QueueConfig, QueueId, QueueState, QueueStats};
+pub use job::{
+    AttemptRecord, CancellationReason, EnqueueReceipt, Job, JobId, JobLogLevel, JobLogLine,
+    JobOverrides, JobRecordView, JobResult, JobSource, JobStatus, Priority, duration_ms,
+};
+pub use queue::{
+    HandlerSource, JobTypeConfig, JobTypeInfo, PriorityCounts, Queue, QueueBuilder, QueueConfig,
+    QueueId, QueueState, QueueStats, ThroughputBucket,
+};
+pub use worker::WorkerInfo;
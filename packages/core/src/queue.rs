@@ -1,11 +1,16 @@
 //! Queue domain types for job containers.
 
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
+use crate::job::Priority;
+
 /// Unique identifier for a queue.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(value_type = String))]
 #[serde(transparent)]
 pub struct QueueId(pub Ulid);
 
@@ -35,6 +40,7 @@ impl std::fmt::Display for QueueId {
 
 /// Current operational state of a queue.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum QueueState {
     /// Queue is active and processing jobs.
@@ -59,22 +65,113 @@ impl std::fmt::Display for QueueState {
     }
 }
 
-/// Configuration for queue behavior.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(default)]
-pub struct QueueConfig {
-    /// Number of concurrent workers for this queue.
-    pub concurrency: u32,
-    /// Default timeout for jobs in this queue (seconds).
-    pub default_timeout_secs: u64,
-    /// Default max retries for jobs in this queue.
-    pub default_max_retries: u32,
-    /// Maximum number of jobs that can be queued.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_queue_size: Option<usize>,
-    /// Rate limit: max jobs per second.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub rate_limit: Option<f64>,
+pub use schema_defaults::{PriorityCounts, QueueConfig, QueueStats};
+
+/// Types that are `#[serde(default)]` and derive `ToSchema`.
+///
+/// utoipa documents their field defaults with `serde_json::to_value(..).unwrap()`,
+/// so the `unwrap` ban is lifted for the derived code here. Their impls live
+/// outside this module and stay linted.
+#[cfg_attr(feature = "openapi", allow(clippy::disallowed_methods))]
+mod schema_defaults {
+    use serde::{Deserialize, Serialize};
+
+    /// Configuration for queue behavior.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+    #[serde(default)]
+    pub struct QueueConfig {
+        /// Number of concurrent workers for this queue.
+        pub concurrency: u32,
+        /// Default timeout for jobs in this queue (seconds).
+        pub default_timeout_secs: u64,
+        /// Default max retries for jobs in this queue.
+        pub default_max_retries: u32,
+        /// Maximum number of jobs that can be queued.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub max_queue_size: Option<usize>,
+        /// Largest payload accepted, in bytes of serialized JSON.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub max_payload_bytes: Option<usize>,
+        /// Rate limit: max jobs per second.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub rate_limit: Option<f64>,
+        /// Priority levels a job is raised by when it is retried (0 disables).
+        pub retry_priority_boost: u8,
+        /// Tags added to every job enqueued on this queue (e.g. `team:payments`).
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub default_tags: Vec<String>,
+        /// Job types this queue accepts; `None` accepts any type.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub allowed_job_types: Option<Vec<String>>,
+        /// Reject jobs whose type has no registered handler when they're enqueued.
+        ///
+        /// Turn off to accept job types a newer deployment will handle; such jobs
+        /// fail at the worker if no handler turns up in time.
+        pub strict_job_types: bool,
+        /// How often idle workers poll for jobs (milliseconds).
+        ///
+        /// Workers are also woken when a job is enqueued, so this mainly bounds
+        /// how late delayed and retried jobs are picked up.
+        pub poll_interval_ms: u64,
+        /// URL sent a signed POST of the event whenever a job in this queue finishes.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub webhook_url: Option<String>,
+        /// Failure rate (0.0 to 1.0) over recent jobs that trips the circuit
+        /// breaker and pauses the queue; `None` disables the breaker.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub breaker_threshold: Option<f64>,
+        /// How long a tripped breaker keeps the queue paused before letting a
+        /// trial job through (seconds).
+        pub breaker_cooldown_secs: u64,
+        /// Accept jobs whose database write fails at enqueue instead of rejecting
+        /// them, retrying the write in the background.
+        ///
+        /// Such jobs live only in memory until the write goes through, so a crash
+        /// before then loses them.
+        pub accept_unpersisted: bool,
+    }
+
+    /// Statistics for a queue's current state.
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+    #[serde(default)]
+    pub struct QueueStats {
+        /// Number of pending jobs ready for a worker.
+        pub pending: u64,
+        /// Number of jobs waiting for their scheduled time.
+        pub scheduled: u64,
+        /// Number of running jobs.
+        pub running: u64,
+        /// Number of completed jobs (since last reset).
+        pub completed: u64,
+        /// Number of failed jobs (since last reset).
+        pub failed: u64,
+        /// Average job duration in milliseconds.
+        pub avg_duration_ms: Option<f64>,
+        /// Jobs processed per minute.
+        pub throughput_per_min: Option<f64>,
+        /// Age of the oldest pending job in milliseconds.
+        pub oldest_pending_age_ms: Option<u64>,
+        /// Pending jobs by priority.
+        pub pending_by_priority: PriorityCounts,
+        /// Whether pending jobs are being held back by the queue's rate limit.
+        pub rate_limited: bool,
+        /// Whether the circuit breaker has paused the queue after a failure spike.
+        pub breaker_open: bool,
+    }
+
+    /// Job counts broken down by priority.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+    #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+    #[serde(default)]
+    pub struct PriorityCounts {
+        pub deferred: u64,
+        pub low: u64,
+        pub normal: u64,
+        pub high: u64,
+        pub critical: u64,
+    }
 }
 
 impl Default for QueueConfig {
@@ -84,27 +181,55 @@ impl Default for QueueConfig {
             default_timeout_secs: 300,
             default_max_retries: 3,
             max_queue_size: None,
+            max_payload_bytes: None,
             rate_limit: None,
+            retry_priority_boost: 0,
+            default_tags: Vec::new(),
+            allowed_job_types: None,
+            strict_job_types: true,
+            poll_interval_ms: 100,
+            webhook_url: None,
+            breaker_threshold: None,
+            breaker_cooldown_secs: 60,
+            accept_unpersisted: false,
         }
     }
 }
 
-/// Statistics for a queue's current state.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
-#[serde(default)]
-pub struct QueueStats {
-    /// Number of pending jobs.
-    pub pending: u64,
-    /// Number of running jobs.
-    pub running: u64,
-    /// Number of completed jobs (since last reset).
-    pub completed: u64,
-    /// Number of failed jobs (since last reset).
-    pub failed: u64,
-    /// Average job duration in milliseconds.
-    pub avg_duration_ms: Option<f64>,
-    /// Jobs processed per minute.
-    pub throughput_per_min: Option<f64>,
+impl QueueConfig {
+    /// Worker poll interval, at least one millisecond.
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms.max(1))
+    }
+
+    /// Circuit breaker cooldown, at least one second.
+    pub fn breaker_cooldown(&self) -> Duration {
+        Duration::from_secs(self.breaker_cooldown_secs.max(1))
+    }
+}
+
+impl PriorityCounts {
+    /// Count for a single priority.
+    pub fn get(&self, priority: Priority) -> u64 {
+        match priority {
+            Priority::Deferred => self.deferred,
+            Priority::Low => self.low,
+            Priority::Normal => self.normal,
+            Priority::High => self.high,
+            Priority::Critical => self.critical,
+        }
+    }
+
+    /// Count one more job at the given priority.
+    pub fn add(&mut self, priority: Priority) {
+        match priority {
+            Priority::Deferred => self.deferred += 1,
+            Priority::Low => self.low += 1,
+            Priority::Normal => self.normal += 1,
+            Priority::High => self.high += 1,
+            Priority::Critical => self.critical += 1,
+        }
+    }
 }
 
 impl QueueStats {
@@ -127,10 +252,77 @@ impl QueueStats {
             Some((self.completed as f64 / total as f64) * 100.0)
         }
     }
+
+    /// Stats for a fresh reporting window.
+    ///
+    /// Live gauges (pending/scheduled/running and their breakdowns) carry over;
+    /// counters and rates reset.
+    pub fn rolled_over(&self) -> Self {
+        Self {
+            pending: self.pending,
+            scheduled: self.scheduled,
+            running: self.running,
+            oldest_pending_age_ms: self.oldest_pending_age_ms,
+            pending_by_priority: self.pending_by_priority,
+            ..Default::default()
+        }
+    }
+}
+
+/// Archived job counts for one time bucket of a queue's throughput history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ThroughputBucket {
+    /// Start of the bucket.
+    pub bucket_start: DateTime<Utc>,
+    /// Jobs that completed within the bucket.
+    pub completed: u64,
+    /// Jobs that failed permanently within the bucket.
+    pub failed: u64,
+}
+
+/// Where the handler for a job type comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum HandlerSource {
+    /// The queue's own handler overrides.
+    Queue,
+    /// The global handler registry.
+    Global,
+}
+
+/// Settings a job of a given type runs with in a queue, after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct JobTypeConfig {
+    /// Queue the job type was resolved against.
+    pub queue_id: QueueId,
+    /// The job type.
+    pub job_type: String,
+    /// Handler that would run the job; `None` means jobs of this type fail.
+    pub handler: Option<HandlerSource>,
+    /// Whether payloads are validated against a schema before enqueue.
+    pub has_payload_schema: bool,
+    /// Timeout in seconds for jobs that don't set their own.
+    pub timeout_secs: u64,
+    /// Max retries for jobs that don't set their own.
+    pub max_retries: u32,
+}
+
+/// A job type with a registered handler.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct JobTypeInfo {
+    /// The job type.
+    pub job_type: String,
+    /// Example payload the handler accepts, for trying it out.
+    pub sample_payload: Option<serde_json::Value>,
 }
 
 /// A queue manages a set of jobs and their execution.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Queue {
     /// Unique identifier for this queue.
     pub id: QueueId,
@@ -143,12 +335,22 @@ pub struct Queue {
     pub state: QueueState,
     /// Queue configuration.
     pub config: QueueConfig,
+    /// Labels for operating on groups of queues at once.
+    #[serde(default)]
+    pub tags: Vec<String>,
     /// Current statistics.
     pub stats: QueueStats,
     /// When the queue was created.
     pub created_at: DateTime<Utc>,
     /// When the queue was last updated.
     pub updated_at: DateTime<Utc>,
+    /// Tenant owning the queue, copied onto its jobs.
+    ///
+    /// Only a label: reads scope to it when they ask to, via
+    /// `QueueRepository::list_for_tenant` or `JobFilter::tenant_id`, and
+    /// nothing else hides the queue or its jobs from other tenants.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
 }
 
 impl Queue {
@@ -161,9 +363,11 @@ impl Queue {
             description: None,
             state: QueueState::Running,
             config: QueueConfig::default(),
+            tags: Vec::new(),
             stats: QueueStats::default(),
             created_at: now,
             updated_at: now,
+            tenant_id: None,
         }
     }
 
@@ -179,6 +383,23 @@ impl Queue {
         self
     }
 
+    /// Set the tags for this queue.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Set the tenant owning this queue.
+    pub fn with_tenant(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Check if the queue carries the given tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
     /// Check if the queue is accepting new jobs.
     pub fn is_accepting_jobs(&self) -> bool {
         matches!(self.state, QueueState::Running)
@@ -189,3 +410,143 @@ impl Queue {
         matches!(self.state, QueueState::Running | QueueState::Draining)
     }
 }
+
+/// Builder for declaring a queue and its configuration in code.
+///
+/// ```
+/// use queue_core::QueueBuilder;
+///
+/// let queue = QueueBuilder::new("emails")
+///     .concurrency(8)
+///     .max_size(1000)
+///     .rate_limit(50.0)
+///     .build();
+/// assert_eq!(queue.config.concurrency, 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct QueueBuilder {
+    queue: Queue,
+}
+
+impl QueueBuilder {
+    /// Start a queue with the given name and default configuration.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            queue: Queue::new(name),
+        }
+    }
+
+    /// Set what the queue is for.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.queue.description = Some(description.into());
+        self
+    }
+
+    /// Add a label for operating on groups of queues at once.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.queue.tags.push(tag.into());
+        self
+    }
+
+    /// Set the number of concurrent workers.
+    pub fn concurrency(mut self, concurrency: u32) -> Self {
+        self.queue.config.concurrency = concurrency;
+        self
+    }
+
+    /// Set the timeout for jobs that don't set their own.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.queue.config.default_timeout_secs = timeout.as_secs();
+        self
+    }
+
+    /// Set the max retries for jobs that don't set their own.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.queue.config.default_max_retries = max_retries;
+        self
+    }
+
+    /// Cap how many jobs may be queued.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.queue.config.max_queue_size = Some(max_size);
+        self
+    }
+
+    /// Reject jobs whose serialized payload is larger than this many bytes.
+    pub fn max_payload_bytes(mut self, max_bytes: usize) -> Self {
+        self.queue.config.max_payload_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Limit how many jobs start per second.
+    pub fn rate_limit(mut self, jobs_per_sec: f64) -> Self {
+        self.queue.config.rate_limit = Some(jobs_per_sec);
+        self
+    }
+
+    /// Raise retried jobs by this many priority levels.
+    pub fn retry_priority_boost(mut self, levels: u8) -> Self {
+        self.queue.config.retry_priority_boost = levels;
+        self
+    }
+
+    /// Add a tag to every job enqueued on this queue.
+    pub fn default_tag(mut self, tag: impl Into<String>) -> Self {
+        self.queue.config.default_tags.push(tag.into());
+        self
+    }
+
+    /// Only accept jobs of the given types.
+    pub fn allowed_job_types<I, S>(mut self, job_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.queue.config.allowed_job_types = Some(job_types.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Accept job types that have no registered handler yet.
+    pub fn accept_unknown_job_types(mut self) -> Self {
+        self.queue.config.strict_job_types = false;
+        self
+    }
+
+    /// Set how often idle workers poll for jobs.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.queue.config.poll_interval_ms = interval.as_millis() as u64;
+        self
+    }
+
+    /// Post finished-job events to this URL.
+    pub fn webhook_url(mut self, url: impl Into<String>) -> Self {
+        self.queue.config.webhook_url = Some(url.into());
+        self
+    }
+
+    /// Pause the queue for `cooldown` whenever the recent failure rate reaches
+    /// `threshold` (0.0 to 1.0).
+    pub fn circuit_breaker(mut self, threshold: f64, cooldown: Duration) -> Self {
+        self.queue.config.breaker_threshold = Some(threshold);
+        self.queue.config.breaker_cooldown_secs = cooldown.as_secs();
+        self
+    }
+
+    /// Keep jobs whose database write fails at enqueue, retrying the write in
+    /// the background rather than rejecting them.
+    pub fn accept_unpersisted(mut self) -> Self {
+        self.queue.config.accept_unpersisted = true;
+        self
+    }
+
+    /// Assign the queue, and every job enqueued on it, to a tenant.
+    pub fn tenant(mut self, tenant_id: impl Into<String>) -> Self {
+        self.queue.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Finish the queue.
+    pub fn build(self) -> Queue {
+        self.queue
+    }
+}
@@ -0,0 +1,30 @@
+//! Worker status as tracked by the supervisor.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::job::JobId;
+use crate::queue::QueueId;
+
+/// Snapshot of a live worker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WorkerInfo {
+    /// Unique worker ID.
+    pub worker_id: String,
+    /// Queue the worker serves.
+    pub queue_id: QueueId,
+    /// Name of that queue.
+    pub queue_name: String,
+    /// Job the worker is running, if any.
+    pub current_job: Option<JobId>,
+    /// When the worker last reported in.
+    pub last_heartbeat: DateTime<Utc>,
+}
+
+impl WorkerInfo {
+    /// Whether the worker is running a job.
+    pub fn is_busy(&self) -> bool {
+        self.current_job.is_some()
+    }
+}
@@ -0,0 +1,80 @@
+#![allow(clippy::disallowed_methods)]
+
+use chrono::Utc;
+use queue_core::{CancellationReason, JobStatus};
+use serde_json::json;
+
+#[test]
+fn test_every_reason_round_trips() {
+    let reasons = [
+        (CancellationReason::UserRequested, "user_requested"),
+        (CancellationReason::Superseded, "superseded"),
+        (CancellationReason::Expired, "expired"),
+        (CancellationReason::DependencyFailed, "dependency_failed"),
+        (
+            CancellationReason::Administrative {
+                note: "maintenance window".into(),
+            },
+            "administrative: maintenance window",
+        ),
+    ];
+
+    for (reason, stored) in reasons {
+        let value = serde_json::to_value(&reason).unwrap();
+        assert_eq!(value, stored);
+        assert_eq!(
+            serde_json::from_value::<CancellationReason>(value).unwrap(),
+            reason
+        );
+    }
+}
+
+#[test]
+fn test_note_matching_a_variant_name_stays_administrative() {
+    let reason = CancellationReason::Administrative {
+        note: "expired".into(),
+    };
+    let value = serde_json::to_value(&reason).unwrap();
+    assert_eq!(
+        serde_json::from_value::<CancellationReason>(value).unwrap(),
+        reason
+    );
+}
+
+#[test]
+fn test_free_form_reasons_read_back_as_administrative() {
+    let reason: CancellationReason = serde_json::from_value(json!("Cancelled from admin")).unwrap();
+    assert_eq!(
+        reason,
+        CancellationReason::Administrative {
+            note: "Cancelled from admin".into()
+        }
+    );
+}
+
+#[test]
+fn test_cancelled_status_keeps_its_reason() {
+    let status = JobStatus::Cancelled {
+        cancelled_at: Utc::now(),
+        reason: Some(CancellationReason::Superseded),
+    };
+    let value = serde_json::to_value(&status).unwrap();
+    assert_eq!(value["reason"], "superseded");
+    assert_eq!(serde_json::from_value::<JobStatus>(value).unwrap(), status);
+
+    // Statuses stored with a plain string reason still load
+    let legacy = json!({
+        "status": "cancelled",
+        "cancelled_at": Utc::now(),
+        "reason": "no longer needed",
+    });
+    let JobStatus::Cancelled { reason, .. } = serde_json::from_value(legacy).unwrap() else {
+        panic!("expected a cancelled status");
+    };
+    assert_eq!(
+        reason,
+        Some(CancellationReason::Administrative {
+            note: "no longer needed".into()
+        })
+    );
+}
@@ -0,0 +1,26 @@
+use queue_core::JobId;
+
+#[test]
+fn test_job_ids_sort_in_creation_order() {
+    let ids: Vec<String> = (0..10_000).map(|_| JobId::new().to_string()).collect();
+
+    // Many of these share a millisecond, so this only holds if ids are monotonic
+    for pair in ids.windows(2) {
+        assert!(pair[0] < pair[1], "{} was not before {}", pair[0], pair[1]);
+    }
+}
+
+#[test]
+fn test_job_ids_stay_unique_across_threads() {
+    let handles: Vec<_> = (0..4)
+        .map(|_| std::thread::spawn(|| (0..1_000).map(|_| JobId::new()).collect::<Vec<_>>()))
+        .collect();
+
+    let mut ids = std::collections::HashSet::new();
+    for handle in handles {
+        for id in handle.join().unwrap_or_default() {
+            assert!(ids.insert(id), "duplicate id {}", id);
+        }
+    }
+    assert_eq!(ids.len(), 4_000);
+}
@@ -0,0 +1,43 @@
+#![allow(clippy::disallowed_methods)]
+
+use chrono::{Duration, Utc};
+use queue_core::{Job, JobStatus, QueueId};
+use serde_json::json;
+
+#[test]
+fn test_scheduled_status_round_trips() {
+    let run_at = Utc::now();
+    let status = JobStatus::Scheduled { run_at };
+
+    let value = serde_json::to_value(&status).unwrap();
+    assert_eq!(value["status"], "scheduled");
+    assert_eq!(serde_json::from_value::<JobStatus>(value).unwrap(), status);
+    assert_eq!(status.as_str(), "scheduled");
+    assert!(!status.is_terminal());
+    assert!(!status.can_retry());
+}
+
+#[test]
+fn test_waiting_status_is_scheduled_until_run_at() {
+    let now = Utc::now();
+    let run_at = now + Duration::minutes(5);
+    let job = Job::new(QueueId::new(), "report", json!({})).with_run_at(run_at);
+
+    assert_eq!(job.waiting_status(now), JobStatus::Scheduled { run_at });
+    assert_eq!(job.waiting_status(run_at), JobStatus::Pending);
+
+    let unscheduled = Job::new(QueueId::new(), "report", json!({}));
+    assert_eq!(unscheduled.waiting_status(now), JobStatus::Pending);
+}
+
+#[test]
+fn test_scheduled_job_is_not_ready_before_run_at() {
+    let now = Utc::now();
+    let run_at = now + Duration::minutes(5);
+    let mut job = Job::new(QueueId::new(), "report", json!({})).with_run_at(run_at);
+    job.status = job.waiting_status(now);
+
+    assert!(job.is_scheduled(now));
+    assert!(!job.is_due(now));
+    assert!(!job.is_scheduled(run_at));
+}
@@ -0,0 +1,67 @@
+#![allow(clippy::disallowed_methods)]
+
+use queue_core::{Priority, PriorityCounts};
+
+const ASCENDING: [Priority; 5] = [
+    Priority::Deferred,
+    Priority::Low,
+    Priority::Normal,
+    Priority::High,
+    Priority::Critical,
+];
+
+#[test]
+fn test_priorities_order_from_deferred_to_critical() {
+    for pair in ASCENDING.windows(2) {
+        assert!(
+            pair[0] < pair[1],
+            "{} should sort below {}",
+            pair[0],
+            pair[1]
+        );
+    }
+
+    let mut shuffled = [
+        Priority::High,
+        Priority::Deferred,
+        Priority::Critical,
+        Priority::Normal,
+        Priority::Low,
+    ];
+    shuffled.sort();
+    assert_eq!(shuffled, ASCENDING);
+}
+
+#[test]
+fn test_priorities_round_trip_as_lowercase_strings() {
+    let names = ["deferred", "low", "normal", "high", "critical"];
+    for (priority, name) in ASCENDING.into_iter().zip(names) {
+        assert_eq!(priority.to_string(), name);
+        assert_eq!(serde_json::to_value(priority).unwrap(), name);
+        assert_eq!(
+            serde_json::from_value::<Priority>(name.into()).unwrap(),
+            priority
+        );
+    }
+}
+
+#[test]
+fn test_boost_steps_through_every_level() {
+    assert_eq!(Priority::Deferred.boosted(0), Priority::Deferred);
+    assert_eq!(Priority::Deferred.boosted(1), Priority::Low);
+    assert_eq!(Priority::Deferred.boosted(2), Priority::Normal);
+    assert_eq!(Priority::Low.boosted(2), Priority::High);
+    assert_eq!(Priority::Deferred.boosted(u8::MAX), Priority::Critical);
+}
+
+#[test]
+fn test_priority_counts_track_deferred_jobs() {
+    let mut counts = PriorityCounts::default();
+    for priority in ASCENDING {
+        counts.add(priority);
+    }
+    counts.add(Priority::Deferred);
+
+    assert_eq!(counts.get(Priority::Deferred), 2);
+    assert_eq!(counts.get(Priority::Critical), 1);
+}
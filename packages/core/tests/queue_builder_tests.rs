@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use queue_core::{QueueBuilder, QueueConfig, QueueState};
+
+#[test]
+fn test_builder_defaults_match_queue_config() {
+    let queue = QueueBuilder::new("defaults").build();
+    assert_eq!(queue.name, "defaults");
+    assert_eq!(queue.state, QueueState::Running);
+    assert_eq!(queue.description, None);
+    assert!(queue.tags.is_empty());
+    assert_eq!(queue.config, QueueConfig::default());
+}
+
+#[test]
+fn test_builder_sets_queue_fields() {
+    let queue = QueueBuilder::new("emails")
+        .description("Outgoing mail")
+        .tag("team:growth")
+        .tag("tier:1")
+        .build();
+    assert_eq!(queue.name, "emails");
+    assert_eq!(queue.description.as_deref(), Some("Outgoing mail"));
+    assert_eq!(queue.tags, ["team:growth", "tier:1"]);
+}
+
+#[test]
+fn test_builder_setters_map_to_config_fields() {
+    let config = QueueBuilder::new("emails")
+        .concurrency(8)
+        .timeout(Duration::from_secs(90))
+        .max_retries(5)
+        .max_size(1000)
+        .max_payload_bytes(64 * 1024)
+        .rate_limit(50.0)
+        .retry_priority_boost(2)
+        .default_tag("source:smtp")
+        .allowed_job_types(["send_email", "send_digest"])
+        .poll_interval(Duration::from_millis(250))
+        .webhook_url("https://example.com/hooks")
+        .circuit_breaker(0.5, Duration::from_secs(120))
+        .accept_unpersisted()
+        .build()
+        .config;
+
+    assert_eq!(
+        config,
+        QueueConfig {
+            concurrency: 8,
+            default_timeout_secs: 90,
+            default_max_retries: 5,
+            max_queue_size: Some(1000),
+            max_payload_bytes: Some(64 * 1024),
+            rate_limit: Some(50.0),
+            retry_priority_boost: 2,
+            default_tags: vec!["source:smtp".to_string()],
+            allowed_job_types: Some(vec!["send_email".to_string(), "send_digest".to_string()]),
+            strict_job_types: true,
+            poll_interval_ms: 250,
+            webhook_url: Some("https://example.com/hooks".to_string()),
+            breaker_threshold: Some(0.5),
+            breaker_cooldown_secs: 120,
+            accept_unpersisted: true,
+        }
+    );
+}
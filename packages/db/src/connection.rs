@@ -1,6 +1,8 @@
 //! Database connection management with lazy initialization.
 
-use std::sync::LazyLock;
+use std::future::IntoFuture;
+use std::sync::{LazyLock, OnceLock};
+use std::time::Duration;
 use surrealdb::Surreal;
 use surrealdb::engine::any::{Any, connect};
 use surrealdb::opt::auth::Root;
@@ -10,6 +12,12 @@ use tokio::sync::OnceCell;
 /// Global database instance using lazy initialization.
 static DB: LazyLock<OnceCell<Surreal<Any>>> = LazyLock::new(OnceCell::new);
 
+/// Backend of the global database connection, set once it connects.
+static BACKEND: OnceLock<String> = OnceLock::new();
+
+/// Per-query deadline of the global connection, set once it connects.
+static QUERY_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
 /// Database connection wrapper.
 pub type Database = Surreal<Any>;
 
@@ -24,6 +32,12 @@ pub struct DbConfig {
     pub database: String,
     /// Optional root credentials for authentication
     pub credentials: Option<(String, String)>,
+    /// How many times to retry a failed connection before giving up
+    pub max_connect_retries: u32,
+    /// Delay before the first retry in milliseconds, doubled for each one after
+    pub connect_retry_base_ms: u64,
+    /// How long a single query may run before failing with `DbError::Timeout`
+    pub query_timeout_ms: u64,
 }
 
 impl Default for DbConfig {
@@ -33,6 +47,9 @@ impl Default for DbConfig {
             namespace: "jobqueue".to_string(),
             database: "main".to_string(),
             credentials: None,
+            max_connect_retries: 5,
+            connect_retry_base_ms: 250,
+            query_timeout_ms: 30_000,
         }
     }
 }
@@ -59,6 +76,13 @@ impl DbConfig {
         }
     }
 
+    /// Storage engine named by the endpoint scheme (`mem`, `file`, `rocksdb`, ...).
+    pub fn backend(&self) -> &str {
+        self.endpoint
+            .split_once("://")
+            .map_or(self.endpoint.as_str(), |(scheme, _)| scheme)
+    }
+
     /// Set the namespace.
     pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
         self.namespace = namespace.into();
@@ -80,6 +104,31 @@ impl DbConfig {
         self.credentials = Some((username.into(), password.into()));
         self
     }
+
+    /// Set how often and how patiently a failed connection is retried.
+    pub fn with_connect_retries(mut self, max_retries: u32, base_delay_ms: u64) -> Self {
+        self.max_connect_retries = max_retries;
+        self.connect_retry_base_ms = base_delay_ms;
+        self
+    }
+
+    /// Set how long a single query may run before it is abandoned.
+    pub fn with_query_timeout(mut self, timeout: Duration) -> Self {
+        self.query_timeout_ms = timeout.as_millis().try_into().unwrap_or(u64::MAX);
+        self
+    }
+
+    /// Per-query deadline as a `Duration`.
+    pub fn query_timeout(&self) -> Duration {
+        Duration::from_millis(self.query_timeout_ms)
+    }
+
+    /// Delay before the given retry (1-based), doubling each time up to 30s.
+    fn retry_delay(&self, retry: u32) -> Duration {
+        let factor = 2u64.saturating_pow(retry.saturating_sub(1));
+        Duration::from_millis(self.connect_retry_base_ms.saturating_mul(factor))
+            .min(Duration::from_secs(30))
+    }
 }
 
 /// Database errors.
@@ -91,38 +140,88 @@ pub enum DbError {
     AlreadyInitialized,
     #[error("Connection error: {0}")]
     Connection(#[from] surrealdb::Error),
+    #[error("Database unreachable after {attempts} attempts: {source}")]
+    Unreachable {
+        attempts: u32,
+        source: surrealdb::Error,
+    },
     #[error("Query error: {0}")]
     Query(String),
     #[error("Not found: {0}")]
     NotFound(String),
     #[error("Serialization error: {0}")]
     Serialization(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    #[error("Query timed out after {0:?}")]
+    Timeout(Duration),
+}
+
+/// Connect, sign in and select the namespace and database once.
+async fn connect_once(config: &DbConfig) -> Result<Database, surrealdb::Error> {
+    let db = connect(&config.endpoint).await?;
+
+    // Authenticate if credentials provided
+    if let Some((username, password)) = &config.credentials {
+        db.signin(Root { username, password }).await?;
+    }
+
+    // Select namespace and database
+    db.use_ns(&config.namespace)
+        .use_db(&config.database)
+        .await?;
+
+    Ok(db)
+}
+
+/// Open a database connection, retrying with exponential backoff.
+///
+/// Makes up to `max_connect_retries + 1` attempts, so a database that is still
+/// starting up alongside the app gets time to come up.
+pub async fn connect_db(config: &DbConfig) -> Result<Database, DbError> {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        tracing::info!(
+            "Connecting to database: {} (attempt {})",
+            config.endpoint,
+            attempts
+        );
+
+        match connect_once(config).await {
+            Ok(db) => return Ok(db),
+            Err(source) if attempts > config.max_connect_retries => {
+                return Err(DbError::Unreachable { attempts, source });
+            }
+            Err(e) => {
+                let delay = config.retry_delay(attempts);
+                tracing::warn!(
+                    "Database connection attempt {} failed: {}; retrying in {:?}",
+                    attempts,
+                    e,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
 }
 
 /// Initialize the database connection.
 ///
 /// This should be called once at application startup before any database operations.
+/// A failed initialization leaves the connection unset, so it can be retried.
 pub async fn init_db(config: DbConfig) -> Result<&'static Database, DbError> {
     DB.get_or_try_init(|| async {
-        tracing::info!("Connecting to database: {}", config.endpoint);
-
-        let db = connect(&config.endpoint).await?;
-
-        // Authenticate if credentials provided
-        if let Some((username, password)) = &config.credentials {
-            db.signin(Root { username, password }).await?;
-        }
-
-        // Select namespace and database
-        db.use_ns(&config.namespace)
-            .use_db(&config.database)
-            .await?;
+        let db = connect_db(&config).await?;
 
         tracing::info!(
             "Connected to database: {}/{}",
             config.namespace,
             config.database
         );
+        let _ = BACKEND.set(config.backend().to_string());
+        let _ = QUERY_TIMEOUT.set(config.query_timeout());
 
         Ok(db)
     })
@@ -144,3 +243,29 @@ pub fn get_db() -> Result<&'static Database, DbError> {
 pub fn try_get_db() -> Option<&'static Database> {
     DB.get()
 }
+
+/// Backend of the database connection (see [`DbConfig::backend`]).
+///
+/// Returns None if the database hasn't been initialized yet.
+pub fn backend() -> Option<&'static str> {
+    BACKEND.get().map(String::as_str)
+}
+
+/// Await a database call, giving up once the configured query timeout passes.
+///
+/// A stuck query fails with [`DbError::Timeout`] instead of hanging the
+/// caller. Before `init_db` has run, the default timeout applies.
+pub async fn with_deadline<F, T>(call: F) -> Result<T, DbError>
+where
+    F: IntoFuture<Output = Result<T, surrealdb::Error>>,
+{
+    let timeout = QUERY_TIMEOUT
+        .get()
+        .copied()
+        .unwrap_or_else(|| DbConfig::default().query_timeout());
+
+    tokio::time::timeout(timeout, call)
+        .await
+        .map_err(|_| DbError::Timeout(timeout))?
+        .map_err(DbError::from)
+}
@@ -12,7 +12,9 @@ mod connection;
 pub mod repositories;
 mod schema;
 
-pub use connection::{Database, DbConfig, DbError, get_db, init_db};
+pub use connection::{
+    Database, DbConfig, DbError, backend, connect_db, get_db, init_db, try_get_db, with_deadline,
+};
 pub use schema::init_schema;
 
 /// Initialize the database with the given configuration.
@@ -0,0 +1,91 @@
+//! Event repository for the append-only event log.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use queue_core::{JobEvent, QueueId};
+use serde::Deserialize;
+use surrealdb::sql::Datetime;
+
+use crate::{DbError, get_db, with_deadline};
+
+/// Repository for the persisted event log.
+pub struct EventRepository;
+
+/// Internal record type for reading logged events.
+#[derive(Debug, Deserialize)]
+struct EventRecord {
+    /// The event as serialized JSON.
+    event: String,
+}
+
+impl EventRepository {
+    /// Append an event to the log.
+    pub async fn append(event: &JobEvent) -> Result<(), DbError> {
+        let db = get_db()?;
+        let json =
+            serde_json::to_string(event).map_err(|e| DbError::Serialization(e.to_string()))?;
+
+        with_deadline(
+            db.query(
+                "CREATE event_log CONTENT { queue_id: $queue_id, job_id: $job_id, timestamp: $timestamp, event: $event }",
+            )
+            .bind(("queue_id", event.queue_id().map(|id| id.to_string())))
+            .bind(("job_id", event.job_id().map(|id| id.to_string())))
+            .bind(("timestamp", Datetime::from(event.timestamp())))
+            .bind(("event", json)),
+        )
+        .await?
+        .check()?;
+
+        Ok(())
+    }
+
+    /// List a queue's events at or after `since`, oldest first.
+    pub async fn list(
+        queue_id: QueueId,
+        since: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<JobEvent>, DbError> {
+        let db = get_db()?;
+
+        let mut result = with_deadline(
+            db.query(
+                "SELECT event, timestamp FROM event_log WHERE queue_id = $queue_id AND timestamp >= $since ORDER BY timestamp ASC LIMIT $limit",
+            )
+            .bind(("queue_id", queue_id.to_string()))
+            .bind(("since", Datetime::from(since)))
+            .bind(("limit", limit)),
+        )
+        .await?;
+
+        let records: Vec<EventRecord> = result.take(0)?;
+        records
+            .into_iter()
+            .map(|r| serde_json::from_str(&r.event))
+            .collect::<Result<_, _>>()
+            .map_err(|e| DbError::Serialization(e.to_string()))
+    }
+
+    /// Delete logged events older than `older_than`.
+    ///
+    /// Returns the number of events removed.
+    pub async fn prune(older_than: Duration) -> Result<u64, DbError> {
+        let db = get_db()?;
+
+        let age = chrono::Duration::from_std(older_than)
+            .map_err(|e| DbError::Query(format!("Retention window out of range: {}", e)))?;
+        let cutoff = Utc::now()
+            .checked_sub_signed(age)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+        let mut result = with_deadline(
+            db.query("DELETE event_log WHERE timestamp < $cutoff RETURN BEFORE")
+                .bind(("cutoff", Datetime::from(cutoff))),
+        )
+        .await?;
+
+        let records: Vec<EventRecord> = result.take(0)?;
+        Ok(records.len() as u64)
+    }
+}
@@ -1,12 +1,24 @@
 //! Job repository for CRUD operations.
 
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::future::ready;
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
-use queue_core::{Job, JobId, JobStatus, Priority, QueueId, QueueStats};
+use futures_util::{Stream, StreamExt};
+use queue_core::{
+    AttemptRecord, CancellationReason, Job, JobId, JobLogLine, JobRecordView, JobResult, JobSource,
+    JobStatus, Priority, QueueId, QueueStats, ThroughputBucket,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use surrealdb::sql::Thing;
+use surrealdb::engine::any::Any;
+use surrealdb::method::Query;
+use surrealdb::sql::{Datetime, Thing};
+use surrealdb::{Action, Notification};
 
-use crate::{DbError, get_db};
+use crate::{DbError, get_db, with_deadline};
 
 /// Repository for job persistence operations.
 pub struct JobRepository;
@@ -20,11 +32,25 @@ struct JobRecord {
     job_type: String,
     payload: JsonValue,
     priority: Priority,
+    #[serde(default)]
+    priority_boost: u8,
     status: JobStatus,
     attempts: u32,
     max_retries: u32,
     timeout_secs: u64,
     tags: Vec<String>,
+    #[serde(default)]
+    run_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    logs: Vec<JobLogLine>,
+    #[serde(default)]
+    attempts_log: Vec<AttemptRecord>,
+    #[serde(default)]
+    version: u64,
+    #[serde(default)]
+    tenant_id: Option<String>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -38,11 +64,18 @@ impl JobRecord {
             job_type: self.job_type,
             payload: self.payload,
             priority: self.priority,
+            priority_boost: self.priority_boost,
             status: self.status,
             attempts: self.attempts,
             max_retries: self.max_retries,
             timeout_secs: self.timeout_secs,
             tags: self.tags,
+            run_at: self.run_at,
+            deadline: self.deadline,
+            logs: self.logs,
+            attempts_log: self.attempts_log,
+            version: self.version,
+            tenant_id: self.tenant_id,
             created_at: self.created_at,
             updated_at: self.updated_at,
         }
@@ -56,11 +89,23 @@ struct JobCreate {
     job_type: String,
     payload: JsonValue,
     priority: Priority,
+    priority_boost: u8,
     status: JobStatus,
+    // Flat copy of the status discriminator for indexes and filters
+    status_str: &'static str,
     attempts: u32,
     max_retries: u32,
     timeout_secs: u64,
     tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    run_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deadline: Option<DateTime<Utc>>,
+    logs: Vec<JobLogLine>,
+    attempts_log: Vec<AttemptRecord>,
+    version: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tenant_id: Option<String>,
 }
 
 /// Job history record for archival - omits completed_at to use SurrealDB default.
@@ -69,6 +114,9 @@ pub struct JobHistoryCreate {
     pub job_id: String,
     pub queue_id: String,
     pub job_type: String,
+    // Kept so the job can be replayed from history; `None` when oversized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<JsonValue>,
     pub priority: String,
     pub final_status: String,
     pub attempts: u32,
@@ -76,11 +124,211 @@ pub struct JobHistoryCreate {
     pub error: Option<String>,
     pub result_summary: Option<String>,
     pub tags: Vec<String>,
+    pub logs: Vec<JobLogLine>,
+    pub attempts_log: Vec<AttemptRecord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
     // Note: created_at from original job is stored as ISO string for reference
     pub created_at: String,
     // completed_at uses SurrealDB DEFAULT time::now()
 }
 
+/// Internal record type for reading archived jobs from `job_history`.
+#[derive(Debug, Deserialize)]
+struct JobHistoryRecord {
+    job_id: String,
+    queue_id: String,
+    job_type: String,
+    #[serde(default)]
+    payload: Option<JsonValue>,
+    priority: String,
+    final_status: String,
+    attempts: u32,
+    duration_ms: Option<u64>,
+    error: Option<String>,
+    result_summary: Option<String>,
+    tags: Vec<String>,
+    #[serde(default)]
+    logs: Vec<JobLogLine>,
+    #[serde(default)]
+    attempts_log: Vec<AttemptRecord>,
+    #[serde(default)]
+    tenant_id: Option<String>,
+    created_at: String,
+    completed_at: DateTime<Utc>,
+}
+
+impl JobHistoryRecord {
+    fn into_view(self) -> Option<JobRecordView> {
+        let id = JobId::parse(&self.job_id).ok()?;
+        let queue_id = QueueId::parse(&self.queue_id).unwrap_or_else(|_| QueueId::new());
+        let priority = serde_json::from_value(JsonValue::String(self.priority)).unwrap_or_default();
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or(self.completed_at);
+
+        Some(JobRecordView {
+            id,
+            queue_id,
+            job_type: self.job_type,
+            priority,
+            status: self.final_status,
+            attempts: self.attempts,
+            duration_ms: self.duration_ms,
+            error: self.error,
+            result_summary: self.result_summary,
+            tags: self.tags,
+            logs: self.logs,
+            attempts_log: self.attempts_log,
+            tenant_id: self.tenant_id,
+            created_at,
+            updated_at: self.completed_at,
+            source: JobSource::History,
+        })
+    }
+
+    fn into_archived(mut self) -> Option<ArchivedJob> {
+        let payload = self.payload.take();
+        Some(ArchivedJob {
+            record: self.into_view()?,
+            payload,
+        })
+    }
+}
+
+/// An archived job read back from `job_history`.
+#[derive(Debug, Clone)]
+pub struct ArchivedJob {
+    /// The fields archived jobs share with active ones.
+    pub record: JobRecordView,
+    /// Original payload; `None` for jobs archived before payloads were kept.
+    pub payload: Option<JsonValue>,
+}
+
+impl ArchivedJob {
+    /// A fresh pending copy of the job, with a new ID, for the same queue.
+    ///
+    /// Keeps the job type, payload, priority and tags; retries and timeout
+    /// aren't archived, so they fall back to the queue's defaults on enqueue.
+    /// Returns `None` if the payload wasn't archived.
+    pub fn replay(&self) -> Option<Job> {
+        let payload = self.payload.clone()?;
+        Some(
+            Job::new(self.record.queue_id, self.record.job_type.clone(), payload)
+                .with_priority(self.record.priority)
+                .with_tags(self.record.tags.clone()),
+        )
+    }
+
+    /// The archived job as a [`Job`] for display, keeping its ID and final status.
+    ///
+    /// Start times aren't archived, so the start is derived from the duration.
+    /// Retries and timeout aren't either and come back as zero; a payload that
+    /// wasn't archived comes back as `null`.
+    pub fn into_job(self) -> Job {
+        let record = self.record;
+        let finished_at = record.updated_at;
+        let started_at = record
+            .duration_ms
+            .and_then(|ms| {
+                finished_at.checked_sub_signed(chrono::Duration::milliseconds(ms as i64))
+            })
+            .unwrap_or(finished_at);
+        let status = match record.status.as_str() {
+            "completed" => JobStatus::Completed {
+                started_at,
+                completed_at: finished_at,
+                result: JobResult::new(record.result_summary.unwrap_or_default()),
+            },
+            "cancelled" => JobStatus::Cancelled {
+                cancelled_at: finished_at,
+                reason: record.error.map(CancellationReason::from),
+            },
+            _ => JobStatus::Failed {
+                started_at,
+                failed_at: finished_at,
+                error: record.error.unwrap_or_default(),
+                attempts: record.attempts,
+            },
+        };
+
+        Job {
+            id: record.id,
+            queue_id: record.queue_id,
+            job_type: record.job_type,
+            payload: self.payload.unwrap_or(JsonValue::Null),
+            priority: record.priority,
+            priority_boost: 0,
+            status,
+            attempts: record.attempts,
+            max_retries: 0,
+            timeout_secs: 0,
+            created_at: record.created_at,
+            updated_at: finished_at,
+            tags: record.tags,
+            run_at: None,
+            deadline: None,
+            logs: record.logs,
+            attempts_log: record.attempts_log,
+            version: 0,
+            tenant_id: record.tenant_id,
+        }
+    }
+}
+
+/// One page of a queue's history from [`JobRepository::list_history_paged`].
+#[derive(Debug, Clone)]
+pub struct HistoryPage {
+    /// Archived jobs on this page, oldest ID first.
+    pub records: Vec<JobRecordView>,
+    /// Cursor for the next page; `None` once the history is exhausted.
+    pub next_cursor: Option<String>,
+}
+
+/// What [`JobRepository::find_anywhere`] should search for.
+#[derive(Debug, Clone)]
+pub enum JobLookup {
+    /// A single job by ID.
+    Id(JobId),
+    /// Every job matching a filter.
+    Filter(Box<JobFilter>),
+}
+
+impl From<JobId> for JobLookup {
+    fn from(id: JobId) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl From<JobFilter> for JobLookup {
+    fn from(filter: JobFilter) -> Self {
+        Self::Filter(Box::new(filter))
+    }
+}
+
+/// Largest serialized payload kept on an archived job, in bytes.
+///
+/// Bigger payloads are dropped from history rather than truncated, since a
+/// cut-off JSON document couldn't be replayed anyway.
+pub const MAX_ARCHIVED_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// Projection ranking a job's priority for `ORDER BY priority_rank`.
+///
+/// Priorities are stored as strings, which would sort alphabetically; the rank
+/// follows the `Priority` enum instead, lowest first.
+const PRIORITY_RANK: &str = "array::find_index(['deferred', 'low', 'normal', 'high', 'critical'], priority) AS priority_rank";
+
+/// Most buckets a single throughput query may span.
+const MAX_THROUGHPUT_BUCKETS: u128 = 10_000;
+
+/// One aggregated row of the throughput query.
+#[derive(Debug, Deserialize)]
+struct ThroughputRow {
+    bucket_start: DateTime<Utc>,
+    completed: u64,
+    failed: u64,
+}
+
 /// Filter options for listing jobs.
 #[derive(Debug, Default, Clone)]
 pub struct JobFilter {
@@ -89,10 +337,44 @@ pub struct JobFilter {
     pub job_type: Option<String>,
     pub priority: Option<Priority>,
     pub tags: Option<Vec<String>>,
+    /// Only jobs owned by this tenant.
+    pub tenant_id: Option<String>,
+    /// Only jobs this worker is running or has made an attempt at.
+    pub worker_id: Option<String>,
+    /// Only jobs created at or after this time.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only jobs created at or before this time.
+    pub created_before: Option<DateTime<Utc>>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 }
 
+/// Bind a filter's creation-time bounds as datetimes, so they compare as times
+/// rather than strings.
+fn bind_created_range<'r>(mut query: Query<'r, Any>, filter: &JobFilter) -> Query<'r, Any> {
+    if let Some(after) = filter.created_after {
+        query = query.bind(("created_after", Datetime::from(after)));
+    }
+    if let Some(before) = filter.created_before {
+        query = query.bind(("created_before", Datetime::from(before)));
+    }
+    query
+}
+
+/// Queue statistics from a queue's job counts by status.
+fn stats_from_counts(counts: &HashMap<String, u64>) -> QueueStats {
+    QueueStats {
+        pending: counts.get("pending").copied().unwrap_or(0),
+        scheduled: counts.get("scheduled").copied().unwrap_or(0),
+        running: counts.get("running").copied().unwrap_or(0),
+        completed: counts.get("completed").copied().unwrap_or(0),
+        failed: counts.get("failed").copied().unwrap_or(0),
+        avg_duration_ms: None,    // TODO: Calculate from history
+        throughput_per_min: None, // TODO: Calculate from history
+        ..Default::default()
+    }
+}
+
 #[allow(clippy::result_large_err)]
 fn to_json<T: Serialize>(value: T) -> Result<serde_json::Value, DbError> {
     serde_json::to_value(value).map_err(|e| DbError::Serialization(e.to_string()))
@@ -109,17 +391,23 @@ impl JobRepository {
             job_type: job.job_type.clone(),
             payload: job.payload.clone(),
             priority: job.priority,
+            priority_boost: job.priority_boost,
             status: job.status.clone(),
+            status_str: job.status.as_str(),
             attempts: job.attempts,
             max_retries: job.max_retries,
             timeout_secs: job.timeout_secs,
             tags: job.tags.clone(),
+            run_at: job.run_at,
+            deadline: job.deadline,
+            logs: job.logs.clone(),
+            attempts_log: job.attempts_log.clone(),
+            version: job.version,
+            tenant_id: job.tenant_id.clone(),
         };
 
-        let record: Option<JobRecord> = db
-            .create(("job", job.id.to_string()))
-            .content(create_data)
-            .await?;
+        let record: Option<JobRecord> =
+            with_deadline(db.create(("job", job.id.to_string())).content(create_data)).await?;
 
         record
             .map(|r| r.into_job(job.id))
@@ -130,7 +418,7 @@ impl JobRepository {
     pub async fn get(id: JobId) -> Result<Job, DbError> {
         let db = get_db()?;
 
-        let record: Option<JobRecord> = db.select(("job", id.to_string())).await?;
+        let record: Option<JobRecord> = with_deadline(db.select(("job", id.to_string()))).await?;
 
         record
             .map(|r| r.into_job(id))
@@ -150,7 +438,7 @@ impl JobRepository {
         }
 
         if let Some(status) = &filter.status {
-            conditions.push("status.status = $status");
+            conditions.push("status_str = $status");
             bindings.push(("status", to_json(status)?));
         }
 
@@ -164,6 +452,25 @@ impl JobRepository {
             bindings.push(("priority", to_json(priority.to_string())?));
         }
 
+        if let Some(tenant_id) = &filter.tenant_id {
+            conditions.push("tenant_id = $tenant_id");
+            bindings.push(("tenant_id", to_json(tenant_id)?));
+        }
+
+        if let Some(worker_id) = &filter.worker_id {
+            conditions
+                .push("(status.worker_id = $worker_id OR $worker_id IN attempts_log.worker_id)");
+            bindings.push(("worker_id", to_json(worker_id)?));
+        }
+
+        if filter.created_after.is_some() {
+            conditions.push("created_at >= $created_after");
+        }
+
+        if filter.created_before.is_some() {
+            conditions.push("created_at <= $created_before");
+        }
+
         let where_clause = if conditions.is_empty() {
             String::new()
         } else {
@@ -181,8 +488,8 @@ impl JobRepository {
             .unwrap_or_default();
 
         let query = format!(
-            "SELECT * FROM job {} ORDER BY priority DESC, created_at ASC {} {}",
-            where_clause, limit_clause, offset_clause
+            "SELECT *, {} FROM job {} ORDER BY priority_rank DESC, created_at ASC {} {}",
+            PRIORITY_RANK, where_clause, limit_clause, offset_clause
         );
 
         let mut result = db.query(&query);
@@ -190,8 +497,9 @@ impl JobRepository {
         for (name, value) in bindings {
             result = result.bind((name, value));
         }
+        result = bind_created_range(result, &filter);
 
-        let mut response = result.await?;
+        let mut response = with_deadline(result).await?;
         let records: Vec<JobRecord> = response.take(0)?;
 
         Ok(records
@@ -204,25 +512,28 @@ impl JobRepository {
             .collect())
     }
 
-    /// Get pending jobs for a queue, ordered by priority and creation time.
+    /// Get waiting jobs for a queue, ordered by priority and creation time.
+    ///
+    /// Includes scheduled jobs, which are waiting on their `run_at` rather than a worker.
     pub async fn get_pending_for_queue(
         queue_id: QueueId,
         limit: usize,
     ) -> Result<Vec<Job>, DbError> {
         let db = get_db()?;
 
-        let mut result = db
-            .query(
+        let mut result = with_deadline(
+            db.query(format!(
                 r#"
-                SELECT * FROM job
-                WHERE queue_id = $queue_id AND status.status = "pending"
-                ORDER BY priority DESC, created_at ASC
+                SELECT *, {PRIORITY_RANK} FROM job
+                WHERE queue_id = $queue_id AND status_str IN ["pending", "scheduled"]
+                ORDER BY priority_rank DESC, created_at ASC
                 LIMIT $limit
-                "#,
-            )
+                "#
+            ))
             .bind(("queue_id", queue_id.to_string()))
-            .bind(("limit", limit as i64))
-            .await?;
+            .bind(("limit", limit as i64)),
+        )
+        .await?;
 
         let records: Vec<JobRecord> = result.take(0)?;
 
@@ -236,73 +547,177 @@ impl JobRepository {
             .collect())
     }
 
+    /// Explain why a versioned update matched no row.
+    async fn rejected_update(id: JobId, expected_version: u64) -> DbError {
+        match Self::get(id).await {
+            Ok(current) => DbError::Conflict(format!(
+                "Job {} was modified concurrently (expected version {}, found {})",
+                id, expected_version, current.version
+            )),
+            Err(e) => e,
+        }
+    }
+
     /// Update a job's status and attempts.
+    ///
+    /// Only applies if the stored job is still at `expected_version`; otherwise
+    /// returns [`DbError::Conflict`]. The returned job carries the new version.
     pub async fn update_status(
         id: JobId,
         status: &JobStatus,
         attempts: u32,
+        expected_version: u64,
     ) -> Result<Job, DbError> {
         let db = get_db()?;
         let status_clone = status.clone();
 
         // Use SurrealQL to set updated_at with time::now()
-        let mut result = db
+        let mut result = with_deadline(db
             .query(
-                "UPDATE type::thing('job', $id) SET status = $status, attempts = $attempts, updated_at = time::now() RETURN AFTER",
+                "UPDATE type::thing('job', $id) SET status = $status, status_str = $status_str, attempts = $attempts, version = (version ?? 0) + 1, updated_at = time::now() WHERE (version ?? 0) = $version RETURN AFTER",
             )
             .bind(("id", id.to_string()))
+            .bind(("status_str", status.as_str()))
             .bind(("status", status_clone))
             .bind(("attempts", attempts))
-            .await?;
+            .bind(("version", expected_version))).await?;
 
         let records: Vec<JobRecord> = result.take(0)?;
 
-        records
-            .into_iter()
-            .next()
-            .map(|r| r.into_job(id))
-            .ok_or_else(|| DbError::NotFound(format!("Job not found: {}", id)))
+        match records.into_iter().next() {
+            Some(record) => Ok(record.into_job(id)),
+            None => Err(Self::rejected_update(id, expected_version).await),
+        }
+    }
+
+    /// Update a job's status, attempts, attempt log and priority boost.
+    ///
+    /// Versioned like [`Self::update_status`]; used as a job starts and ends runs.
+    pub async fn update_attempts(job: &Job) -> Result<Job, DbError> {
+        let db = get_db()?;
+
+        let mut result = with_deadline(db
+            .query(
+                "UPDATE type::thing('job', $id) SET priority_boost = $priority_boost, status = $status, status_str = $status_str, attempts = $attempts, attempts_log = $attempts_log, version = (version ?? 0) + 1, updated_at = time::now() WHERE (version ?? 0) = $version RETURN AFTER",
+            )
+            .bind(("id", job.id.to_string()))
+            .bind(("priority_boost", job.priority_boost))
+            .bind(("status", job.status.clone()))
+            .bind(("status_str", job.status.as_str()))
+            .bind(("attempts", job.attempts))
+            .bind(("attempts_log", job.attempts_log.clone()))
+            .bind(("version", job.version))).await?;
+
+        let records: Vec<JobRecord> = result.take(0)?;
+
+        match records.into_iter().next() {
+            Some(record) => Ok(record.into_job(job.id)),
+            None => Err(Self::rejected_update(job.id, job.version).await),
+        }
+    }
+
+    /// Stream every change made to a job from now on.
+    ///
+    /// Backed by a SurrealDB `LIVE SELECT`, so updates are pushed rather than
+    /// polled. This works on the embedded engines (including `mem://`) and over
+    /// WebSocket, but not over HTTP, which has no notification channel. The
+    /// stream ends once the job is deleted (e.g. archived), and dropping it
+    /// kills the live query.
+    pub async fn watch(id: JobId) -> Result<impl Stream<Item = Job> + Send, DbError> {
+        let db = get_db()?;
+
+        // Live query conditions don't see bound parameters, so inline the record ID
+        let record = Thing::from(("job", id.to_string().as_str()));
+        let mut response =
+            with_deadline(db.query(format!("LIVE SELECT * FROM job WHERE id = {}", record)))
+                .await?;
+
+        let notifications = response.stream::<Notification<JobRecord>>(0)?;
+
+        Ok(notifications
+            .take_while(|notification| {
+                ready(!matches!(
+                    notification,
+                    Ok(Notification {
+                        action: Action::Delete,
+                        ..
+                    })
+                ))
+            })
+            .filter_map(move |notification| {
+                ready(match notification {
+                    Ok(notification) => Some(notification.data.into_job(id)),
+                    Err(e) => {
+                        tracing::warn!("Dropped change notification for job {}: {}", id, e);
+                        None
+                    }
+                })
+            }))
+    }
+
+    /// Append handler log lines to a job.
+    pub async fn append_logs(id: JobId, logs: &[JobLogLine]) -> Result<(), DbError> {
+        let db = get_db()?;
+
+        with_deadline(
+            db.query("UPDATE type::thing('job', $id) SET logs = array::concat(logs ?? [], $logs)")
+                .bind(("id", id.to_string()))
+                .bind(("logs", logs.to_vec())),
+        )
+        .await?;
+
+        Ok(())
     }
 
     /// Update a job.
+    ///
+    /// Only applies if the stored job is still at `job.version`; otherwise
+    /// returns [`DbError::Conflict`]. The returned job carries the new version.
     pub async fn update(job: &Job) -> Result<Job, DbError> {
         let db = get_db()?;
 
-        let mut result = db
+        let mut result = with_deadline(db
             .query(
-                "UPDATE type::thing('job', $id) SET queue_id = $queue_id, job_type = $job_type, payload = $payload, priority = $priority, status = $status, attempts = $attempts, max_retries = $max_retries, timeout_secs = $timeout_secs, tags = $tags, updated_at = time::now() RETURN AFTER",
+                "UPDATE type::thing('job', $id) SET queue_id = $queue_id, job_type = $job_type, payload = $payload, priority = $priority, priority_boost = $priority_boost, status = $status, status_str = $status_str, attempts = $attempts, max_retries = $max_retries, timeout_secs = $timeout_secs, tags = $tags, run_at = $run_at, deadline = $deadline, logs = $logs, attempts_log = $attempts_log, version = (version ?? 0) + 1, updated_at = time::now() WHERE (version ?? 0) = $version RETURN AFTER",
             )
             .bind(("id", job.id.to_string()))
             .bind(("queue_id", job.queue_id.to_string()))
             .bind(("job_type", job.job_type.clone()))
             .bind(("payload", job.payload.clone()))
             .bind(("priority", job.priority))
+            .bind(("priority_boost", job.priority_boost))
             .bind(("status", job.status.clone()))
+            .bind(("status_str", job.status.as_str()))
             .bind(("attempts", job.attempts))
             .bind(("max_retries", job.max_retries))
             .bind(("timeout_secs", job.timeout_secs))
             .bind(("tags", job.tags.clone()))
-            .await?;
+            .bind(("run_at", job.run_at))
+            .bind(("deadline", job.deadline))
+            .bind(("logs", job.logs.clone()))
+            .bind(("attempts_log", job.attempts_log.clone()))
+            .bind(("version", job.version))).await?;
 
         let records: Vec<JobRecord> = result.take(0)?;
 
-        records
-            .into_iter()
-            .next()
-            .map(|r| r.into_job(job.id))
-            .ok_or_else(|| DbError::NotFound(format!("Job not found: {}", job.id)))
+        match records.into_iter().next() {
+            Some(record) => Ok(record.into_job(job.id)),
+            None => Err(Self::rejected_update(job.id, job.version).await),
+        }
     }
 
     /// Delete a job.
     pub async fn delete(id: JobId) -> Result<(), DbError> {
         let db = get_db()?;
 
-        let _: Option<JobRecord> = db.delete(("job", id.to_string())).await?;
+        let _: Option<JobRecord> = with_deadline(db.delete(("job", id.to_string()))).await?;
 
         Ok(())
     }
 
     /// Archive a completed/failed job to history and delete from active jobs.
+    ///
+    /// Both happen in one transaction: if it fails, the active job is left as it was.
     pub async fn archive(job: &Job) -> Result<(), DbError> {
         let db = get_db()?;
 
@@ -313,7 +728,13 @@ impl JobRepository {
                 completed_at,
                 result,
             } => {
-                let duration = (*completed_at - *started_at).num_milliseconds() as u64;
+                if completed_at < started_at {
+                    tracing::warn!(
+                        "Job {} completed before it started (clock skew); clamping duration to 0",
+                        job.id
+                    );
+                }
+                let duration = queue_core::duration_ms(*started_at, *completed_at);
                 (
                     "completed",
                     job.attempts.max(1),
@@ -325,16 +746,34 @@ impl JobRepository {
             JobStatus::Failed { error, .. } => {
                 ("failed", job.attempts, None, Some(error.clone()), None)
             }
-            JobStatus::Cancelled { reason, .. } => {
-                ("cancelled", job.attempts, None, reason.clone(), None)
-            }
+            JobStatus::Cancelled { reason, .. } => (
+                "cancelled",
+                job.attempts,
+                None,
+                reason.as_ref().map(|r| r.to_string()),
+                None,
+            ),
             _ => return Ok(()), // Don't archive non-terminal jobs
         };
 
+        let payload_size = serde_json::to_vec(&job.payload).map_or(usize::MAX, |b| b.len());
+        let payload = if payload_size <= MAX_ARCHIVED_PAYLOAD_BYTES {
+            Some(job.payload.clone())
+        } else {
+            tracing::warn!(
+                "Job {} payload is {} bytes (limit {}); archiving without it",
+                job.id,
+                payload_size,
+                MAX_ARCHIVED_PAYLOAD_BYTES
+            );
+            None
+        };
+
         let history = JobHistoryCreate {
             job_id: job.id.to_string(),
             queue_id: job.queue_id.to_string(),
             job_type: job.job_type.clone(),
+            payload,
             priority: job.priority.to_string(),
             final_status: final_status.to_string(),
             attempts,
@@ -342,37 +781,374 @@ impl JobRepository {
             error,
             result_summary,
             tags: job.tags.clone(),
+            logs: job.logs.clone(),
+            attempts_log: job.attempts_log.clone(),
+            tenant_id: job.tenant_id.clone(),
             created_at: job.created_at.to_rfc3339(),
         };
 
-        // Create history record
-        db.query("CREATE job_history CONTENT $data")
+        // Create the history record and drop the active job together, so a
+        // crash between the two can't lose the job
+        with_deadline(
+            db.query(
+                r#"
+                BEGIN TRANSACTION;
+                CREATE job_history CONTENT $data;
+                DELETE type::thing('job', $id);
+                COMMIT TRANSACTION;
+                "#,
+            )
             .bind(("data", history))
-            .await?;
-
-        // Delete active job
-        Self::delete(job.id).await?;
+            .bind(("id", job.id.to_string())),
+        )
+        .await?
+        .check()
+        .map_err(|e| DbError::Query(format!("Failed to archive job {}: {}", job.id, e)))?;
 
         Ok(())
     }
 
+    /// Get an archived job by the ID it had while active.
+    pub async fn get_history(id: JobId) -> Result<ArchivedJob, DbError> {
+        let db = get_db()?;
+
+        let mut response = with_deadline(
+            db.query("SELECT * FROM job_history WHERE job_id = $job_id LIMIT 1")
+                .bind(("job_id", id.to_string())),
+        )
+        .await?;
+        let records: Vec<JobHistoryRecord> = response.take(0)?;
+
+        records
+            .into_iter()
+            .next()
+            .and_then(JobHistoryRecord::into_archived)
+            .ok_or_else(|| DbError::NotFound(format!("Archived job not found: {}", id)))
+    }
+
+    /// Get archived failures of a job type in a queue that kept their payload,
+    /// oldest first.
+    pub async fn failed_history(
+        queue_id: QueueId,
+        job_type: &str,
+    ) -> Result<Vec<ArchivedJob>, DbError> {
+        let db = get_db()?;
+
+        let mut response = with_deadline(
+            db.query(
+                r#"
+                SELECT * FROM job_history
+                WHERE queue_id = $queue_id AND job_type = $job_type
+                    AND final_status = "failed" AND payload != NONE
+                ORDER BY completed_at ASC
+                "#,
+            )
+            .bind(("queue_id", queue_id.to_string()))
+            .bind(("job_type", job_type.to_string())),
+        )
+        .await?;
+        let records: Vec<JobHistoryRecord> = response.take(0)?;
+
+        Ok(records
+            .into_iter()
+            .filter_map(JobHistoryRecord::into_archived)
+            .collect())
+    }
+
+    /// Page through a queue's archived jobs in ID order, oldest first.
+    ///
+    /// Job IDs sort chronologically, so `after` (the previous page's
+    /// `next_cursor`) is an ID to resume past; `None` starts at the
+    /// beginning. Unlike offset paging, this stays cheap deep into history
+    /// and doesn't skip or repeat rows archived between calls.
+    pub async fn list_history_paged(
+        queue_id: QueueId,
+        after: Option<String>,
+        limit: usize,
+    ) -> Result<HistoryPage, DbError> {
+        let db = get_db()?;
+
+        if limit == 0 {
+            return Err(DbError::Query("Page size must be at least 1".into()));
+        }
+        let after = match after {
+            Some(cursor) => Some(
+                JobId::parse(&cursor)
+                    .map_err(|e| {
+                        DbError::Query(format!("Invalid history cursor {:?}: {}", cursor, e))
+                    })?
+                    .to_string(),
+            ),
+            None => None,
+        };
+        let condition = if after.is_some() {
+            "queue_id = $queue_id AND job_id > $after"
+        } else {
+            "queue_id = $queue_id"
+        };
+
+        // One extra row tells us whether another page follows
+        let query = format!(
+            "SELECT * FROM job_history WHERE {} ORDER BY job_id ASC LIMIT {}",
+            condition,
+            limit + 1
+        );
+        let mut response = with_deadline(
+            db.query(&query)
+                .bind(("queue_id", queue_id.to_string()))
+                .bind(("after", after)),
+        )
+        .await?;
+        let records: Vec<JobHistoryRecord> = response.take(0)?;
+
+        let has_more = records.len() > limit;
+        let records: Vec<JobRecordView> = records
+            .into_iter()
+            .take(limit)
+            .filter_map(JobHistoryRecord::into_view)
+            .collect();
+        let next_cursor = if has_more {
+            records.last().map(|view| view.id.to_string())
+        } else {
+            None
+        };
+
+        Ok(HistoryPage {
+            records,
+            next_cursor,
+        })
+    }
+
+    /// Delete archived jobs that finished more than `older_than` ago.
+    ///
+    /// Returns the number of history rows removed.
+    pub async fn prune_history(older_than: Duration) -> Result<u64, DbError> {
+        let db = get_db()?;
+
+        let age = chrono::Duration::from_std(older_than)
+            .map_err(|e| DbError::Query(format!("Retention window out of range: {}", e)))?;
+        let cutoff = Utc::now()
+            .checked_sub_signed(age)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+        let mut result = with_deadline(
+            db.query("DELETE job_history WHERE completed_at < $cutoff RETURN BEFORE")
+                .bind(("cutoff", Datetime::from(cutoff))),
+        )
+        .await?;
+
+        let records: Vec<JobHistoryRecord> = result.take(0)?;
+        Ok(records.len() as u64)
+    }
+
+    /// Count a queue's archived jobs per time bucket over the trailing `window`.
+    ///
+    /// Buckets are `bucket` long, aligned to the Unix epoch, and end with the
+    /// one containing now. Every bucket in the window is returned, oldest
+    /// first, so buckets (or whole queues) without history come back as zeros.
+    pub async fn throughput_buckets(
+        queue_id: QueueId,
+        bucket: Duration,
+        window: Duration,
+    ) -> Result<Vec<ThroughputBucket>, DbError> {
+        let db = get_db()?;
+
+        let bucket_ms = i64::try_from(bucket.as_millis())
+            .ok()
+            .filter(|ms| *ms > 0)
+            .ok_or_else(|| DbError::Query("Bucket size must be at least 1ms".into()))?;
+        let count = window.as_millis().div_ceil(bucket.as_millis()).max(1);
+        if count > MAX_THROUGHPUT_BUCKETS {
+            return Err(DbError::Query(format!(
+                "Window spans {} buckets; at most {} are allowed",
+                count, MAX_THROUGHPUT_BUCKETS
+            )));
+        }
+        let count = count as i64;
+
+        let now_ms = Utc::now().timestamp_millis();
+        let last_start = now_ms - now_ms.rem_euclid(bucket_ms);
+        let first_start = last_start - (count - 1) * bucket_ms;
+        let since = DateTime::<Utc>::from_timestamp_millis(first_start)
+            .ok_or_else(|| DbError::Query("Throughput window out of range".into()))?;
+
+        let mut result = with_deadline(db
+            .query(
+                "SELECT time::floor(completed_at, $bucket) AS bucket_start, count(final_status = 'completed') AS completed, count(final_status = 'failed') AS failed FROM job_history WHERE queue_id = $queue_id AND completed_at >= $since GROUP BY bucket_start",
+            )
+            .bind(("queue_id", queue_id.to_string()))
+            .bind(("bucket", surrealdb::sql::Duration::from(bucket)))
+            .bind(("since", Datetime::from(since)))).await?;
+
+        let rows: Vec<ThroughputRow> = result.take(0)?;
+        let counts: HashMap<i64, ThroughputRow> = rows
+            .into_iter()
+            .map(|row| (row.bucket_start.timestamp_millis(), row))
+            .collect();
+
+        Ok((0..count)
+            .filter_map(|i| {
+                let start = first_start + i * bucket_ms;
+                let row = counts.get(&start);
+                Some(ThroughputBucket {
+                    bucket_start: DateTime::<Utc>::from_timestamp_millis(start)?,
+                    completed: row.map_or(0, |row| row.completed),
+                    failed: row.map_or(0, |row| row.failed),
+                })
+            })
+            .collect())
+    }
+
+    /// Search active jobs and job history together.
+    ///
+    /// An ID lookup checks the active `job` table first and falls back to
+    /// `job_history`. A filter lookup merges both tables newest first, with
+    /// `limit`/`offset` applied to the merged result. `status` matches the
+    /// active status or the archived `final_status`.
+    pub async fn find_anywhere(
+        lookup: impl Into<JobLookup>,
+    ) -> Result<Vec<JobRecordView>, DbError> {
+        match lookup.into() {
+            JobLookup::Id(id) => match Self::get(id).await {
+                Ok(job) => Ok(vec![job.into()]),
+                Err(DbError::NotFound(_)) => {
+                    let db = get_db()?;
+                    let mut response = with_deadline(
+                        db.query("SELECT * FROM job_history WHERE job_id = $job_id LIMIT 1")
+                            .bind(("job_id", id.to_string())),
+                    )
+                    .await?;
+                    let records: Vec<JobHistoryRecord> = response.take(0)?;
+                    Ok(records
+                        .into_iter()
+                        .filter_map(JobHistoryRecord::into_view)
+                        .collect())
+                }
+                Err(e) => Err(e),
+            },
+            JobLookup::Filter(filter) => {
+                let window = filter.limit.map(|l| l + filter.offset.unwrap_or(0));
+
+                let active = Self::list(JobFilter {
+                    limit: window,
+                    offset: None,
+                    ..(*filter).clone()
+                })
+                .await?;
+                let archived = Self::list_history(&filter, window).await?;
+
+                let mut views: Vec<JobRecordView> = active
+                    .into_iter()
+                    .map(JobRecordView::from)
+                    .chain(archived)
+                    .collect();
+                views.sort_by_key(|view| Reverse(view.created_at));
+
+                Ok(views
+                    .into_iter()
+                    .skip(filter.offset.unwrap_or(0))
+                    .take(filter.limit.unwrap_or(usize::MAX))
+                    .collect())
+            }
+        }
+    }
+
+    /// List archived jobs matching a filter, newest first.
+    async fn list_history(
+        filter: &JobFilter,
+        limit: Option<usize>,
+    ) -> Result<Vec<JobRecordView>, DbError> {
+        let db = get_db()?;
+
+        let mut conditions = Vec::new();
+        let mut bindings: Vec<(&str, serde_json::Value)> = Vec::new();
+
+        if let Some(queue_id) = &filter.queue_id {
+            conditions.push("queue_id = $queue_id");
+            bindings.push(("queue_id", to_json(queue_id.to_string())?));
+        }
+
+        if let Some(status) = &filter.status {
+            conditions.push("final_status = $status");
+            bindings.push(("status", to_json(status)?));
+        }
+
+        if let Some(job_type) = &filter.job_type {
+            conditions.push("job_type = $job_type");
+            bindings.push(("job_type", to_json(job_type)?));
+        }
+
+        if let Some(priority) = &filter.priority {
+            conditions.push("priority = $priority");
+            bindings.push(("priority", to_json(priority.to_string())?));
+        }
+
+        if let Some(tenant_id) = &filter.tenant_id {
+            conditions.push("tenant_id = $tenant_id");
+            bindings.push(("tenant_id", to_json(tenant_id)?));
+        }
+
+        if let Some(worker_id) = &filter.worker_id {
+            conditions.push("$worker_id IN attempts_log.worker_id");
+            bindings.push(("worker_id", to_json(worker_id)?));
+        }
+
+        // History keeps `created_at` as an RFC 3339 string
+        if filter.created_after.is_some() {
+            conditions.push("<datetime> created_at >= $created_after");
+        }
+
+        if filter.created_before.is_some() {
+            conditions.push("<datetime> created_at <= $created_before");
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let limit_clause = limit.map(|l| format!("LIMIT {}", l)).unwrap_or_default();
+
+        let query = format!(
+            "SELECT * FROM job_history {} ORDER BY completed_at DESC {}",
+            where_clause, limit_clause
+        );
+
+        let mut result = db.query(&query);
+
+        for (name, value) in bindings {
+            result = result.bind((name, value));
+        }
+        result = bind_created_range(result, filter);
+
+        let mut response = with_deadline(result).await?;
+        let records: Vec<JobHistoryRecord> = response.take(0)?;
+
+        Ok(records
+            .into_iter()
+            .filter_map(JobHistoryRecord::into_view)
+            .collect())
+    }
+
     /// Count jobs by status for a queue.
     pub async fn count_by_status(
         queue_id: QueueId,
     ) -> Result<std::collections::HashMap<String, u64>, DbError> {
         let db = get_db()?;
 
-        let mut result = db
-            .query(
+        let mut result = with_deadline(
+            db.query(
                 r#"
-                SELECT status.status AS status_value, count() as count
+                SELECT status_str AS status_value, count() as count
                 FROM job
                 WHERE queue_id = $queue_id
                 GROUP BY status_value
                 "#,
             )
-            .bind(("queue_id", queue_id.to_string()))
-            .await?;
+            .bind(("queue_id", queue_id.to_string())),
+        )
+        .await?;
 
         #[derive(Deserialize)]
         struct StatusCount {
@@ -392,18 +1168,58 @@ impl JobRepository {
         Ok(map)
     }
 
+    /// Count jobs by status for every queue in one aggregate query.
+    ///
+    /// Keyed by queue, then status; queues without any jobs are absent.
+    pub async fn count_by_status_all_queues()
+    -> Result<HashMap<QueueId, HashMap<String, u64>>, DbError> {
+        let db = get_db()?;
+
+        let mut result = with_deadline(db.query(
+            r#"
+                SELECT queue_id, status_str AS status_value, count() as count
+                FROM job
+                GROUP BY queue_id, status_value
+                "#,
+        ))
+        .await?;
+
+        #[derive(Deserialize)]
+        struct QueueStatusCount {
+            queue_id: String,
+            status_value: Option<String>,
+            count: i64,
+        }
+
+        let counts: Vec<QueueStatusCount> = result.take(0)?;
+
+        let mut map: HashMap<QueueId, HashMap<String, u64>> = HashMap::new();
+        for count in counts {
+            if let (Ok(queue_id), Some(status)) =
+                (QueueId::parse(&count.queue_id), count.status_value)
+            {
+                map.entry(queue_id)
+                    .or_default()
+                    .insert(status, count.count as u64);
+            }
+        }
+
+        Ok(map)
+    }
+
     /// Get queue statistics from job counts.
     pub async fn get_queue_stats(queue_id: QueueId) -> Result<QueueStats, DbError> {
         let counts = Self::count_by_status(queue_id).await?;
+        Ok(stats_from_counts(&counts))
+    }
 
-        Ok(QueueStats {
-            pending: counts.get("pending").copied().unwrap_or(0),
-            running: counts.get("running").copied().unwrap_or(0),
-            completed: counts.get("completed").copied().unwrap_or(0),
-            failed: counts.get("failed").copied().unwrap_or(0),
-            avg_duration_ms: None,    // TODO: Calculate from history
-            throughput_per_min: None, // TODO: Calculate from history
-        })
+    /// Get statistics for every queue with jobs, from a single aggregate query.
+    pub async fn get_all_queue_stats() -> Result<HashMap<QueueId, QueueStats>, DbError> {
+        Ok(Self::count_by_status_all_queues()
+            .await?
+            .into_iter()
+            .map(|(queue_id, counts)| (queue_id, stats_from_counts(&counts)))
+            .collect())
     }
 
     /// Reset all "running" jobs for a queue to "pending" status.
@@ -411,41 +1227,79 @@ impl JobRepository {
     /// This is used during server restart to recover jobs that were interrupted
     /// when the server crashed or was stopped. Jobs in "running" status at restart
     /// time are stale - there's no worker processing them anymore.
-    pub async fn reset_running_to_pending_for_queue(queue_id: QueueId) -> Result<u64, DbError> {
+    ///
+    /// Returns the reset jobs as they were before the reset, so callers can see
+    /// which worker each one was taken from.
+    pub async fn reset_running_to_pending_for_queue(
+        queue_id: QueueId,
+    ) -> Result<Vec<Job>, DbError> {
         let db = get_db()?;
 
-        let mut result = db
-            .query(
+        let mut result = with_deadline(
+            db.query(
                 r#"
                 UPDATE job
-                SET status = { status: "pending" }, updated_at = time::now()
-                WHERE queue_id = $queue_id AND status.status = "running"
-                RETURN AFTER
+                SET status = { status: "pending" }, status_str = "pending", version = (version ?? 0) + 1,
+                    updated_at = time::now()
+                WHERE queue_id = $queue_id AND status_str = "running"
+                RETURN BEFORE
                 "#,
             )
-            .bind(("queue_id", queue_id.to_string()))
-            .await?;
+            .bind(("queue_id", queue_id.to_string())),
+        )
+        .await?;
+
+        let records: Vec<JobRecord> = result.take(0)?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| {
+                let id_str = r.id.as_ref().map(|t| t.id.to_raw()).unwrap_or_default();
+                let job_id = JobId::parse(&id_str).unwrap_or_else(|_| JobId::new());
+                r.into_job(job_id)
+            })
+            .collect())
+    }
+
+    /// Delete every pending or scheduled job for a queue without archiving it.
+    ///
+    /// Returns the number of rows removed. Running jobs are left untouched.
+    pub async fn delete_pending_for_queue(queue_id: QueueId) -> Result<u64, DbError> {
+        let db = get_db()?;
+
+        let mut result = with_deadline(
+            db.query(
+                r#"
+                DELETE job
+                WHERE queue_id = $queue_id AND status_str IN ["pending", "scheduled"]
+                RETURN BEFORE
+                "#,
+            )
+            .bind(("queue_id", queue_id.to_string())),
+        )
+        .await?;
 
         let records: Vec<JobRecord> = result.take(0)?;
         Ok(records.len() as u64)
     }
 
-    /// Get all non-terminal jobs for a queue (pending + running).
+    /// Get all non-terminal jobs for a queue (pending + scheduled + running).
     ///
     /// Used during rehydration to load the full working set of jobs into memory.
     pub async fn get_active_for_queue(queue_id: QueueId) -> Result<Vec<Job>, DbError> {
         let db = get_db()?;
 
-        let mut result = db
-            .query(
+        let mut result = with_deadline(
+            db.query(format!(
                 r#"
-                SELECT * FROM job
-                WHERE queue_id = $queue_id AND (status.status = "pending" OR status.status = "running")
-                ORDER BY priority DESC, created_at ASC
-                "#,
-            )
-            .bind(("queue_id", queue_id.to_string()))
-            .await?;
+                SELECT *, {PRIORITY_RANK} FROM job
+                WHERE queue_id = $queue_id AND status_str IN ["pending", "scheduled", "running"]
+                ORDER BY priority_rank DESC, created_at ASC
+                "#
+            ))
+            .bind(("queue_id", queue_id.to_string())),
+        )
+        .await?;
 
         let records: Vec<JobRecord> = result.take(0)?;
 
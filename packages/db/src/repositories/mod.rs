@@ -1,7 +1,11 @@
 //! Repository implementations for database operations.
 
+mod event_repo;
 mod job_repo;
 mod queue_repo;
 
-pub use job_repo::{JobFilter, JobRepository};
-pub use queue_repo::QueueRepository;
+pub use event_repo::EventRepository;
+pub use job_repo::{
+    ArchivedJob, HistoryPage, JobFilter, JobLookup, JobRepository, MAX_ARCHIVED_PAYLOAD_BYTES,
+};
+pub use queue_repo::{QueueRepository, QueueStatsSnapshot};
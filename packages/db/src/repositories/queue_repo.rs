@@ -3,9 +3,9 @@
 use chrono::{DateTime, Utc};
 use queue_core::{Queue, QueueConfig, QueueId, QueueState, QueueStats};
 use serde::{Deserialize, Serialize};
-use surrealdb::sql::Thing;
+use surrealdb::sql::{Datetime, Thing};
 
-use crate::{DbError, get_db};
+use crate::{DbError, get_db, with_deadline};
 
 /// Repository for queue persistence operations.
 pub struct QueueRepository;
@@ -19,9 +19,13 @@ struct QueueRecord {
     description: Option<String>,
     state: QueueState,
     config: QueueConfig,
+    #[serde(default)]
+    tags: Vec<String>,
     stats: QueueStats,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    #[serde(default)]
+    tenant_id: Option<String>,
 }
 
 impl QueueRecord {
@@ -32,9 +36,11 @@ impl QueueRecord {
             description: self.description,
             state: self.state,
             config: self.config,
+            tags: self.tags,
             stats: self.stats,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            tenant_id: self.tenant_id,
         }
     }
 }
@@ -46,7 +52,19 @@ struct QueueCreate {
     description: Option<String>,
     state: QueueState,
     config: QueueConfig,
+    tags: Vec<String>,
     stats: QueueStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tenant_id: Option<String>,
+}
+
+/// Snapshot of a queue's stats for one rolled-over reporting window.
+#[derive(Debug, Clone, Deserialize)]
+pub struct QueueStatsSnapshot {
+    pub queue_id: QueueId,
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    pub stats: QueueStats,
 }
 
 impl QueueRepository {
@@ -61,11 +79,13 @@ impl QueueRepository {
             description: queue.description.clone(),
             state: queue.state,
             config: queue.config.clone(),
+            tags: queue.tags.clone(),
             stats: queue.stats.clone(),
+            tenant_id: queue.tenant_id.clone(),
         };
 
         let record: Option<QueueRecord> =
-            db.create(("queue", &queue_id)).content(create_data).await?;
+            with_deadline(db.create(("queue", &queue_id)).content(create_data)).await?;
 
         record
             .map(|r| r.into_queue(queue.id))
@@ -76,7 +96,8 @@ impl QueueRepository {
     pub async fn get(id: QueueId) -> Result<Queue, DbError> {
         let db = get_db()?;
 
-        let record: Option<QueueRecord> = db.select(("queue", id.to_string())).await?;
+        let record: Option<QueueRecord> =
+            with_deadline(db.select(("queue", id.to_string()))).await?;
 
         record
             .map(|r| r.into_queue(id))
@@ -88,10 +109,11 @@ impl QueueRepository {
         let db = get_db()?;
         let name_owned = name.to_string();
 
-        let mut result = db
-            .query("SELECT * FROM queue WHERE name = $name LIMIT 1")
-            .bind(("name", name_owned))
-            .await?;
+        let mut result = with_deadline(
+            db.query("SELECT * FROM queue WHERE name = $name LIMIT 1")
+                .bind(("name", name_owned)),
+        )
+        .await?;
 
         let records: Vec<QueueRecord> = result.take(0)?;
 
@@ -111,7 +133,29 @@ impl QueueRepository {
     pub async fn list() -> Result<Vec<Queue>, DbError> {
         let db = get_db()?;
 
-        let records: Vec<QueueRecord> = db.select("queue").await?;
+        let records: Vec<QueueRecord> = with_deadline(db.select("queue")).await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| {
+                let id_str = r.id.as_ref().map(|t| t.id.to_raw()).unwrap_or_default();
+                let queue_id = QueueId::parse(&id_str).unwrap_or_else(|_| QueueId::new());
+                r.into_queue(queue_id)
+            })
+            .collect())
+    }
+
+    /// List the queues owned by a tenant.
+    pub async fn list_for_tenant(tenant_id: &str) -> Result<Vec<Queue>, DbError> {
+        let db = get_db()?;
+
+        let mut result = with_deadline(
+            db.query("SELECT * FROM queue WHERE tenant_id = $tenant_id ORDER BY created_at DESC")
+                .bind(("tenant_id", tenant_id.to_string())),
+        )
+        .await?;
+
+        let records: Vec<QueueRecord> = result.take(0)?;
 
         Ok(records
             .into_iter()
@@ -127,10 +171,11 @@ impl QueueRepository {
     pub async fn list_by_state(state: QueueState) -> Result<Vec<Queue>, DbError> {
         let db = get_db()?;
 
-        let mut result = db
-            .query("SELECT * FROM queue WHERE state = $state ORDER BY created_at DESC")
-            .bind(("state", state.to_string()))
-            .await?;
+        let mut result = with_deadline(
+            db.query("SELECT * FROM queue WHERE state = $state ORDER BY created_at DESC")
+                .bind(("state", state.to_string())),
+        )
+        .await?;
 
         let records: Vec<QueueRecord> = result.take(0)?;
 
@@ -149,11 +194,10 @@ impl QueueRepository {
         let db = get_db()?;
 
         // Use SurrealQL to set updated_at with time::now()
-        let mut result = db
+        let mut result = with_deadline(db
             .query("UPDATE type::thing('queue', $id) SET state = $state, updated_at = time::now() RETURN AFTER")
             .bind(("id", id.to_string()))
-            .bind(("state", state))
-            .await?;
+            .bind(("state", state))).await?;
 
         let records: Vec<QueueRecord> = result.take(0)?;
 
@@ -170,11 +214,10 @@ impl QueueRepository {
         let stats_clone = stats.clone();
 
         // Use SurrealQL to set updated_at with time::now()
-        let mut result = db
+        let mut result = with_deadline(db
             .query("UPDATE type::thing('queue', $id) SET stats = $stats, updated_at = time::now() RETURN AFTER")
             .bind(("id", id.to_string()))
-            .bind(("stats", stats_clone))
-            .await?;
+            .bind(("stats", stats_clone))).await?;
 
         let records: Vec<QueueRecord> = result.take(0)?;
 
@@ -185,21 +228,58 @@ impl QueueRepository {
             .ok_or_else(|| DbError::NotFound(format!("Queue not found: {}", id)))
     }
 
+    /// Persist stats for several queues in a single transaction.
+    pub async fn update_stats_many(entries: &[(QueueId, QueueStats)]) -> Result<(), DbError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        #[derive(Serialize)]
+        struct StatsUpdate {
+            id: String,
+            stats: QueueStats,
+        }
+
+        let db = get_db()?;
+        let updates: Vec<StatsUpdate> = entries
+            .iter()
+            .map(|(id, stats)| StatsUpdate {
+                id: id.to_string(),
+                stats: stats.clone(),
+            })
+            .collect();
+
+        with_deadline(db.query(
+            r#"
+            BEGIN TRANSACTION;
+            FOR $update IN $updates {
+                UPDATE type::thing('queue', $update.id) SET stats = $update.stats, updated_at = time::now();
+            };
+            COMMIT TRANSACTION;
+            "#,
+        )
+        .bind(("updates", updates)))
+        .await?
+        .check()?;
+
+        Ok(())
+    }
+
     /// Update a queue.
     pub async fn update(queue: &Queue) -> Result<Queue, DbError> {
         let db = get_db()?;
 
-        let mut result = db
+        let mut result = with_deadline(db
             .query(
-                "UPDATE type::thing('queue', $id) SET name = $name, description = $description, state = $state, config = $config, stats = $stats, updated_at = time::now() RETURN AFTER",
+                "UPDATE type::thing('queue', $id) SET name = $name, description = $description, state = $state, config = $config, tags = $tags, stats = $stats, updated_at = time::now() RETURN AFTER",
             )
             .bind(("id", queue.id.to_string()))
             .bind(("name", queue.name.clone()))
             .bind(("description", queue.description.clone()))
             .bind(("state", queue.state))
             .bind(("config", queue.config.clone()))
-            .bind(("stats", queue.stats.clone()))
-            .await?;
+            .bind(("tags", queue.tags.clone()))
+            .bind(("stats", queue.stats.clone()))).await?;
 
         let records: Vec<QueueRecord> = result.take(0)?;
 
@@ -214,7 +294,7 @@ impl QueueRepository {
     pub async fn delete(id: QueueId) -> Result<(), DbError> {
         let db = get_db()?;
 
-        let _: Option<QueueRecord> = db.delete(("queue", id.to_string())).await?;
+        let _: Option<QueueRecord> = with_deadline(db.delete(("queue", id.to_string()))).await?;
 
         Ok(())
     }
@@ -223,7 +303,8 @@ impl QueueRepository {
     pub async fn exists(id: QueueId) -> Result<bool, DbError> {
         let db = get_db()?;
 
-        let record: Option<QueueRecord> = db.select(("queue", id.to_string())).await?;
+        let record: Option<QueueRecord> =
+            with_deadline(db.select(("queue", id.to_string()))).await?;
 
         Ok(record.is_some())
     }
@@ -233,10 +314,11 @@ impl QueueRepository {
         let db = get_db()?;
         let name_owned = name.to_string();
 
-        let mut result = db
-            .query("SELECT count() FROM queue WHERE name = $name GROUP ALL")
-            .bind(("name", name_owned))
-            .await?;
+        let mut result = with_deadline(
+            db.query("SELECT count() FROM queue WHERE name = $name GROUP ALL")
+                .bind(("name", name_owned)),
+        )
+        .await?;
 
         #[derive(Deserialize)]
         struct CountResult {
@@ -247,4 +329,41 @@ impl QueueRepository {
 
         Ok(counts.first().is_some_and(|c| c.count > 0))
     }
+
+    /// Store a stats snapshot for a closed reporting window.
+    pub async fn snapshot_stats(
+        id: QueueId,
+        stats: &QueueStats,
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+    ) -> Result<(), DbError> {
+        let db = get_db()?;
+
+        with_deadline(db.query(
+            "CREATE stats_history CONTENT { queue_id: $queue_id, window_start: $window_start, window_end: $window_end, stats: $stats }",
+        )
+        .bind(("queue_id", id.to_string()))
+        .bind(("window_start", Datetime::from(window_start)))
+        .bind(("window_end", Datetime::from(window_end)))
+        .bind(("stats", stats.clone()))).await?
+        .check()?;
+
+        Ok(())
+    }
+
+    /// List stats snapshots for a queue, oldest window first.
+    pub async fn stats_history(id: QueueId) -> Result<Vec<QueueStatsSnapshot>, DbError> {
+        let db = get_db()?;
+
+        let mut result = with_deadline(
+            db.query(
+                "SELECT * FROM stats_history WHERE queue_id = $queue_id ORDER BY window_start ASC",
+            )
+            .bind(("queue_id", id.to_string())),
+        )
+        .await?;
+
+        let snapshots: Vec<QueueStatsSnapshot> = result.take(0)?;
+        Ok(snapshots)
+    }
 }
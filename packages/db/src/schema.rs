@@ -19,6 +19,12 @@ pub async fn init_schema() -> Result<(), DbError> {
     // Job history table (for analytics)
     db.query(JOB_HISTORY_SCHEMA).await?;
 
+    // Stats history table (rolled-over reporting windows)
+    db.query(STATS_HISTORY_SCHEMA).await?;
+
+    // Event log table (backfill for late subscribers)
+    db.query(EVENT_LOG_SCHEMA).await?;
+
     tracing::info!("Database schema initialized");
 
     Ok(())
@@ -37,20 +43,46 @@ DEFINE FIELD IF NOT EXISTS config.concurrency ON queue TYPE int DEFAULT 4;
 DEFINE FIELD IF NOT EXISTS config.default_timeout_secs ON queue TYPE int DEFAULT 300;
 DEFINE FIELD IF NOT EXISTS config.default_max_retries ON queue TYPE int DEFAULT 3;
 DEFINE FIELD IF NOT EXISTS config.max_queue_size ON queue TYPE option<int>;
+DEFINE FIELD IF NOT EXISTS config.max_payload_bytes ON queue TYPE option<int>;
 DEFINE FIELD IF NOT EXISTS config.rate_limit ON queue TYPE option<float>;
+DEFINE FIELD IF NOT EXISTS config.retry_priority_boost ON queue TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS config.poll_interval_ms ON queue TYPE int DEFAULT 100;
+DEFINE FIELD IF NOT EXISTS config.breaker_threshold ON queue TYPE option<float>;
+DEFINE FIELD IF NOT EXISTS config.breaker_cooldown_secs ON queue TYPE int DEFAULT 60;
+DEFINE FIELD IF NOT EXISTS config.accept_unpersisted ON queue TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS config.webhook_url ON queue TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS config.default_tags ON queue TYPE array DEFAULT [];
+DEFINE FIELD IF NOT EXISTS config.default_tags.* ON queue TYPE string;
+DEFINE FIELD IF NOT EXISTS config.allowed_job_types ON queue TYPE option<array>;
+DEFINE FIELD IF NOT EXISTS config.allowed_job_types.* ON queue TYPE string;
+DEFINE FIELD IF NOT EXISTS config.strict_job_types ON queue TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS tags ON queue TYPE array DEFAULT [];
+DEFINE FIELD IF NOT EXISTS tags.* ON queue TYPE string;
+DEFINE FIELD IF NOT EXISTS tenant_id ON queue TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS stats ON queue TYPE object DEFAULT {};
 DEFINE FIELD IF NOT EXISTS stats.pending ON queue TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.scheduled ON queue TYPE int DEFAULT 0;
 DEFINE FIELD IF NOT EXISTS stats.running ON queue TYPE int DEFAULT 0;
 DEFINE FIELD IF NOT EXISTS stats.completed ON queue TYPE int DEFAULT 0;
 DEFINE FIELD IF NOT EXISTS stats.failed ON queue TYPE int DEFAULT 0;
 DEFINE FIELD IF NOT EXISTS stats.avg_duration_ms ON queue TYPE option<float>;
 DEFINE FIELD IF NOT EXISTS stats.throughput_per_min ON queue TYPE option<float>;
+DEFINE FIELD IF NOT EXISTS stats.oldest_pending_age_ms ON queue TYPE option<int>;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority ON queue TYPE object DEFAULT {};
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.deferred ON queue TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.low ON queue TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.normal ON queue TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.high ON queue TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.critical ON queue TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.rate_limited ON queue TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS stats.breaker_open ON queue TYPE bool DEFAULT false;
 DEFINE FIELD IF NOT EXISTS created_at ON queue TYPE datetime DEFAULT time::now();
 DEFINE FIELD IF NOT EXISTS updated_at ON queue TYPE datetime DEFAULT time::now();
 
 -- Indexes for efficient lookups
 DEFINE INDEX IF NOT EXISTS queue_name ON queue FIELDS name UNIQUE;
 DEFINE INDEX IF NOT EXISTS queue_state ON queue FIELDS state;
+DEFINE INDEX IF NOT EXISTS queue_tenant ON queue FIELDS tenant_id;
 "#;
 
 /// Job table schema.
@@ -62,9 +94,14 @@ DEFINE FIELD IF NOT EXISTS queue_id ON job TYPE string;
 DEFINE FIELD IF NOT EXISTS job_type ON job TYPE string;
 DEFINE FIELD IF NOT EXISTS payload ON job TYPE object;
 DEFINE FIELD IF NOT EXISTS payload.* ON job TYPE any;
+DEFINE FIELD IF NOT EXISTS run_at ON job TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS deadline ON job TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS priority ON job TYPE string DEFAULT "normal";
+DEFINE FIELD IF NOT EXISTS priority_boost ON job TYPE int DEFAULT 0;
 DEFINE FIELD IF NOT EXISTS status ON job TYPE object;
 DEFINE FIELD IF NOT EXISTS status.status ON job TYPE string;
+-- Flat copy of status.status, kept in sync on every write, for cheap indexes and filters
+DEFINE FIELD IF NOT EXISTS status_str ON job TYPE string;
 DEFINE FIELD IF NOT EXISTS status.started_at ON job TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS status.worker_id ON job TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS status.completed_at ON job TYPE option<string>;
@@ -73,6 +110,7 @@ DEFINE FIELD IF NOT EXISTS status.cancelled_at ON job TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS status.error ON job TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS status.attempts ON job TYPE option<int>;
 DEFINE FIELD IF NOT EXISTS status.reason ON job TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS status.run_at ON job TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS status.result ON job TYPE option<object>;
 DEFINE FIELD IF NOT EXISTS status.result.summary ON job TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS status.result.output ON job TYPE option<object>;
@@ -81,18 +119,38 @@ DEFINE FIELD IF NOT EXISTS max_retries ON job TYPE int DEFAULT 3;
 DEFINE FIELD IF NOT EXISTS timeout_secs ON job TYPE int DEFAULT 300;
 DEFINE FIELD IF NOT EXISTS tags ON job TYPE array DEFAULT [];
 DEFINE FIELD IF NOT EXISTS tags.* ON job TYPE string;
+DEFINE FIELD IF NOT EXISTS logs ON job TYPE array DEFAULT [];
+DEFINE FIELD IF NOT EXISTS logs.* ON job TYPE object;
+DEFINE FIELD IF NOT EXISTS logs.*.level ON job TYPE string;
+DEFINE FIELD IF NOT EXISTS logs.*.message ON job TYPE string;
+DEFINE FIELD IF NOT EXISTS logs.*.timestamp ON job TYPE string;
+DEFINE FIELD IF NOT EXISTS attempts_log ON job TYPE array DEFAULT [];
+DEFINE FIELD IF NOT EXISTS attempts_log.* ON job TYPE object;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.attempt ON job TYPE int;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.worker_id ON job TYPE string;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.started_at ON job TYPE string;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.finished_at ON job TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.error ON job TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS version ON job TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS tenant_id ON job TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS created_at ON job TYPE datetime DEFAULT time::now();
 DEFINE FIELD IF NOT EXISTS updated_at ON job TYPE datetime DEFAULT time::now();
 
 -- Indexes for efficient job queries
 DEFINE INDEX IF NOT EXISTS job_queue ON job FIELDS queue_id;
-DEFINE INDEX IF NOT EXISTS job_status ON job FIELDS status.status;
+REMOVE INDEX IF EXISTS job_status ON job;
+DEFINE INDEX IF NOT EXISTS job_status_str ON job FIELDS status_str;
 DEFINE INDEX IF NOT EXISTS job_priority ON job FIELDS priority;
 DEFINE INDEX IF NOT EXISTS job_type ON job FIELDS job_type;
 DEFINE INDEX IF NOT EXISTS job_created ON job FIELDS created_at;
+DEFINE INDEX IF NOT EXISTS job_tenant ON job FIELDS tenant_id;
 
 -- Compound index for queue polling (pending jobs by priority)
-DEFINE INDEX IF NOT EXISTS job_queue_pending ON job FIELDS queue_id, status.status, priority;
+REMOVE INDEX IF EXISTS job_queue_pending ON job;
+DEFINE INDEX IF NOT EXISTS job_queue_status_str ON job FIELDS queue_id, status_str, priority;
+
+-- Backfill status_str on jobs stored before it existed
+UPDATE job SET status_str = status.status WHERE status_str = NONE;
 "#;
 
 /// Job history table schema for analytics and auditing.
@@ -103,6 +161,8 @@ DEFINE TABLE IF NOT EXISTS job_history SCHEMAFULL;
 DEFINE FIELD IF NOT EXISTS job_id ON job_history TYPE string;
 DEFINE FIELD IF NOT EXISTS queue_id ON job_history TYPE string;
 DEFINE FIELD IF NOT EXISTS job_type ON job_history TYPE string;
+DEFINE FIELD IF NOT EXISTS payload ON job_history TYPE option<object>;
+DEFINE FIELD IF NOT EXISTS payload.* ON job_history TYPE any;
 DEFINE FIELD IF NOT EXISTS priority ON job_history TYPE string;
 DEFINE FIELD IF NOT EXISTS final_status ON job_history TYPE string;
 DEFINE FIELD IF NOT EXISTS attempts ON job_history TYPE int DEFAULT 1;
@@ -111,6 +171,19 @@ DEFINE FIELD IF NOT EXISTS error ON job_history TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS result_summary ON job_history TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS tags ON job_history TYPE array DEFAULT [];
 DEFINE FIELD IF NOT EXISTS tags.* ON job_history TYPE string;
+DEFINE FIELD IF NOT EXISTS logs ON job_history TYPE array DEFAULT [];
+DEFINE FIELD IF NOT EXISTS logs.* ON job_history TYPE object;
+DEFINE FIELD IF NOT EXISTS logs.*.level ON job_history TYPE string;
+DEFINE FIELD IF NOT EXISTS logs.*.message ON job_history TYPE string;
+DEFINE FIELD IF NOT EXISTS logs.*.timestamp ON job_history TYPE string;
+DEFINE FIELD IF NOT EXISTS attempts_log ON job_history TYPE array DEFAULT [];
+DEFINE FIELD IF NOT EXISTS attempts_log.* ON job_history TYPE object;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.attempt ON job_history TYPE int;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.worker_id ON job_history TYPE string;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.started_at ON job_history TYPE string;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.finished_at ON job_history TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS attempts_log.*.error ON job_history TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS tenant_id ON job_history TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS created_at ON job_history TYPE string;
 DEFINE FIELD IF NOT EXISTS completed_at ON job_history TYPE datetime DEFAULT time::now();
 
@@ -119,4 +192,51 @@ DEFINE INDEX IF NOT EXISTS history_queue ON job_history FIELDS queue_id;
 DEFINE INDEX IF NOT EXISTS history_type ON job_history FIELDS job_type;
 DEFINE INDEX IF NOT EXISTS history_status ON job_history FIELDS final_status;
 DEFINE INDEX IF NOT EXISTS history_completed ON job_history FIELDS completed_at;
+DEFINE INDEX IF NOT EXISTS history_queue_job ON job_history FIELDS queue_id, job_id;
+"#;
+
+/// Stats history schema for rolled-over reporting windows.
+const STATS_HISTORY_SCHEMA: &str = r#"
+-- Queue stats snapshots taken at each rollover boundary
+DEFINE TABLE IF NOT EXISTS stats_history SCHEMAFULL;
+
+DEFINE FIELD IF NOT EXISTS queue_id ON stats_history TYPE string;
+DEFINE FIELD IF NOT EXISTS window_start ON stats_history TYPE datetime;
+DEFINE FIELD IF NOT EXISTS window_end ON stats_history TYPE datetime;
+DEFINE FIELD IF NOT EXISTS stats ON stats_history TYPE object;
+DEFINE FIELD IF NOT EXISTS stats.pending ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.scheduled ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.running ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.completed ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.failed ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.avg_duration_ms ON stats_history TYPE option<float>;
+DEFINE FIELD IF NOT EXISTS stats.throughput_per_min ON stats_history TYPE option<float>;
+DEFINE FIELD IF NOT EXISTS stats.oldest_pending_age_ms ON stats_history TYPE option<int>;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority ON stats_history TYPE object DEFAULT {};
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.deferred ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.low ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.normal ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.high ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.pending_by_priority.critical ON stats_history TYPE int DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS stats.rate_limited ON stats_history TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS stats.breaker_open ON stats_history TYPE bool DEFAULT false;
+
+-- Indexes for reporting queries
+DEFINE INDEX IF NOT EXISTS stats_history_queue ON stats_history FIELDS queue_id;
+DEFINE INDEX IF NOT EXISTS stats_history_window ON stats_history FIELDS window_end;
+"#;
+
+/// Event log schema, appended to as events are broadcast.
+const EVENT_LOG_SCHEMA: &str = r#"
+-- Append-only log of broadcast events, stored as serialized JSON
+DEFINE TABLE IF NOT EXISTS event_log SCHEMAFULL;
+
+DEFINE FIELD IF NOT EXISTS queue_id ON event_log TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS job_id ON event_log TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS timestamp ON event_log TYPE datetime;
+DEFINE FIELD IF NOT EXISTS event ON event_log TYPE string;
+
+-- Indexes for backfill queries and pruning
+DEFINE INDEX IF NOT EXISTS event_log_queue ON event_log FIELDS queue_id, timestamp;
+DEFINE INDEX IF NOT EXISTS event_log_timestamp ON event_log FIELDS timestamp;
 "#;
@@ -1,17 +1,36 @@
-use std::sync::LazyLock;
+use std::error::Error;
+use std::sync::{LazyLock, OnceLock};
 
+use tokio::runtime::Runtime;
 use tokio::sync::{Mutex, MutexGuard};
 
-use db::{DbConfig, DbError};
+use db::DbConfig;
 
 static TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
 
-pub async fn setup_db() -> Result<MutexGuard<'static, ()>, DbError> {
+/// Runtime the in-memory database connects on.
+///
+/// The engine's background task lives on the runtime that connected, and each
+/// `#[tokio::test]` gets its own, so the connection has to outlive them all.
+static DB_RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+pub async fn setup_db() -> Result<MutexGuard<'static, ()>, Box<dyn Error>> {
     let guard = TEST_LOCK.lock().await;
-    db::init(DbConfig::memory()).await?;
+    // Only the lock holder sets the runtime, so `set` can't fail
+    if DB_RUNTIME.get().is_none() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()?;
+        let _ = DB_RUNTIME.set(runtime);
+    }
+    let runtime = DB_RUNTIME.get().ok_or("database runtime not started")?;
+    runtime.spawn(db::init(DbConfig::memory())).await??;
     let db_conn = db::get_db()?;
     db_conn
-        .query("DELETE job_history; DELETE job; DELETE queue;")
+        .query(
+            "DELETE event_log; DELETE stats_history; DELETE job_history; DELETE job; DELETE queue;",
+        )
         .await?;
     Ok(guard)
 }
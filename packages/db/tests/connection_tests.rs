@@ -0,0 +1,28 @@
+#![allow(clippy::disallowed_methods)]
+
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+use db::{DbConfig, DbError};
+
+#[tokio::test]
+async fn test_connect_retries_unreachable_endpoint() -> Result<(), Box<dyn Error>> {
+    let config = DbConfig {
+        endpoint: "ws://127.0.0.1:9".to_string(),
+        ..DbConfig::default()
+    }
+    .with_connect_retries(3, 10);
+
+    let started = Instant::now();
+    let result = db::connect_db(&config).await;
+
+    assert!(
+        matches!(result, Err(DbError::Unreachable { attempts: 4, .. })),
+        "expected 4 attempts, got {:?}",
+        result.err()
+    );
+    // Backoff of 10ms, 20ms and 40ms between the attempts
+    assert!(started.elapsed() >= Duration::from_millis(70));
+
+    Ok(())
+}
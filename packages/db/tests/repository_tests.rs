@@ -3,11 +3,19 @@
 mod common;
 
 use chrono::Utc;
-use queue_core::{Job, JobResult, JobStatus, Priority, Queue, QueueConfig, QueueState, QueueStats};
+use futures_util::StreamExt;
+use queue_core::{
+    Job, JobEvent, JobId, JobResult, JobSource, JobStatus, Priority, Queue, QueueConfig, QueueId,
+    QueueState, QueueStats,
+};
 use serde_json::{Map, Value};
+use std::collections::HashSet;
 use std::error::Error;
 
-use db::{DbError, repositories::JobRepository, repositories::QueueRepository};
+use db::{
+    DbError, repositories::EventRepository, repositories::JobFilter, repositories::JobRepository,
+    repositories::MAX_ARCHIVED_PAYLOAD_BYTES, repositories::QueueRepository,
+};
 
 fn payload_with_message(message: &str) -> Value {
     let mut map = Map::new();
@@ -18,7 +26,9 @@ fn payload_with_message(message: &str) -> Value {
 async fn reset_db() -> Result<(), DbError> {
     let db_conn = db::get_db()?;
     db_conn
-        .query("DELETE job_history; DELETE job; DELETE queue;")
+        .query(
+            "DELETE event_log; DELETE stats_history; DELETE job_history; DELETE job; DELETE queue;",
+        )
         .await?;
     Ok(())
 }
@@ -45,6 +55,7 @@ async fn test_repositories() -> Result<(), Box<dyn Error>> {
         failed: 4,
         avg_duration_ms: Some(10.5),
         throughput_per_min: Some(2.25),
+        ..Default::default()
     };
     let updated_stats = QueueRepository::update_stats(queue.id, &stats).await?;
     assert_eq!(updated_stats.stats.pending, 1);
@@ -57,10 +68,15 @@ async fn test_repositories() -> Result<(), Box<dyn Error>> {
         default_max_retries: 1,
         max_queue_size: Some(10),
         rate_limit: Some(5.0),
+        retry_priority_boost: 1,
+        default_tags: vec!["team:payments".to_string()],
+        poll_interval_ms: 250,
+        ..QueueConfig::default()
     };
     let updated = QueueRepository::update(&queue).await?;
     assert_eq!(updated.description.as_deref(), Some("updated"));
     assert_eq!(updated.config.concurrency, 2);
+    assert_eq!(updated.config.default_tags, ["team:payments"]);
 
     let exists = QueueRepository::exists(queue.id).await?;
     assert!(exists);
@@ -114,16 +130,25 @@ async fn test_repositories() -> Result<(), Box<dyn Error>> {
         started_at: Utc::now(),
         worker_id: "worker-1".to_string(),
     };
-    let updated_status = JobRepository::update_status(job.id, &running_status, 1).await?;
+    let updated_status =
+        JobRepository::update_status(job.id, &running_status, 1, job.version).await?;
     assert!(matches!(updated_status.status, JobStatus::Running { .. }));
     assert_eq!(updated_status.attempts, 1);
+    assert_eq!(updated_status.version, job.version + 1);
 
     job = updated_status;
     job.tags = vec!["tag-a".to_string()];
     job.priority = Priority::High;
+    job.run_at = Some(Utc::now() + chrono::Duration::minutes(5));
+    job.deadline = Some(Utc::now() + chrono::Duration::hours(1));
     let updated_job = JobRepository::update(&job).await?;
     assert_eq!(updated_job.tags.len(), 1);
     assert_eq!(updated_job.priority, Priority::High);
+    assert_eq!(updated_job.run_at, job.run_at);
+    assert_eq!(updated_job.deadline, job.deadline);
+    let reloaded = JobRepository::get(job.id).await?;
+    assert_eq!(reloaded.run_at, job.run_at);
+    assert_eq!(reloaded.deadline, job.deadline);
 
     JobRepository::delete(job.id).await?;
     let missing_job = JobRepository::get(job.id).await;
@@ -217,3 +242,730 @@ async fn test_repositories() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_find_anywhere() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("finder");
+    QueueRepository::create(&queue).await?;
+
+    let mut job = Job::new(queue.id, "report", payload_with_message("r"));
+    JobRepository::create(&job).await?;
+
+    let found = JobRepository::find_anywhere(job.id).await?;
+    let view = found.first().ok_or("active job not found")?;
+    assert_eq!(view.source, JobSource::Active);
+    assert_eq!(view.status, "pending");
+
+    job.status = JobStatus::Completed {
+        started_at: Utc::now(),
+        completed_at: Utc::now(),
+        result: JobResult::new("done"),
+    };
+    job.attempts = 1;
+    JobRepository::update(&job).await?;
+    JobRepository::archive(&job).await?;
+
+    let found = JobRepository::find_anywhere(job.id).await?;
+    let view = found.first().ok_or("archived job not found")?;
+    assert_eq!(view.source, JobSource::History);
+    assert_eq!(view.status, "completed");
+    assert_eq!(view.result_summary.as_deref(), Some("done"));
+
+    let other = Job::new(queue.id, "report", payload_with_message("o"));
+    JobRepository::create(&other).await?;
+
+    let all = JobRepository::find_anywhere(JobFilter {
+        queue_id: Some(queue.id),
+        ..Default::default()
+    })
+    .await?;
+    assert_eq!(all.len(), 2);
+    assert!(all.iter().any(|v| v.id == job.id && v.is_archived()));
+    assert!(all.iter().any(|v| v.id == other.id && !v.is_archived()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archive_clamps_negative_duration() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("skewed");
+    QueueRepository::create(&queue).await?;
+
+    let mut job = Job::new(queue.id, "report", payload_with_message("s"));
+    JobRepository::create(&job).await?;
+
+    // Simulate clock skew: completion recorded before the start.
+    let started_at = Utc::now();
+    job.status = JobStatus::Completed {
+        started_at,
+        completed_at: started_at - chrono::Duration::seconds(5),
+        result: JobResult::new("done"),
+    };
+    job.attempts = 1;
+    JobRepository::update(&job).await?;
+    JobRepository::archive(&job).await?;
+
+    let found = JobRepository::find_anywhere(job.id).await?;
+    let view = found.first().ok_or("archived job not found")?;
+    assert_eq!(view.duration_ms, Some(0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_stats_many() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let mut entries = Vec::new();
+    for (i, name) in ["batch-a", "batch-b", "batch-c"].into_iter().enumerate() {
+        let queue = Queue::new(name);
+        QueueRepository::create(&queue).await?;
+        let stats = QueueStats {
+            pending: i as u64 + 1,
+            completed: (i as u64 + 1) * 10,
+            ..Default::default()
+        };
+        entries.push((queue.id, stats));
+    }
+
+    QueueRepository::update_stats_many(&entries).await?;
+
+    for (id, stats) in &entries {
+        let stored = QueueRepository::get(*id).await?;
+        assert_eq!(&stored.stats, stats);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stale_update_conflicts() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("versioned");
+    QueueRepository::create(&queue).await?;
+
+    let job =
+        JobRepository::create(&Job::new(queue.id, "report", payload_with_message("v"))).await?;
+    assert_eq!(job.version, 0);
+
+    // Two copies of the same job; the first write wins.
+    let mut first = job.clone();
+    first.priority = Priority::High;
+    let first = JobRepository::update(&first).await?;
+    assert_eq!(first.version, 1);
+
+    let mut stale = job.clone();
+    stale.priority = Priority::Low;
+    let result = JobRepository::update(&stale).await;
+    assert!(matches!(result, Err(DbError::Conflict(_))));
+
+    let result = JobRepository::update_status(job.id, &JobStatus::Pending, 0, job.version).await;
+    assert!(matches!(result, Err(DbError::Conflict(_))));
+
+    let stored = JobRepository::get(job.id).await?;
+    assert_eq!(stored.priority, Priority::High);
+    assert_eq!(stored.version, 1);
+
+    // A missing job is still reported as not found.
+    let missing = JobRepository::update_status(JobId::new(), &JobStatus::Pending, 0, 0).await;
+    assert!(matches!(missing, Err(DbError::NotFound(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_watch_streams_job_updates() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("watched");
+    QueueRepository::create(&queue).await?;
+
+    let job =
+        JobRepository::create(&Job::new(queue.id, "report", payload_with_message("w"))).await?;
+    let mut changes = std::pin::pin!(JobRepository::watch(job.id).await?);
+
+    let running_status = JobStatus::Running {
+        started_at: Utc::now(),
+        worker_id: "worker-1".to_string(),
+    };
+    JobRepository::update_status(job.id, &running_status, 1, job.version).await?;
+
+    let changed = tokio::time::timeout(std::time::Duration::from_secs(5), changes.next())
+        .await?
+        .ok_or("watch stream ended")?;
+    assert_eq!(changed.id, job.id);
+    assert!(matches!(changed.status, JobStatus::Running { .. }));
+    assert_eq!(changed.attempts, 1);
+
+    // Deleting the job ends the stream.
+    JobRepository::delete(job.id).await?;
+    let ended = tokio::time::timeout(std::time::Duration::from_secs(5), changes.next()).await?;
+    assert!(ended.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prune_history_removes_old_rows() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+    let db_conn = db::get_db()?;
+
+    for (job_type, age_days) in [("old", 40), ("older", 90), ("recent", 1)] {
+        db_conn
+            .query(
+                "CREATE job_history CONTENT { job_id: $job_id, queue_id: $queue_id, job_type: $job_type, priority: 'normal', final_status: 'completed', created_at: '', completed_at: time::now() - duration::from::days($age) }",
+            )
+            .bind(("job_id", JobId::new().to_string()))
+            .bind(("queue_id", QueueId::new().to_string()))
+            .bind(("job_type", job_type))
+            .bind(("age", age_days))
+            .await?
+            .check()?;
+    }
+
+    let pruned =
+        JobRepository::prune_history(std::time::Duration::from_secs(30 * 24 * 60 * 60)).await?;
+    assert_eq!(pruned, 2);
+
+    let remaining = JobRepository::find_anywhere(JobFilter::default()).await?;
+    assert_eq!(remaining.len(), 1);
+    let survivor = remaining.first().ok_or("recent history row missing")?;
+    assert_eq!(survivor.job_type, "recent");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_throughput_buckets_count_per_bucket() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+    let db_conn = db::get_db()?;
+    let queue_id = QueueId::new();
+    let hour = std::time::Duration::from_secs(60 * 60);
+
+    // Two in the previous hour, one at the start of the current hour, and one for another queue
+    for (queue, status, mins_before_hour) in [
+        (queue_id, "completed", 30),
+        (queue_id, "failed", 10),
+        (queue_id, "completed", 0),
+        (QueueId::new(), "completed", 0),
+    ] {
+        db_conn
+            .query(
+                "CREATE job_history CONTENT { job_id: $job_id, queue_id: $queue_id, job_type: 'noop', priority: 'normal', final_status: $status, created_at: '', completed_at: time::floor(time::now(), 1h) - duration::from::mins($mins) }",
+            )
+            .bind(("job_id", JobId::new().to_string()))
+            .bind(("queue_id", queue.to_string()))
+            .bind(("status", status))
+            .bind(("mins", mins_before_hour))
+            .await?
+            .check()?;
+    }
+
+    let buckets = JobRepository::throughput_buckets(queue_id, hour, hour * 3).await?;
+    let counts: Vec<(u64, u64)> = buckets
+        .iter()
+        .map(|bucket| (bucket.completed, bucket.failed))
+        .collect();
+    assert_eq!(counts, vec![(0, 0), (1, 1), (1, 0)]);
+    let last = buckets.last().ok_or("no buckets returned")?;
+    assert!(last.bucket_start <= Utc::now());
+
+    let empty = JobRepository::throughput_buckets(QueueId::new(), hour, hour * 3).await?;
+    assert_eq!(empty.len(), 3);
+    assert!(
+        empty
+            .iter()
+            .all(|bucket| bucket.completed == 0 && bucket.failed == 0)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archive_keeps_payload() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("audited");
+    QueueRepository::create(&queue).await?;
+
+    let mut job = Job::new(queue.id, "report", payload_with_message("keep me"));
+    JobRepository::create(&job).await?;
+    let now = Utc::now();
+    job.status = JobStatus::Failed {
+        started_at: now,
+        failed_at: now,
+        error: "boom".to_string(),
+        attempts: 1,
+    };
+    JobRepository::update(&job).await?;
+    JobRepository::archive(&job).await?;
+
+    let archived = JobRepository::get_history(job.id).await?;
+    assert_eq!(archived.payload, Some(payload_with_message("keep me")));
+
+    // Oversized payloads are archived without them
+    let mut big = Job::new(
+        queue.id,
+        "report",
+        payload_with_message(&"x".repeat(MAX_ARCHIVED_PAYLOAD_BYTES)),
+    );
+    JobRepository::create(&big).await?;
+    big.status = JobStatus::Cancelled {
+        reason: None,
+        cancelled_at: Utc::now(),
+    };
+    JobRepository::update(&big).await?;
+    JobRepository::archive(&big).await?;
+
+    let archived = JobRepository::get_history(big.id).await?;
+    assert_eq!(archived.payload, None);
+    assert!(archived.replay().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_event_log_lists_events_in_order() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue_id = QueueId::new();
+    let job = Job::new(queue_id, "report", payload_with_message("hi"));
+    let base = Utc::now();
+    let at = |secs: i64| base + chrono::Duration::seconds(secs);
+
+    // Appended out of order, plus one too old and one for another queue
+    let events = [
+        JobEvent::JobCompleted {
+            job_id: job.id,
+            queue_id,
+            duration_ms: 5,
+            timestamp: at(3),
+        },
+        JobEvent::JobEnqueued {
+            job: job.clone(),
+            timestamp: at(1),
+        },
+        JobEvent::JobStarted {
+            job_id: job.id,
+            queue_id,
+            worker_id: "worker-1".into(),
+            timestamp: at(2),
+        },
+        JobEvent::JobEnqueued {
+            job: job.clone(),
+            timestamp: at(-60),
+        },
+        JobEvent::QueueDeleted {
+            queue_id: QueueId::new(),
+            timestamp: at(2),
+        },
+    ];
+    for event in &events {
+        EventRepository::append(event).await?;
+    }
+
+    let logged = EventRepository::list(queue_id, base, 10).await?;
+    let timestamps: Vec<_> = logged.iter().map(JobEvent::timestamp).collect();
+    assert_eq!(timestamps, [at(1), at(2), at(3)]);
+    assert!(matches!(
+        logged.first(),
+        Some(JobEvent::JobEnqueued { job: logged_job, .. }) if logged_job.id == job.id
+    ));
+
+    let limited = EventRepository::list(queue_id, base, 2).await?;
+    assert_eq!(limited.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_filters_by_creation_window() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("incident");
+    QueueRepository::create(&queue).await?;
+
+    let mut created = Vec::new();
+    for message in ["before", "during", "after"] {
+        created.push(
+            JobRepository::create(&Job::new(queue.id, "report", payload_with_message(message)))
+                .await?,
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let [before, during, after] = created.as_slice() else {
+        return Err("expected three jobs".into());
+    };
+
+    let window = JobRepository::list(JobFilter {
+        queue_id: Some(queue.id),
+        created_after: Some(during.created_at),
+        created_before: Some(during.created_at),
+        ..Default::default()
+    })
+    .await?;
+    let ids: Vec<_> = window.iter().map(|job| job.id).collect();
+    assert_eq!(ids, [during.id]);
+
+    let since = JobRepository::list(JobFilter {
+        created_after: Some(during.created_at),
+        ..Default::default()
+    })
+    .await?;
+    let ids: Vec<_> = since.iter().map(|job| job.id).collect();
+    assert_eq!(ids, [during.id, after.id]);
+
+    // The same bounds apply to archived jobs
+    let mut archived = before.clone();
+    archived.status = JobStatus::Cancelled {
+        reason: None,
+        cancelled_at: Utc::now(),
+    };
+    JobRepository::archive(&archived).await?;
+    let until = JobRepository::find_anywhere(JobFilter {
+        created_before: Some(during.created_at - chrono::Duration::milliseconds(1)),
+        ..Default::default()
+    })
+    .await?;
+    let ids: Vec<_> = until.iter().map(|view| view.id).collect();
+    assert_eq!(ids, [before.id]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archive_moves_job_in_one_step() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("atomic-archive");
+    QueueRepository::create(&queue).await?;
+
+    let mut job = Job::new(queue.id, "report", payload_with_message("once"));
+    JobRepository::create(&job).await?;
+    let now = Utc::now();
+    job.status = JobStatus::Completed {
+        started_at: now,
+        completed_at: now,
+        result: JobResult::new("done"),
+    };
+    job.attempts = 1;
+    JobRepository::update(&job).await?;
+    JobRepository::archive(&job).await?;
+
+    let mut response = db::get_db()?
+        .query("SELECT count() FROM type::thing('job', $id) GROUP ALL")
+        .query("SELECT count() FROM job_history WHERE job_id = $id GROUP ALL")
+        .bind(("id", job.id.to_string()))
+        .await?;
+    let active: Option<i64> = response.take((0, "count"))?;
+    let archived: Option<i64> = response.take((1, "count"))?;
+    assert_eq!(active.unwrap_or(0), 0);
+    assert_eq!(archived, Some(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tenants_only_see_their_own_queues_and_jobs() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let acme = Queue::new("acme-emails").with_tenant("acme");
+    let globex = Queue::new("globex-emails").with_tenant("globex");
+    QueueRepository::create(&acme).await?;
+    QueueRepository::create(&globex).await?;
+    QueueRepository::create(&Queue::new("shared")).await?;
+
+    let acme_queues = QueueRepository::list_for_tenant("acme").await?;
+    let names: Vec<_> = acme_queues.iter().map(|q| q.name.as_str()).collect();
+    assert_eq!(names, ["acme-emails"]);
+    assert_eq!(acme_queues[0].tenant_id.as_deref(), Some("acme"));
+    assert!(
+        QueueRepository::list_for_tenant("initech")
+            .await?
+            .is_empty()
+    );
+
+    let mut acme_job = Job::new(acme.id, "send", payload_with_message("acme"));
+    acme_job.tenant_id = acme.tenant_id.clone();
+    let mut globex_job = Job::new(globex.id, "send", payload_with_message("globex"));
+    globex_job.tenant_id = globex.tenant_id.clone();
+    JobRepository::create(&acme_job).await?;
+    JobRepository::create(&globex_job).await?;
+
+    let visible = JobRepository::list(JobFilter {
+        tenant_id: Some("globex".to_string()),
+        ..Default::default()
+    })
+    .await?;
+    let ids: Vec<_> = visible.iter().map(|job| job.id).collect();
+    assert_eq!(ids, [globex_job.id]);
+
+    // Archiving keeps the tenant
+    acme_job.status = JobStatus::Completed {
+        started_at: Utc::now(),
+        completed_at: Utc::now(),
+        result: JobResult::new("sent"),
+    };
+    JobRepository::archive(&acme_job).await?;
+    let archived = JobRepository::get_history(acme_job.id).await?.into_job();
+    assert_eq!(archived.tenant_id.as_deref(), Some("acme"));
+    let found = JobRepository::find_anywhere(acme_job.id).await?;
+    let view = found.first().ok_or("archived job not found")?;
+    assert_eq!(view.tenant_id.as_deref(), Some("acme"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pending_jobs_follow_priority_order_not_alphabetical() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("priority-order");
+    QueueRepository::create(&queue).await?;
+
+    // Created lowest first so creation order can't mask the priority order
+    let ascending = [
+        Priority::Deferred,
+        Priority::Low,
+        Priority::Normal,
+        Priority::High,
+        Priority::Critical,
+    ];
+    for priority in ascending {
+        let job =
+            Job::new(queue.id, "report", payload_with_message("order")).with_priority(priority);
+        JobRepository::create(&job).await?;
+    }
+
+    let mut expected = ascending;
+    expected.reverse();
+
+    let pending = JobRepository::get_pending_for_queue(queue.id, 10).await?;
+    let order: Vec<_> = pending.iter().map(|job| job.priority).collect();
+    assert_eq!(order, expected);
+
+    let listed = JobRepository::list(JobFilter {
+        queue_id: Some(queue.id),
+        ..Default::default()
+    })
+    .await?;
+    let order: Vec<_> = listed.iter().map(|job| job.priority).collect();
+    assert_eq!(order, expected);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scheduled_jobs_are_counted_apart_from_pending() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("scheduled-stats");
+    QueueRepository::create(&queue).await?;
+
+    let ready = Job::new(queue.id, "report", payload_with_message("now"));
+    JobRepository::create(&ready).await?;
+
+    let run_at = Utc::now() + chrono::Duration::hours(1);
+    let mut later = Job::new(queue.id, "report", payload_with_message("later")).with_run_at(run_at);
+    later.status = JobStatus::Scheduled { run_at };
+    JobRepository::create(&later).await?;
+
+    let stored = JobRepository::get(later.id).await?;
+    assert!(matches!(stored.status, JobStatus::Scheduled { .. }));
+
+    let stats = JobRepository::get_queue_stats(queue.id).await?;
+    assert_eq!(stats.pending, 1);
+    assert_eq!(stats.scheduled, 1);
+
+    // Both are still loaded back into the queue on restart
+    let waiting = JobRepository::get_pending_for_queue(queue.id, 10).await?;
+    assert_eq!(waiting.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_count_by_status_all_queues_groups_by_queue() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let first = Queue::new("counts-first");
+    let second = Queue::new("counts-second");
+    let empty = Queue::new("counts-empty");
+    for queue in [&first, &second, &empty] {
+        QueueRepository::create(queue).await?;
+    }
+
+    for _ in 0..2 {
+        JobRepository::create(&Job::new(first.id, "report", payload_with_message("p"))).await?;
+    }
+    let mut running = Job::new(first.id, "report", payload_with_message("r"));
+    running.status = JobStatus::Running {
+        started_at: Utc::now(),
+        worker_id: "worker-1".into(),
+    };
+    JobRepository::create(&running).await?;
+
+    let mut failed = Job::new(second.id, "report", payload_with_message("f"));
+    failed.status = JobStatus::Failed {
+        started_at: Utc::now(),
+        failed_at: Utc::now(),
+        error: "boom".into(),
+        attempts: 1,
+    };
+    JobRepository::create(&failed).await?;
+
+    let counts = JobRepository::count_by_status_all_queues().await?;
+    assert_eq!(counts.len(), 2);
+    assert!(!counts.contains_key(&empty.id));
+
+    let first_counts = &counts[&first.id];
+    assert_eq!(first_counts.get("pending"), Some(&2));
+    assert_eq!(first_counts.get("running"), Some(&1));
+    assert_eq!(first_counts.get("failed"), None);
+
+    let second_counts = &counts[&second.id];
+    assert_eq!(second_counts.get("failed"), Some(&1));
+    assert_eq!(second_counts.get("pending"), None);
+
+    let stats = JobRepository::get_all_queue_stats().await?;
+    assert_eq!(stats[&first.id].pending, 2);
+    assert_eq!(stats[&second.id].failed, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_history_paged_walks_every_row_once() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+    let db_conn = db::get_db()?;
+    let queue_id = QueueId::new();
+
+    let mut archived = Vec::new();
+    for _ in 0..25 {
+        let job_id = JobId::new();
+        archived.push(job_id);
+        db_conn
+            .query(
+                "CREATE job_history CONTENT { job_id: $job_id, queue_id: $queue_id, job_type: 'report', priority: 'normal', final_status: 'completed', created_at: '' }",
+            )
+            .bind(("job_id", job_id.to_string()))
+            .bind(("queue_id", queue_id.to_string()))
+            .await?
+            .check()?;
+    }
+    // Another queue's history never shows up
+    db_conn
+        .query(
+            "CREATE job_history CONTENT { job_id: $job_id, queue_id: $queue_id, job_type: 'report', priority: 'normal', final_status: 'completed', created_at: '' }",
+        )
+        .bind(("job_id", JobId::new().to_string()))
+        .bind(("queue_id", QueueId::new().to_string()))
+        .await?
+        .check()?;
+
+    let mut seen = Vec::new();
+    let mut page_sizes = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = JobRepository::list_history_paged(queue_id, cursor, 10).await?;
+        page_sizes.push(page.records.len());
+        seen.extend(page.records.iter().map(|view| view.id));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(page_sizes, [10, 10, 5]);
+    assert_eq!(seen, archived);
+
+    let bad_cursor =
+        JobRepository::list_history_paged(queue_id, Some("not-a-job-id".into()), 10).await;
+    assert!(matches!(bad_cursor, Err(DbError::Query(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_status_str_tracks_status_updates() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+    let db_conn = db::get_db()?;
+
+    let queue = Queue::new("flat-status");
+    QueueRepository::create(&queue).await?;
+    let job =
+        JobRepository::create(&Job::new(queue.id, "report", payload_with_message("s"))).await?;
+
+    let stored_status_str = move |id: JobId| async move {
+        let mut response = db_conn
+            .query("SELECT VALUE status_str FROM type::thing('job', $id)")
+            .bind(("id", id.to_string()))
+            .await?;
+        let values: Vec<String> = response.take(0)?;
+        Ok::<_, Box<dyn Error>>(values.into_iter().next())
+    };
+    assert_eq!(stored_status_str(job.id).await?.as_deref(), Some("pending"));
+
+    let running = JobStatus::Running {
+        started_at: Utc::now(),
+        worker_id: "worker-1".into(),
+    };
+    JobRepository::update_status(job.id, &running, 1, job.version).await?;
+    assert_eq!(stored_status_str(job.id).await?.as_deref(), Some("running"));
+
+    // Filters and counts read the flat column
+    let found = JobRepository::list(JobFilter {
+        status: Some("running".into()),
+        queue_id: Some(queue.id),
+        ..Default::default()
+    })
+    .await?;
+    assert_eq!(found.len(), 1);
+    let counts = JobRepository::count_by_status(queue.id).await?;
+    assert_eq!(counts.get("running"), Some(&1));
+    assert_eq!(counts.get("pending"), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_filters_by_worker_id() -> Result<(), Box<dyn Error>> {
+    let _guard = common::setup_db().await?;
+
+    let queue = Queue::new("worker-filter");
+    QueueRepository::create(&queue).await?;
+
+    let running_on = |worker_id: &str| {
+        let mut job = Job::new(queue.id, "report", payload_with_message(worker_id));
+        job.status = JobStatus::Running {
+            started_at: Utc::now(),
+            worker_id: worker_id.into(),
+        };
+        job.start_attempt(worker_id, Utc::now());
+        job
+    };
+    let on_a = JobRepository::create(&running_on("worker-a")).await?;
+    JobRepository::create(&running_on("worker-b")).await?;
+
+    // Retried after failing on worker-a, now waiting for another worker
+    let mut retried = Job::new(queue.id, "report", payload_with_message("retried"));
+    retried.start_attempt("worker-a", Utc::now());
+    retried.finish_attempt(Utc::now(), Some("boom".into()));
+    let retried = JobRepository::create(&retried).await?;
+
+    let touched = JobRepository::list(JobFilter {
+        queue_id: Some(queue.id),
+        worker_id: Some("worker-a".into()),
+        ..Default::default()
+    })
+    .await?;
+    let ids: HashSet<JobId> = touched.iter().map(|job| job.id).collect();
+    assert_eq!(ids, HashSet::from([on_a.id, retried.id]));
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+#![allow(clippy::disallowed_methods)]
+
+use std::error::Error;
+use std::time::Duration;
+
+use db::{DbConfig, DbError};
+
+// Own binary: the query timeout is fixed when the global connection is created.
+#[tokio::test]
+async fn test_slow_query_times_out() -> Result<(), Box<dyn Error>> {
+    db::init(DbConfig::memory().with_query_timeout(Duration::from_millis(50))).await?;
+    let db_conn = db::get_db()?;
+
+    let result = db::with_deadline(db_conn.query("SLEEP 2s")).await;
+    assert!(
+        matches!(result, Err(DbError::Timeout(timeout)) if timeout == Duration::from_millis(50)),
+        "expected a timeout, got {:?}",
+        result.err()
+    );
+
+    // Quick queries still go through
+    db::with_deadline(db_conn.query("RETURN 1"))
+        .await?
+        .check()?;
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! Bulk actions on selected jobs.
+
+use std::collections::HashSet;
+
+use dioxus::prelude::*;
+use queue_core::{CancellationReason, Job, JobId};
+
+/// An action an admin can take on every selected job at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkAction {
+    Cancel,
+    Retry,
+}
+
+impl BulkAction {
+    /// Actions offered in the toolbar, in display order.
+    pub const ALL: [BulkAction; 2] = [BulkAction::Cancel, BulkAction::Retry];
+
+    /// Button label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            BulkAction::Cancel => "Cancel selected",
+            BulkAction::Retry => "Retry selected",
+        }
+    }
+
+    /// Button style class.
+    fn class(&self) -> &'static str {
+        match self {
+            BulkAction::Cancel => "btn-cancel",
+            BulkAction::Retry => "btn-retry",
+        }
+    }
+
+    /// Whether this action can be applied to every job in `selected`.
+    ///
+    /// Nothing applies to an empty selection.
+    pub fn applies_to(&self, selected: &[&Job]) -> bool {
+        !selected.is_empty()
+            && selected.iter().all(|job| match self {
+                BulkAction::Cancel => !job.status.is_terminal(),
+                BulkAction::Retry => job.status.can_retry(),
+            })
+    }
+
+    /// Apply this action to the given jobs via the server.
+    ///
+    /// Returns how many jobs the server changed.
+    pub async fn perform(self, queue_id: String, ids: Vec<String>) -> Result<usize, ServerFnError> {
+        match self {
+            BulkAction::Cancel => {
+                api::cancel_jobs_by_id(queue_id, ids, Some(CancellationReason::UserRequested)).await
+            }
+            BulkAction::Retry => api::retry_jobs_by_id(queue_id, ids).await,
+        }
+    }
+}
+
+/// Whether every job in `jobs` is selected (false when there are none).
+pub fn all_selected(selected: &HashSet<JobId>, jobs: &[Job]) -> bool {
+    !jobs.is_empty() && jobs.iter().all(|job| selected.contains(&job.id))
+}
+
+/// Select every job in `jobs`, or clear the selection if they already are.
+pub fn toggle_all(selected: &mut HashSet<JobId>, jobs: &[Job]) {
+    if all_selected(selected, jobs) {
+        selected.clear();
+    } else {
+        selected.extend(jobs.iter().map(|job| job.id));
+    }
+}
+
+/// Drop selected jobs that are no longer listed, e.g. after a refresh.
+pub fn retain_listed(selected: &mut HashSet<JobId>, jobs: &[Job]) {
+    selected.retain(|id| jobs.iter().any(|job| job.id == *id));
+}
+
+/// Props for BulkActionBar component.
+#[derive(Props, Clone, PartialEq)]
+pub struct BulkActionBarProps {
+    /// The selected jobs.
+    pub selected: Vec<Job>,
+    /// Disable every action, e.g. while one is in flight.
+    #[props(default = false)]
+    pub busy: bool,
+    /// Callback when an action is clicked.
+    pub on_action: EventHandler<BulkAction>,
+    /// Callback to clear the selection.
+    pub on_clear: EventHandler<()>,
+}
+
+/// Toolbar for acting on the selected jobs.
+///
+/// Actions that don't apply to the whole selection are disabled.
+#[component]
+pub fn BulkActionBar(props: BulkActionBarProps) -> Element {
+    let selected: Vec<&Job> = props.selected.iter().collect();
+    let on_action = props.on_action;
+    let on_clear = props.on_clear;
+
+    rsx! {
+        div { class: "bulk-action-bar",
+            span { class: "bulk-action-count", "{selected.len()} selected" }
+            for action in BulkAction::ALL {
+                button {
+                    key: "{action.label()}",
+                    class: "btn btn-small {action.class()}",
+                    disabled: props.busy || !action.applies_to(&selected),
+                    onclick: move |_| on_action.call(action),
+                    "{action.label()}"
+                }
+            }
+            button {
+                class: "btn btn-small btn-secondary",
+                onclick: move |_| on_clear.call(()),
+                "Clear"
+            }
+        }
+    }
+}
@@ -19,16 +19,72 @@ pub fn CreateJobForm(props: CreateJobFormProps) -> Element {
     let mut job_type = use_signal(|| "echo".to_string());
     let mut payload = use_signal(|| r#"{"message": "Hello, world!"}"#.to_string());
     let mut priority = use_signal(|| "normal".to_string());
+    let mut delay = use_signal(String::new);
     let mut error = use_signal(|| None::<String>);
     let mut submitting = use_signal(|| false);
+    let queue_id_for_types = props.queue_id.clone();
+    let job_types = use_resource(move || api::list_job_types(Some(queue_id_for_types.clone())));
+
+    // Sample payload of a listed job type
+    let sample_for = move |name: &str| -> Option<serde_json::Value> {
+        job_types
+            .read()
+            .as_ref()?
+            .as_ref()
+            .ok()?
+            .iter()
+            .find(|info| info.job_type == name)?
+            .sample_payload
+            .clone()
+    };
+
+    // Switching type prefills the payload with that type's sample, if it has one
+    let on_type_change = move |e: Event<FormData>| {
+        if let Some(sample) = sample_for(&e.value()) {
+            payload.set(serde_json::to_string_pretty(&sample).unwrap_or_default());
+        }
+        job_type.set(e.value());
+    };
+
+    // Test fire - enqueue the selected type with its sample payload as-is
+    let queue_id_for_test = props.queue_id.clone();
+    let test_fire = move |_| {
+        let request = api::CreateJobRequest {
+            queue_id: queue_id_for_test.clone(),
+            job_type: job_type(),
+            payload: sample_for(&job_type()).unwrap_or_else(|| serde_json::json!({})),
+            priority: None,
+            max_retries: None,
+            timeout_secs: None,
+            tags: vec![],
+            delay_secs: None,
+        };
+
+        spawn(async move {
+            submitting.set(true);
+            error.set(None);
+            match api::enqueue_job(request).await {
+                Ok(_job) => props.on_created.call(()),
+                Err(e) => error.set(Some(format!("Failed to test fire job: {}", e))),
+            }
+            submitting.set(false);
+        });
+    };
 
     let queue_id = props.queue_id.clone();
 
+    // Registered job types; until they load, just the current selection
+    let type_options: Vec<String> = match &*job_types.read() {
+        Some(Ok(types)) => types.iter().map(|info| info.job_type.clone()).collect(),
+        _ => vec![job_type()],
+    };
+
     let submit = move |_| {
         let queue_id = queue_id.clone();
         let job_type_val = job_type();
         let payload_val = payload();
         let priority_val = priority();
+        let delay_val = delay();
 
         spawn(async move {
             submitting.set(true);
@@ -44,6 +100,19 @@ pub fn CreateJobForm(props: CreateJobFormProps) -> Element {
                 }
             };
 
+            // Blank means run as soon as possible
+            let delay_secs = match delay_val.trim() {
+                "" => None,
+                value => match value.parse::<u64>() {
+                    Ok(secs) => Some(secs),
+                    Err(_) => {
+                        error.set(Some(format!("Invalid delay: {}", value)));
+                        submitting.set(false);
+                        return;
+                    }
+                },
+            };
+
             let request = api::CreateJobRequest {
                 queue_id,
                 job_type: job_type_val,
@@ -52,6 +121,7 @@ pub fn CreateJobForm(props: CreateJobFormProps) -> Element {
                 max_retries: None,
                 timeout_secs: None,
                 tags: vec![],
+                delay_secs,
             };
 
             match api::enqueue_job(request).await {
@@ -79,11 +149,11 @@ pub fn CreateJobForm(props: CreateJobFormProps) -> Element {
                 label { "Job Type" }
                 select {
                     value: "{job_type}",
-                    onchange: move |e| job_type.set(e.value()),
+                    onchange: on_type_change,
 
-                    option { value: "echo", "Echo" }
-                    option { value: "sleep", "Sleep" }
-                    option { value: "fail", "Fail (for testing)" }
+                    for name in type_options {
+                        option { key: "{name}", value: "{name}", "{name}" }
+                    }
                 }
             }
 
@@ -93,6 +163,7 @@ pub fn CreateJobForm(props: CreateJobFormProps) -> Element {
                     value: "{priority}",
                     onchange: move |e| priority.set(e.value()),
 
+                    option { value: "deferred", "Deferred" }
                     option { value: "low", "Low" }
                     option { value: "normal", "Normal" }
                     option { value: "high", "High" }
@@ -100,6 +171,17 @@ pub fn CreateJobForm(props: CreateJobFormProps) -> Element {
                 }
             }
 
+            div { class: "form-group",
+                label { "Delay (seconds)" }
+                input {
+                    r#type: "number",
+                    min: "0",
+                    placeholder: "Run immediately",
+                    value: "{delay}",
+                    oninput: move |e| delay.set(e.value()),
+                }
+            }
+
             div { class: "form-group",
                 label { "Payload (JSON)" }
                 textarea {
@@ -116,6 +198,13 @@ pub fn CreateJobForm(props: CreateJobFormProps) -> Element {
                     onclick: submit,
                     if submitting() { "Creating..." } else { "Create Job" }
                 }
+                button {
+                    class: "btn btn-secondary",
+                    title: "Enqueue this job type with its sample payload",
+                    disabled: submitting(),
+                    onclick: test_fire,
+                    "Test Fire"
+                }
                 button {
                     class: "btn btn-secondary",
                     onclick: move |_| props.on_cancel.call(()),
@@ -0,0 +1,161 @@
+//! Create queue form component.
+
+use dioxus::prelude::*;
+use queue_core::QueueConfig;
+
+/// Props for CreateQueueForm component.
+#[derive(Props, Clone, PartialEq)]
+pub struct CreateQueueFormProps {
+    /// Callback when queue is created.
+    pub on_created: EventHandler<()>,
+    /// Callback when form is cancelled.
+    pub on_cancel: EventHandler<()>,
+}
+
+/// Form component for creating a new queue.
+#[component]
+pub fn CreateQueueForm(props: CreateQueueFormProps) -> Element {
+    let defaults = QueueConfig::default();
+    let mut name = use_signal(String::new);
+    let mut description = use_signal(String::new);
+    let mut concurrency = use_signal(|| defaults.concurrency.to_string());
+    let mut max_queue_size = use_signal(String::new);
+    let mut tags = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut submitting = use_signal(|| false);
+
+    let submit = move |_| {
+        let name_val = name().trim().to_string();
+        let description_val = Some(description().trim().to_string()).filter(|d| !d.is_empty());
+        let concurrency_val = concurrency();
+        let max_queue_size_val = max_queue_size();
+        let tags_val: Vec<String> = tags()
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+
+        spawn(async move {
+            submitting.set(true);
+            error.set(None);
+
+            if name_val.is_empty() {
+                error.set(Some("Queue name is required".to_string()));
+                submitting.set(false);
+                return;
+            }
+
+            let concurrency: u32 = match concurrency_val.trim().parse() {
+                Ok(v) => v,
+                Err(_) => {
+                    error.set(Some("Concurrency must be a whole number".to_string()));
+                    submitting.set(false);
+                    return;
+                }
+            };
+
+            let max_queue_size: Option<usize> = match max_queue_size_val.trim() {
+                "" => None,
+                value => match value.parse() {
+                    Ok(v) => Some(v),
+                    Err(_) => {
+                        error.set(Some("Max queue size must be a whole number".to_string()));
+                        submitting.set(false);
+                        return;
+                    }
+                },
+            };
+
+            let config = QueueConfig {
+                concurrency,
+                max_queue_size,
+                ..Default::default()
+            };
+
+            match api::create_queue(name_val, description_val, Some(config), Some(tags_val)).await {
+                Ok(_queue) => {
+                    props.on_created.call(());
+                }
+                Err(e) => {
+                    error.set(Some(format!("Failed to create queue: {}", e)));
+                }
+            }
+
+            submitting.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "create-job-form",
+            h3 { "Create New Queue" }
+
+            if let Some(err) = error() {
+                div { class: "error-message", "{err}" }
+            }
+
+            div { class: "form-group",
+                label { "Name" }
+                input {
+                    r#type: "text",
+                    placeholder: "emails",
+                    value: "{name}",
+                    oninput: move |e| name.set(e.value()),
+                }
+            }
+
+            div { class: "form-group",
+                label { "Description" }
+                input {
+                    r#type: "text",
+                    value: "{description}",
+                    oninput: move |e| description.set(e.value()),
+                }
+            }
+
+            div { class: "form-group",
+                label { "Concurrency" }
+                input {
+                    r#type: "number",
+                    min: "0",
+                    value: "{concurrency}",
+                    oninput: move |e| concurrency.set(e.value()),
+                }
+            }
+
+            div { class: "form-group",
+                label { "Max Queue Size" }
+                input {
+                    r#type: "number",
+                    min: "1",
+                    placeholder: "Unlimited",
+                    value: "{max_queue_size}",
+                    oninput: move |e| max_queue_size.set(e.value()),
+                }
+            }
+
+            div { class: "form-group",
+                label { "Tags" }
+                input {
+                    r#type: "text",
+                    placeholder: "batch, nightly",
+                    value: "{tags}",
+                    oninput: move |e| tags.set(e.value()),
+                }
+            }
+
+            div { class: "form-actions",
+                button {
+                    class: "btn btn-primary",
+                    disabled: submitting() || name().trim().is_empty(),
+                    onclick: submit,
+                    if submitting() { "Creating..." } else { "Create Queue" }
+                }
+                button {
+                    class: "btn btn-secondary",
+                    onclick: move |_| props.on_cancel.call(()),
+                    "Cancel"
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Main admin dashboard component.
 
 use dioxus::prelude::*;
-use queue_core::{Job, Queue};
+use queue_core::{CancellationReason, Job, Queue};
 
 use super::{CreateJobForm, JobDetail, JobList, QueueList};
 
@@ -88,8 +88,7 @@ pub fn AdminDashboard() -> Element {
         let job_id = job.id.to_string();
         let queue = selected_queue().clone();
         spawn(async move {
-            if let Err(e) = api::cancel_job(job_id, Some("Cancelled from admin".to_string())).await
-            {
+            if let Err(e) = api::cancel_job(job_id, Some(CancellationReason::UserRequested)).await {
                 error.set(Some(format!("Failed to cancel job: {}", e)));
             } else if let Some(q) = queue {
                 // Refresh jobs
@@ -0,0 +1,59 @@
+//! Job attempts component for displaying each run of a job as a timeline.
+
+use dioxus::prelude::*;
+use queue_core::{AttemptRecord, duration_ms};
+
+/// Card listing a job's attempts, oldest first, with their outcome.
+#[component]
+pub fn JobAttempts(attempts: Vec<AttemptRecord>) -> Element {
+    rsx! {
+        div { class: "card",
+            div { class: "card-header",
+                h2 { class: "card-title", "Attempts" }
+            }
+            div { class: "card-body",
+                if attempts.is_empty() {
+                    div { class: "empty-state",
+                        p { "Not run yet" }
+                    }
+                } else {
+                    ol { class: "attempt-timeline",
+                        for attempt in attempts.iter() {
+                            {
+                                let started = attempt.started_at.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+                                let (outcome, detail) = match (attempt.finished_at, &attempt.error) {
+                                    (None, _) => ("running", "Running".to_string()),
+                                    (Some(finished_at), None) => (
+                                        "succeeded",
+                                        format!(
+                                            "Succeeded in {} ms",
+                                            duration_ms(attempt.started_at, finished_at)
+                                        ),
+                                    ),
+                                    (Some(finished_at), Some(error)) => (
+                                        "failed",
+                                        format!(
+                                            "Failed after {} ms: {}",
+                                            duration_ms(attempt.started_at, finished_at),
+                                            error
+                                        ),
+                                    ),
+                                };
+                                rsx! {
+                                    li { key: "{attempt.attempt}", class: "attempt attempt-{outcome}",
+                                        div { class: "attempt-header",
+                                            span { class: "attempt-number", "Attempt {attempt.attempt}" }
+                                            span { class: "attempt-worker", "{attempt.worker_id}" }
+                                            span { class: "attempt-time tabular-nums", "{started}" }
+                                        }
+                                        p { class: "attempt-detail", "{detail}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,10 @@
 //! Job detail panel component.
 
+use chrono::Utc;
 use dioxus::prelude::*;
 use queue_core::{Job, JobStatus};
 
-use super::StatusBadge;
+use super::{ScheduledCountdown, StatusBadge};
 
 /// Props for JobDetail component.
 #[derive(Props, Clone, PartialEq)]
@@ -25,6 +26,7 @@ pub fn JobDetail(props: JobDetailProps) -> Element {
     let status_str = job.status.as_str().to_string();
     let can_cancel = !job.status.is_terminal();
     let can_retry = job.status.can_retry();
+    let scheduled_at = job.run_at.filter(|_| job.is_scheduled(Utc::now()));
 
     // Clone job for each closure that needs it
     let job_for_cancel = job.clone();
@@ -49,7 +51,7 @@ pub fn JobDetail(props: JobDetailProps) -> Element {
             completed_at,
             result,
         } => {
-            let duration = (*completed_at - *started_at).num_seconds();
+            let duration = queue_core::duration_ms(*started_at, *completed_at) / 1000;
             Some(format!("Completed in {}s: {}", duration, result.summary))
         }
         JobStatus::Failed {
@@ -58,7 +60,7 @@ pub fn JobDetail(props: JobDetailProps) -> Element {
             error,
             attempts,
         } => {
-            let duration = (*failed_at - *started_at).num_seconds();
+            let duration = queue_core::duration_ms(*started_at, *failed_at) / 1000;
             Some(format!(
                 "Failed after {}s (attempt {}): {}",
                 duration, attempts, error
@@ -68,7 +70,9 @@ pub fn JobDetail(props: JobDetailProps) -> Element {
             cancelled_at,
             reason,
         } => {
-            let reason_str = reason.as_deref().unwrap_or("No reason");
+            let reason_str = reason
+                .as_ref()
+                .map_or_else(|| "No reason".to_string(), |r| r.to_string());
             Some(format!(
                 "Cancelled at {}: {}",
                 cancelled_at.format("%H:%M:%S"),
@@ -78,9 +82,15 @@ pub fn JobDetail(props: JobDetailProps) -> Element {
         _ => None,
     };
 
-    // Format payload
+    // Format payload, and output for completed jobs that returned one
     let payload_json =
         serde_json::to_string_pretty(&job.payload).unwrap_or_else(|_| "{}".to_string());
+    let output_json = match &job.status {
+        JobStatus::Completed { result, .. } => result.output.as_ref().map(|output| {
+            serde_json::to_string_pretty(output).unwrap_or_else(|_| "{}".to_string())
+        }),
+        _ => None,
+    };
 
     rsx! {
         div { class: "job-detail-panel",
@@ -121,6 +131,15 @@ pub fn JobDetail(props: JobDetailProps) -> Element {
                     }
                 }
 
+                if let Some(run_at) = scheduled_at {
+                    div { class: "detail-row",
+                        span { class: "detail-label", "Scheduled" }
+                        span { class: "detail-value",
+                            ScheduledCountdown { run_at }
+                        }
+                    }
+                }
+
                 div { class: "detail-row",
                     span { class: "detail-label", "Created" }
                     span { class: "detail-value", "{created}" }
@@ -156,6 +175,13 @@ pub fn JobDetail(props: JobDetailProps) -> Element {
                     h4 { "Payload" }
                     pre { class: "payload-json", "{payload_json}" }
                 }
+
+                if let Some(output_json) = output_json {
+                    div { class: "detail-section",
+                        h4 { "Output" }
+                        pre { class: "payload-json", "{output_json}" }
+                    }
+                }
             }
 
             div { class: "job-detail-actions",
@@ -163,7 +189,7 @@ pub fn JobDetail(props: JobDetailProps) -> Element {
                     button {
                         class: "btn btn-cancel",
                         onclick: move |_| props.on_cancel.call(job_for_cancel.clone()),
-                        "Cancel Job"
+                        if scheduled_at.is_some() { "Cancel Before It Runs" } else { "Cancel Job" }
                     }
                 }
                 if can_retry {
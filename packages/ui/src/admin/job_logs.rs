@@ -0,0 +1,38 @@
+//! Job logs component for displaying the lines a handler logged.
+
+use dioxus::prelude::*;
+use queue_core::JobLogLine;
+
+/// Card listing a job's captured log lines, oldest first.
+#[component]
+pub fn JobLogs(logs: Vec<JobLogLine>) -> Element {
+    rsx! {
+        div { class: "card",
+            div { class: "card-header",
+                h2 { class: "card-title", "Logs" }
+            }
+            div { class: "card-body",
+                if logs.is_empty() {
+                    div { class: "empty-state",
+                        p { "No logs captured" }
+                    }
+                } else {
+                    div { class: "job-logs",
+                        for (index, line) in logs.iter().enumerate() {
+                            {
+                                let time = line.timestamp.format("%H:%M:%S%.3f").to_string();
+                                rsx! {
+                                    div { key: "{index}", class: "job-log-line job-log-{line.level}",
+                                        span { class: "job-log-time tabular-nums", "{time}" }
+                                        span { class: "job-log-level", "{line.level}" }
+                                        span { class: "job-log-message", "{line.message}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
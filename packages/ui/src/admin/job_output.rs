@@ -0,0 +1,28 @@
+//! Job output component for displaying what a completed job returned.
+
+use dioxus::prelude::*;
+
+/// Collapsible card showing a completed job's output as pretty-printed JSON.
+#[component]
+pub fn JobOutput(output: serde_json::Value) -> Element {
+    let mut expanded = use_signal(|| true);
+    let output_json = serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string());
+
+    rsx! {
+        div { class: "card",
+            div { class: "card-header",
+                h2 { class: "card-title", "Output" }
+                button {
+                    class: "btn btn-secondary btn-small",
+                    onclick: move |_| expanded.toggle(),
+                    if expanded() { "Hide" } else { "Show" }
+                }
+            }
+            if expanded() {
+                div { class: "card-body",
+                    pre { class: "payload-json", "{output_json}" }
+                }
+            }
+        }
+    }
+}
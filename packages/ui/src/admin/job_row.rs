@@ -35,7 +35,7 @@ pub fn JobRow(props: JobRowProps) -> Element {
 
             td { class: "job-id", "{job.id}" }
             td { class: "job-type", "{job.job_type}" }
-            td { class: "job-priority", "{job.priority}" }
+            td { class: "job-priority priority-{job.priority}", "{job.priority}" }
             td { class: "job-status",
                 StatusBadge { status: status_str }
             }
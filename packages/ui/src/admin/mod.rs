@@ -1,21 +1,41 @@
 //! Admin dashboard components for the job queue system.
 
+mod bulk_actions;
 mod create_job_form;
+mod create_queue_form;
 mod dashboard;
+mod job_attempts;
 mod job_detail;
 mod job_list;
+mod job_logs;
+mod job_output;
 mod job_row;
 mod pages;
+mod queue_actions;
 mod queue_card;
+mod queue_health;
 mod queue_list;
+mod scheduled_countdown;
 mod status_badge;
+mod worker_table;
 
+pub use bulk_actions::{BulkAction, BulkActionBar, all_selected, retain_listed, toggle_all};
 pub use create_job_form::CreateJobForm;
+pub use create_queue_form::CreateQueueForm;
 pub use dashboard::AdminDashboard;
+pub use job_attempts::JobAttempts;
 pub use job_detail::JobDetail;
 pub use job_list::JobList;
+pub use job_logs::JobLogs;
+pub use job_output::JobOutput;
 pub use job_row::JobRow;
-pub use pages::{AdminJobDetailPage, AdminQueueDetailPage, AdminQueuesPage};
+pub use pages::{
+    AdminJobDetailPage, AdminJobsPage, AdminQueueDetailPage, AdminQueuesPage, AdminWorkersPage,
+};
+pub use queue_actions::{QueueAction, QueueActions};
 pub use queue_card::QueueCard;
+pub use queue_health::{HealthDot, QueueHealth};
 pub use queue_list::QueueList;
-pub use status_badge::{StateBadge, StatusBadge};
+pub use scheduled_countdown::{ScheduledCountdown, format_countdown};
+pub use status_badge::{StateBadge, StatusBadge, WorkerBadge};
+pub use worker_table::WorkerTable;
@@ -1,9 +1,10 @@
 //! Job detail page - displays a single job with full details.
 
+use chrono::Utc;
 use dioxus::prelude::*;
-use queue_core::{Job, JobStatus, Queue};
+use queue_core::{CancellationReason, Job, JobRecordView, JobStatus, Queue};
 
-use crate::admin::StatusBadge;
+use crate::admin::{JobAttempts, JobLogs, JobOutput, ScheduledCountdown, StatusBadge};
 
 /// Refresh interval in milliseconds (5 seconds).
 const REFRESH_INTERVAL_MS: u32 = 5000;
@@ -23,6 +24,7 @@ pub fn AdminJobDetailPage(props: AdminJobDetailPageProps) -> Element {
 
     let mut queue = use_signal(|| None::<Queue>);
     let mut job = use_signal(|| None::<Job>);
+    let mut archived = use_signal(|| None::<JobRecordView>);
     let mut error = use_signal(|| None::<String>);
 
     // Auto-refresh: fetch job every 5 seconds
@@ -40,9 +42,16 @@ pub fn AdminJobDetailPage(props: AdminJobDetailPageProps) -> Element {
                     queue.set(Some(q));
                 }
 
-                // Load job
-                if let Ok(Some(j)) = api::get_job(jid.clone()).await {
-                    job.set(Some(j));
+                // Load job, falling back to history once it has been archived
+                match api::get_job(jid.clone()).await {
+                    Ok(Some(j)) => job.set(Some(j)),
+                    Ok(None) => {
+                        if let Ok(Some(view)) = api::find_job(jid.clone()).await {
+                            job.set(None);
+                            archived.set(Some(view));
+                        }
+                    }
+                    Err(_) => {}
                 }
 
                 // Wait before next refresh
@@ -62,10 +71,12 @@ pub fn AdminJobDetailPage(props: AdminJobDetailPageProps) -> Element {
         let jid = job_id_for_cancel.clone();
         spawn(async move {
             if let Err(e) =
-                api::cancel_job(jid.clone(), Some("Cancelled from admin".to_string())).await
+                api::cancel_job(jid.clone(), Some(CancellationReason::UserRequested)).await
             {
                 error.set(Some(format!("Failed to cancel job: {}", e)));
-            } else if let Ok(Some(j)) = api::get_job(jid).await {
+            }
+            // Refresh either way: a job that finished in the meantime loses its cancel button
+            if let Ok(Some(j)) = api::get_job(jid).await {
                 job.set(Some(j));
             }
         });
@@ -101,9 +112,17 @@ pub fn AdminJobDetailPage(props: AdminJobDetailPageProps) -> Element {
                 {
                     let status_str = j.status.as_str().to_string();
                     let can_cancel = !j.status.is_terminal();
+                    let scheduled_at = j.run_at.filter(|_| j.is_scheduled(Utc::now()));
+                    let scheduled_for = scheduled_at
+                        .map(|run_at| run_at.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                        .unwrap_or_default();
                     let created = j.created_at.format("%Y-%m-%d %H:%M:%S UTC").to_string();
                     let updated = j.updated_at.format("%Y-%m-%d %H:%M:%S UTC").to_string();
                     let payload_json = serde_json::to_string_pretty(&j.payload).unwrap_or_else(|_| "{}".to_string());
+                    let output = match &j.status {
+                        JobStatus::Completed { result, .. } => result.output.clone(),
+                        _ => None,
+                    };
 
                     // Extract status details
                     let status_details = match &j.status {
@@ -113,15 +132,15 @@ pub fn AdminJobDetailPage(props: AdminJobDetailPageProps) -> Element {
                             worker_id
                         )),
                         JobStatus::Completed { started_at, completed_at, result } => {
-                            let duration = (*completed_at - *started_at).num_seconds();
+                            let duration = queue_core::duration_ms(*started_at, *completed_at) / 1000;
                             Some(format!("Completed in {}s — {}", duration, result.summary))
                         }
                         JobStatus::Failed { started_at, failed_at, error, attempts } => {
-                            let duration = (*failed_at - *started_at).num_seconds();
+                            let duration = queue_core::duration_ms(*started_at, *failed_at) / 1000;
                             Some(format!("Failed after {}s (attempt {}) — {}", duration, attempts, error))
                         }
                         JobStatus::Cancelled { cancelled_at, reason } => {
-                            let reason_str = reason.as_deref().unwrap_or("No reason provided");
+                            let reason_str = reason.as_ref().map_or_else(|| "No reason provided".to_string(), |r| r.to_string());
                             Some(format!("Cancelled at {} — {}", cancelled_at.format("%H:%M:%S"), reason_str))
                         }
                         _ => None,
@@ -143,12 +162,20 @@ pub fn AdminJobDetailPage(props: AdminJobDetailPageProps) -> Element {
                                     button {
                                         class: "btn btn-cancel",
                                         onclick: on_cancel,
-                                        "Cancel Job"
+                                        if scheduled_at.is_some() { "Cancel Before It Runs" } else { "Cancel Job" }
                                     }
                                 }
                             }
                         }
 
+                        // Scheduled jobs can still be cancelled before they run
+                        if let Some(run_at) = scheduled_at {
+                            div { class: "status-message status-message-scheduled",
+                                "Scheduled for {scheduled_for} · "
+                                ScheduledCountdown { run_at }
+                            }
+                        }
+
                         // Status message (if any)
                         if let Some(details) = status_details {
                             div { class: "status-message status-message-{status_str}",
@@ -228,6 +255,78 @@ pub fn AdminJobDetailPage(props: AdminJobDetailPageProps) -> Element {
                                 pre { class: "payload-json", "{payload_json}" }
                             }
                         }
+
+                        if let Some(output) = output {
+                            JobOutput { output }
+                        }
+
+                        JobAttempts { attempts: j.attempts_log.clone() }
+
+                        JobLogs { logs: j.logs.clone() }
+                    }
+                }
+            } else if let Some(view) = archived() {
+                {
+                    let created = view.created_at.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+                    let finished = view.updated_at.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+                    let outcome = view.result_summary.clone().or_else(|| view.error.clone());
+
+                    rsx! {
+                        div { class: "page-header",
+                            div { class: "page-header-content",
+                                div { class: "page-header-title-row",
+                                    h1 { class: "page-title", "Job Details" }
+                                    StatusBadge { status: view.status.clone() }
+                                }
+                                p { class: "page-description job-id-display", "{view.id}" }
+                            }
+                        }
+
+                        div { class: "status-message status-message-{view.status}",
+                            "This job has been archived; payload and configuration are no longer available."
+                        }
+
+                        div { class: "card",
+                            div { class: "card-header",
+                                h2 { class: "card-title", "Archived Record" }
+                            }
+                            div { class: "card-body",
+                                div { class: "detail-list",
+                                    div { class: "detail-item",
+                                        span { class: "detail-label", "Job Type" }
+                                        span { class: "detail-value", "{view.job_type}" }
+                                    }
+                                    div { class: "detail-item",
+                                        span { class: "detail-label", "Attempts" }
+                                        span { class: "detail-value", "{view.attempts}" }
+                                    }
+                                    if let Some(duration) = view.duration_ms {
+                                        div { class: "detail-item",
+                                            span { class: "detail-label", "Duration" }
+                                            span { class: "detail-value tabular-nums", "{duration} ms" }
+                                        }
+                                    }
+                                    if let Some(outcome) = outcome {
+                                        div { class: "detail-item",
+                                            span { class: "detail-label", "Outcome" }
+                                            span { class: "detail-value", "{outcome}" }
+                                        }
+                                    }
+                                    div { class: "detail-item",
+                                        span { class: "detail-label", "Created" }
+                                        span { class: "detail-value tabular-nums", "{created}" }
+                                    }
+                                    div { class: "detail-item",
+                                        span { class: "detail-label", "Archived" }
+                                        span { class: "detail-value tabular-nums", "{finished}" }
+                                    }
+                                }
+                            }
+                        }
+
+                        JobAttempts { attempts: view.attempts_log.clone() }
+
+                        JobLogs { logs: view.logs.clone() }
                     }
                 }
             } else {
@@ -0,0 +1,118 @@
+//! Jobs page - searches jobs across all queues, including archived history.
+
+use dioxus::prelude::*;
+use queue_core::JobRecordView;
+
+use crate::admin::StatusBadge;
+
+/// Refresh interval in milliseconds (5 seconds).
+const REFRESH_INTERVAL_MS: u32 = 5000;
+
+/// Status filters offered on the page (empty string means all).
+const STATUS_FILTERS: &[(&str, &str)] = &[
+    ("", "All"),
+    ("pending", "Pending"),
+    ("scheduled", "Scheduled"),
+    ("running", "Running"),
+    ("completed", "Completed"),
+    ("failed", "Failed"),
+    ("cancelled", "Cancelled"),
+];
+
+/// Global jobs page component.
+#[component]
+pub fn AdminJobsPage() -> Element {
+    let mut jobs = use_signal(Vec::<JobRecordView>::new);
+    let mut status_filter = use_signal(String::new);
+
+    // Auto-refresh: search jobs every 5 seconds (and whenever the filter changes)
+    let _refresh = use_resource(move || async move {
+        loop {
+            let status = Some(status_filter()).filter(|s| !s.is_empty());
+            match api::find_jobs(status, None, Some(100)).await {
+                Ok(found) => jobs.set(found),
+                Err(e) => {
+                    tracing::error!("Failed to search jobs: {}", e);
+                }
+            }
+
+            // Wait before next refresh
+            #[cfg(target_arch = "wasm32")]
+            gloo_timers::future::TimeoutFuture::new(REFRESH_INTERVAL_MS).await;
+
+            #[cfg(not(target_arch = "wasm32"))]
+            tokio::time::sleep(std::time::Duration::from_millis(REFRESH_INTERVAL_MS as u64)).await;
+        }
+    });
+
+    rsx! {
+        div { class: "page-container",
+            // Page header
+            div { class: "page-header",
+                div { class: "page-header-content",
+                    h1 { class: "page-title", "Jobs" }
+                    p { class: "page-description", "Search active and archived jobs across all queues" }
+                }
+                div { class: "page-header-actions",
+                    select {
+                        value: "{status_filter}",
+                        onchange: move |e| status_filter.set(e.value()),
+                        for (value, label) in STATUS_FILTERS.iter() {
+                            option { value: "{value}", "{label}" }
+                        }
+                    }
+                    span { class: "auto-refresh-indicator", "Auto-refreshing" }
+                }
+            }
+
+            div { class: "card",
+                if jobs().is_empty() {
+                    div { class: "empty-state",
+                        p { "No jobs found" }
+                    }
+                } else {
+                    div { class: "table-container",
+                        table { class: "data-table",
+                            thead {
+                                tr {
+                                    th { "ID" }
+                                    th { "Type" }
+                                    th { "Status" }
+                                    th { "Queue" }
+                                    th { "Created" }
+                                    th { "Source" }
+                                }
+                            }
+                            tbody {
+                                for job in jobs().iter() {
+                                    {
+                                        let created = job.created_at.format("%Y-%m-%d %H:%M:%S").to_string();
+                                        let source = if job.is_archived() { "History" } else { "Active" };
+
+                                        rsx! {
+                                            tr { key: "{job.id}", class: "data-row",
+                                                td { class: "job-id",
+                                                    Link {
+                                                        to: "/admin/queues/{job.queue_id}/jobs/{job.id}",
+                                                        "{job.id}"
+                                                    }
+                                                }
+                                                td { "{job.job_type}" }
+                                                td {
+                                                    StatusBadge { status: job.status.clone() }
+                                                }
+                                                td { class: "job-id", "{job.queue_id}" }
+                                                td { class: "tabular-nums", "{created}" }
+                                                td { "{source}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
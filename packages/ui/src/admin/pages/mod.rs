@@ -1,9 +1,13 @@
 //! Admin page components for route-based navigation.
 
 mod job_detail_page;
+mod jobs_page;
 mod queue_detail_page;
 mod queues_page;
+mod workers_page;
 
 pub use job_detail_page::AdminJobDetailPage;
+pub use jobs_page::AdminJobsPage;
 pub use queue_detail_page::AdminQueueDetailPage;
 pub use queues_page::AdminQueuesPage;
+pub use workers_page::AdminWorkersPage;
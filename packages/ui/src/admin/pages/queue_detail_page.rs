@@ -1,13 +1,102 @@
 //! Queue detail page - displays a single queue with its jobs.
 
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
 use dioxus::prelude::*;
-use queue_core::{Job, Queue, QueueState};
+use queue_core::{CancellationReason, Job, JobId, Queue};
 
-use crate::admin::{CreateJobForm, StateBadge, StatusBadge};
+use crate::admin::{
+    BulkAction, BulkActionBar, CreateJobForm, QueueAction, QueueActions, StateBadge, StatusBadge,
+    all_selected, retain_listed, toggle_all,
+};
 
 /// Refresh interval in milliseconds (5 seconds).
 const REFRESH_INTERVAL_MS: u32 = 5000;
 
+/// Status filters offered above the jobs table (empty string means all).
+const STATUS_FILTERS: &[(&str, &str)] = &[
+    ("", "All"),
+    ("pending", "Pending"),
+    ("scheduled", "Scheduled"),
+    ("running", "Running"),
+    ("completed", "Completed"),
+    ("failed", "Failed"),
+    ("cancelled", "Cancelled"),
+];
+
+/// Load a queue's jobs matching `status`, with the unfiltered total.
+async fn load_jobs(
+    queue_id: String,
+    status: Option<String>,
+) -> Result<(Vec<Job>, usize), ServerFnError> {
+    let matching = api::list_queue_jobs(queue_id.clone(), status.clone(), Some(100)).await?;
+    let total = match status {
+        Some(_) => api::list_queue_jobs(queue_id, None, Some(100)).await?.len(),
+        None => matching.len(),
+    };
+    Ok((matching, total))
+}
+
+/// Jobs table column that can be sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortColumn {
+    Type,
+    Priority,
+    Status,
+    Created,
+}
+
+impl SortColumn {
+    /// Sortable columns in table order.
+    const ALL: [SortColumn; 4] = [
+        SortColumn::Type,
+        SortColumn::Priority,
+        SortColumn::Status,
+        SortColumn::Created,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            SortColumn::Type => "Type",
+            SortColumn::Priority => "Priority",
+            SortColumn::Status => "Status",
+            SortColumn::Created => "Created",
+        }
+    }
+
+    /// Ascending order for this column; priority follows the `Priority` enum.
+    fn compare(self, a: &Job, b: &Job) -> Ordering {
+        match self {
+            SortColumn::Type => a.job_type.cmp(&b.job_type),
+            SortColumn::Priority => a.priority.cmp(&b.priority),
+            SortColumn::Status => a.status.as_str().cmp(b.status.as_str()),
+            SortColumn::Created => a.created_at.cmp(&b.created_at),
+        }
+    }
+}
+
+/// Active sort for the jobs table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct JobSort {
+    column: SortColumn,
+    ascending: bool,
+}
+
+impl JobSort {
+    /// Sort jobs in place by this column and direction.
+    fn apply(self, jobs: &mut [Job]) {
+        jobs.sort_by(|a, b| {
+            let ordering = self.column.compare(a, b);
+            if self.ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+}
+
 /// Props for AdminQueueDetailPage.
 #[derive(Props, Clone, PartialEq)]
 pub struct AdminQueueDetailPageProps {
@@ -20,7 +109,15 @@ pub fn AdminQueueDetailPage(props: AdminQueueDetailPageProps) -> Element {
     let queue_id = props.queue_id.clone();
     let mut queue = use_signal(|| None::<Queue>);
     let mut jobs = use_signal(Vec::<Job>::new);
+    let mut total_jobs = use_signal(|| 0usize);
+    let mut status_filter = use_signal(String::new);
     let mut show_create_form = use_signal(|| false);
+    let mut show_delete_confirm = use_signal(|| false);
+    let mut delete_confirm_name = use_signal(String::new);
+    let mut deleting = use_signal(|| false);
+    let mut sort = use_signal(|| None::<JobSort>);
+    let mut selected = use_signal(HashSet::<JobId>::new);
+    let mut bulk_busy = use_signal(|| false);
     let mut error = use_signal(|| None::<String>);
 
     // Auto-refresh: fetch queue and jobs every 5 seconds
@@ -36,9 +133,11 @@ pub fn AdminQueueDetailPage(props: AdminQueueDetailPageProps) -> Element {
                     queue.set(Some(q));
                 }
 
-                // Load jobs
-                if let Ok(j) = api::list_queue_jobs(qid.clone(), None, Some(100)).await {
+                // Load jobs matching the selected status
+                let status = Some(status_filter()).filter(|s| !s.is_empty());
+                if let Ok((j, total)) = load_jobs(qid.clone(), status).await {
                     jobs.set(j);
+                    total_jobs.set(total);
                 }
 
                 // Wait before next refresh
@@ -52,39 +151,54 @@ pub fn AdminQueueDetailPage(props: AdminQueueDetailPageProps) -> Element {
         }
     });
 
+    // Keep the selection to jobs still listed after a refresh or filter change
+    use_effect(move || {
+        let listed = jobs();
+        retain_listed(&mut selected.write(), &listed);
+    });
+
     // Job created handler - trigger immediate refresh
     let queue_id_for_created = queue_id.clone();
     let on_job_created = move |_| {
         show_create_form.set(false);
         let qid = queue_id_for_created.clone();
+        let status = Some(status_filter()).filter(|s| !s.is_empty());
         spawn(async move {
-            if let Ok(j) = api::list_queue_jobs(qid, None, Some(100)).await {
+            if let Ok((j, total)) = load_jobs(qid, status).await {
                 jobs.set(j);
+                total_jobs.set(total);
             }
         });
     };
 
-    // Pause/Resume handlers
-    let queue_id_for_pause = queue_id.clone();
-    let on_pause = move |_| {
-        let qid = queue_id_for_pause.clone();
+    // Status filter handler - refetch as soon as the selection changes
+    let queue_id_for_filter = queue_id.clone();
+    let on_filter_change = move |e: Event<FormData>| {
+        status_filter.set(e.value());
+        let qid = queue_id_for_filter.clone();
+        let status = Some(e.value()).filter(|s| !s.is_empty());
         spawn(async move {
-            if let Err(e) = api::pause_queue(qid.clone()).await {
-                error.set(Some(format!("Failed to pause queue: {}", e)));
-            } else if let Ok(queues) = api::list_queues().await
-                && let Some(q) = queues.into_iter().find(|q| q.id.to_string() == qid)
-            {
-                queue.set(Some(q));
+            match load_jobs(qid, status).await {
+                Ok((j, total)) => {
+                    jobs.set(j);
+                    total_jobs.set(total);
+                }
+                Err(e) => error.set(Some(format!("Failed to load jobs: {}", e))),
             }
         });
     };
 
-    let queue_id_for_resume = queue_id.clone();
-    let on_resume = move |_| {
-        let qid = queue_id_for_resume.clone();
+    // Lifecycle action handler (pause/resume/drain/stop/start)
+    let queue_id_for_action = queue_id.clone();
+    let on_action = move |action: QueueAction| {
+        let qid = queue_id_for_action.clone();
         spawn(async move {
-            if let Err(e) = api::resume_queue(qid.clone()).await {
-                error.set(Some(format!("Failed to resume queue: {}", e)));
+            if let Err(e) = action.perform(qid.clone()).await {
+                error.set(Some(format!(
+                    "Failed to {} queue: {}",
+                    action.label().to_lowercase(),
+                    e
+                )));
             } else if let Ok(queues) = api::list_queues().await
                 && let Some(q) = queues.into_iter().find(|q| q.id.to_string() == qid)
             {
@@ -93,6 +207,95 @@ pub fn AdminQueueDetailPage(props: AdminQueueDetailPageProps) -> Element {
         });
     };
 
+    // Requeue handler - returns jobs stuck in running to pending
+    let queue_id_for_requeue = queue_id.clone();
+    let on_requeue_running = move |_| {
+        let qid = queue_id_for_requeue.clone();
+        let status = Some(status_filter()).filter(|s| !s.is_empty());
+        spawn(async move {
+            if let Err(e) = api::requeue_running(qid.clone()).await {
+                error.set(Some(format!("Failed to requeue running jobs: {}", e)));
+            } else if let Ok((j, total)) = load_jobs(qid, status).await {
+                jobs.set(j);
+                total_jobs.set(total);
+            }
+        });
+    };
+
+    // Bulk action handler - applies to every selected job, then clears the selection
+    let queue_id_for_bulk = queue_id.clone();
+    let on_bulk_action = move |action: BulkAction| {
+        let qid = queue_id_for_bulk.clone();
+        let ids: Vec<String> = selected.read().iter().map(|id| id.to_string()).collect();
+        let status = Some(status_filter()).filter(|s| !s.is_empty());
+        spawn(async move {
+            bulk_busy.set(true);
+            match action.perform(qid.clone(), ids).await {
+                Ok(_) => {
+                    selected.write().clear();
+                    if let Ok((j, total)) = load_jobs(qid, status).await {
+                        jobs.set(j);
+                        total_jobs.set(total);
+                    }
+                }
+                Err(e) => error.set(Some(format!(
+                    "Failed to {}: {}",
+                    action.label().to_lowercase(),
+                    e
+                ))),
+            }
+            bulk_busy.set(false);
+        });
+    };
+
+    // Delete handler - only reachable once the queue name has been typed
+    let queue_id_for_delete = queue_id.clone();
+    let on_delete = move |_| {
+        let qid = queue_id_for_delete.clone();
+        spawn(async move {
+            deleting.set(true);
+            match api::delete_queue(qid, None).await {
+                Ok(()) => {
+                    navigator().push("/admin/queues");
+                }
+                Err(e) => {
+                    error.set(Some(format!("Failed to delete queue: {}", e)));
+                    show_delete_confirm.set(false);
+                }
+            }
+            deleting.set(false);
+        });
+    };
+
+    // Clicking a header sorts by it ascending, or flips the direction if already active
+    let mut on_sort = move |column: SortColumn| {
+        let next = match sort() {
+            Some(current) if current.column == column => JobSort {
+                column,
+                ascending: !current.ascending,
+            },
+            _ => JobSort {
+                column,
+                ascending: true,
+            },
+        };
+        sort.set(Some(next));
+    };
+
+    // Jobs in display order (server order until a column is chosen)
+    let sorted_jobs = {
+        let mut sorted = jobs();
+        if let Some(active) = sort() {
+            active.apply(&mut sorted);
+        }
+        sorted
+    };
+    let selected_jobs: Vec<Job> = sorted_jobs
+        .iter()
+        .filter(|job| selected.read().contains(&job.id))
+        .cloned()
+        .collect();
+
     rsx! {
         div { class: "page-container",
             // Breadcrumb
@@ -123,30 +326,87 @@ pub fn AdminQueueDetailPage(props: AdminQueueDetailPageProps) -> Element {
                             h1 { class: "page-title", "{q.name}" }
                             StateBadge { state: q.state }
                             span { class: "auto-refresh-indicator", "Auto-refreshing" }
+                            if q.stats.rate_limited {
+                                span {
+                                    class: "rate-limited-indicator",
+                                    title: "Pending jobs are held back by the queue's rate limit",
+                                    "Rate limited"
+                                }
+                            }
+                            if q.stats.breaker_open {
+                                span {
+                                    class: "breaker-open-indicator",
+                                    title: "Paused by the circuit breaker after a spike in failures",
+                                    "Circuit open"
+                                }
+                            }
                         }
                         if let Some(ref desc) = q.description {
                             p { class: "page-description", "{desc}" }
                         }
                     }
                     div { class: "page-header-actions",
-                        if q.state == QueueState::Paused {
-                            button {
-                                class: "btn btn-resume",
-                                onclick: on_resume,
-                                "Resume Queue"
-                            }
-                        } else {
-                            button {
-                                class: "btn btn-pause",
-                                onclick: on_pause,
-                                "Pause Queue"
-                            }
-                        }
+                        QueueActions { state: q.state, on_action: on_action }
                         button {
                             class: "btn btn-primary",
                             onclick: move |_| show_create_form.set(true),
                             "+ New Job"
                         }
+                        if q.stats.running > 0 {
+                            button {
+                                class: "btn btn-secondary",
+                                title: "Return running jobs to pending, e.g. after a crash",
+                                onclick: on_requeue_running,
+                                "Requeue Running"
+                            }
+                        }
+                        button {
+                            class: "btn btn-cancel",
+                            onclick: move |_| {
+                                delete_confirm_name.set(String::new());
+                                show_delete_confirm.set(true);
+                            },
+                            "Delete Queue"
+                        }
+                    }
+                }
+
+                // Delete confirmation - requires typing the queue name
+                if show_delete_confirm() {
+                    {
+                        let name_matches = delete_confirm_name() == q.name;
+
+                        rsx! {
+                            div { class: "create-job-form confirm-delete",
+                                h3 { "Delete Queue" }
+                                p { class: "confirm-delete-warning",
+                                    "This permanently deletes the queue and stops its workers. Type "
+                                    strong { "{q.name}" }
+                                    " to confirm."
+                                }
+                                div { class: "form-group",
+                                    input {
+                                        r#type: "text",
+                                        placeholder: "{q.name}",
+                                        value: "{delete_confirm_name}",
+                                        oninput: move |e| delete_confirm_name.set(e.value()),
+                                    }
+                                }
+                                div { class: "form-actions",
+                                    button {
+                                        class: "btn btn-cancel",
+                                        disabled: !name_matches || deleting(),
+                                        onclick: on_delete,
+                                        if deleting() { "Deleting..." } else { "Delete Queue" }
+                                    }
+                                    button {
+                                        class: "btn btn-secondary",
+                                        onclick: move |_| show_delete_confirm.set(false),
+                                        "Cancel"
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
 
@@ -183,30 +443,78 @@ pub fn AdminQueueDetailPage(props: AdminQueueDetailPageProps) -> Element {
                 div { class: "card",
                     div { class: "card-header",
                         h2 { class: "card-title", "Jobs" }
-                        span { class: "card-count", "{jobs().len()} total" }
+                        div { class: "card-header-actions",
+                            select {
+                                class: "status-filter",
+                                value: "{status_filter}",
+                                onchange: on_filter_change,
+                                for (value, label) in STATUS_FILTERS.iter() {
+                                    option { value: "{value}", "{label}" }
+                                }
+                            }
+                            if status_filter().is_empty() {
+                                span { class: "card-count", "{jobs().len()} total" }
+                            } else {
+                                span { class: "card-count", "{jobs().len()} of {total_jobs} match" }
+                            }
+                        }
                     }
 
                     if jobs().is_empty() {
                         div { class: "empty-state",
                             div { class: "empty-state-icon", "📋" }
-                            p { "No jobs in this queue" }
-                            p { class: "hint", "Create a job to get started" }
+                            if status_filter().is_empty() {
+                                p { "No jobs in this queue" }
+                                p { class: "hint", "Create a job to get started" }
+                            } else {
+                                p { "No {status_filter} jobs in this queue" }
+                            }
                         }
                     } else {
+                        if !selected_jobs.is_empty() {
+                            BulkActionBar {
+                                selected: selected_jobs.clone(),
+                                busy: bulk_busy(),
+                                on_action: on_bulk_action,
+                                on_clear: move |_| selected.write().clear(),
+                            }
+                        }
                         div { class: "table-container",
                             table { class: "data-table",
                                 thead {
                                     tr {
+                                        th { class: "select-cell",
+                                            input {
+                                                r#type: "checkbox",
+                                                title: "Select all",
+                                                checked: all_selected(&selected.read(), &jobs.read()),
+                                                onchange: move |_| toggle_all(&mut selected.write(), &jobs.read()),
+                                            }
+                                        }
                                         th { "ID" }
-                                        th { "Type" }
-                                        th { "Priority" }
-                                        th { "Status" }
-                                        th { "Created" }
+                                        for column in SortColumn::ALL {
+                                            {
+                                                let active = sort().filter(|s| s.column == column);
+                                                let indicator = match active {
+                                                    Some(s) if s.ascending => " ▲",
+                                                    Some(_) => " ▼",
+                                                    None => "",
+                                                };
+
+                                                rsx! {
+                                                    th {
+                                                        class: if active.is_some() { "sortable sorted" } else { "sortable" },
+                                                        onclick: move |_| on_sort(column),
+                                                        "{column.label()}{indicator}"
+                                                    }
+                                                }
+                                            }
+                                        }
                                         th { class: "text-right", "Actions" }
                                     }
                                 }
                                 tbody {
-                                    for job in jobs().iter() {
+                                    for job in sorted_jobs.iter() {
                                         {
                                             let job_for_row = job.clone();
                                             let job_for_cancel = job.clone();
@@ -215,9 +523,23 @@ pub fn AdminQueueDetailPage(props: AdminQueueDetailPageProps) -> Element {
                                             let created = job.created_at.format("%Y-%m-%d %H:%M:%S").to_string();
                                             let status_str = job.status.as_str().to_string();
                                             let can_cancel = !job.status.is_terminal();
+                                            let id = job.id;
+                                            let is_selected = selected.read().contains(&id);
 
                                             rsx! {
-                                                tr { class: "data-row",
+                                                tr { class: if is_selected { "data-row selected" } else { "data-row" },
+                                                    td { class: "select-cell",
+                                                        input {
+                                                            r#type: "checkbox",
+                                                            checked: is_selected,
+                                                            onchange: move |_| {
+                                                                let mut selected = selected.write();
+                                                                if !selected.remove(&id) {
+                                                                    selected.insert(id);
+                                                                }
+                                                            },
+                                                        }
+                                                    }
                                                     td {
                                                         Link {
                                                             to: "/admin/queues/{queue_id_for_link}/jobs/{job_id}",
@@ -226,7 +548,7 @@ pub fn AdminQueueDetailPage(props: AdminQueueDetailPageProps) -> Element {
                                                         }
                                                     }
                                                     td { class: "job-type-cell", "{job_for_row.job_type}" }
-                                                    td { class: "capitalize", "{job_for_row.priority}" }
+                                                    td { class: "capitalize priority-{job_for_row.priority}", "{job_for_row.priority}" }
                                                     td {
                                                         StatusBadge { status: status_str }
                                                     }
@@ -238,11 +560,13 @@ pub fn AdminQueueDetailPage(props: AdminQueueDetailPageProps) -> Element {
                                                                 onclick: move |_| {
                                                                     let job_id = job_for_cancel.id.to_string();
                                                                     let qid = queue_id_for_link.clone();
+                                                                    let status = Some(status_filter()).filter(|s| !s.is_empty());
                                                                     spawn(async move {
-                                                                        if let Err(e) = api::cancel_job(job_id, Some("Cancelled from admin".to_string())).await {
+                                                                        if let Err(e) = api::cancel_job(job_id, Some(CancellationReason::UserRequested)).await {
                                                                             error.set(Some(format!("Failed to cancel job: {}", e)));
-                                                                        } else if let Ok(j) = api::list_queue_jobs(qid, None, Some(100)).await {
+                                                                        } else if let Ok((j, total)) = load_jobs(qid, status).await {
                                                                             jobs.set(j);
+                                                                            total_jobs.set(total);
                                                                         }
                                                                     });
                                                                 },
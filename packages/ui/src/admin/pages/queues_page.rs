@@ -1,18 +1,33 @@
 //! Queues list page - displays all queues with stats.
 
+use api::SystemStatus;
 use dioxus::prelude::*;
-use queue_core::{Queue, QueueState};
+use queue_core::Queue;
 
-use crate::admin::StateBadge;
+use crate::admin::{CreateQueueForm, HealthDot, QueueAction, QueueActions, StateBadge};
 
 /// Refresh interval in milliseconds (5 seconds).
 const REFRESH_INTERVAL_MS: u32 = 5000;
 
+/// Format an uptime in seconds as `1d 2h`, `3h 4m` or `5m`.
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
 /// Queues list page component.
 #[component]
 pub fn AdminQueuesPage() -> Element {
     let mut queues = use_signal(Vec::<Queue>::new);
+    let mut status = use_signal(|| None::<SystemStatus>);
     let mut error = use_signal(|| None::<String>);
+    let mut show_create_form = use_signal(|| false);
 
     // Auto-refresh: fetch queues every 5 seconds
     let _refresh = use_coroutine(move |_rx: UnboundedReceiver<()>| async move {
@@ -25,6 +40,14 @@ pub fn AdminQueuesPage() -> Element {
                 }
             }
 
+            // Fetch aggregate status for the summary cards
+            match api::system_status().await {
+                Ok(s) => status.set(Some(s)),
+                Err(e) => {
+                    tracing::error!("Failed to fetch system status: {}", e);
+                }
+            }
+
             // Wait before next refresh
             #[cfg(target_arch = "wasm32")]
             gloo_timers::future::TimeoutFuture::new(REFRESH_INTERVAL_MS).await;
@@ -34,24 +57,26 @@ pub fn AdminQueuesPage() -> Element {
         }
     });
 
-    // Pause queue handler
-    let on_pause = move |queue: Queue| {
-        let queue_id = queue.id.to_string();
+    // Queue created handler
+    let on_queue_created = move |_| {
+        show_create_form.set(false);
         spawn(async move {
-            if let Err(e) = api::pause_queue(queue_id).await {
-                error.set(Some(format!("Failed to pause queue: {}", e)));
-            } else if let Ok(q) = api::list_queues().await {
+            if let Ok(q) = api::list_queues().await {
                 queues.set(q);
             }
         });
     };
 
-    // Resume queue handler
-    let on_resume = move |queue: Queue| {
+    // Lifecycle action handler (pause/resume/drain/stop/start)
+    let on_action = move |(queue, action): (Queue, QueueAction)| {
         let queue_id = queue.id.to_string();
         spawn(async move {
-            if let Err(e) = api::resume_queue(queue_id).await {
-                error.set(Some(format!("Failed to resume queue: {}", e)));
+            if let Err(e) = action.perform(queue_id).await {
+                error.set(Some(format!(
+                    "Failed to {} queue: {}",
+                    action.label().to_lowercase(),
+                    e
+                )));
             } else if let Ok(q) = api::list_queues().await {
                 queues.set(q);
             }
@@ -68,6 +93,18 @@ pub fn AdminQueuesPage() -> Element {
                 }
                 div { class: "page-header-actions",
                     span { class: "auto-refresh-indicator", "Auto-refreshing" }
+                    button {
+                        class: "btn btn-primary",
+                        onclick: move |_| show_create_form.toggle(),
+                        "+ New Queue"
+                    }
+                }
+            }
+
+            if show_create_form() {
+                CreateQueueForm {
+                    on_created: on_queue_created,
+                    on_cancel: move |_| show_create_form.set(false),
                 }
             }
 
@@ -83,28 +120,28 @@ pub fn AdminQueuesPage() -> Element {
             }
 
             // Stats summary
-            div { class: "stats-grid",
-                div { class: "stat-card",
-                    div { class: "stat-card-value", "{queues().len()}" }
-                    div { class: "stat-card-label", "Total Queues" }
-                }
-                div { class: "stat-card",
-                    div { class: "stat-card-value",
-                        {queues().iter().filter(|q| q.state == QueueState::Running).count().to_string()}
+            if let Some(s) = status() {
+                div { class: "stats-grid",
+                    div { class: "stat-card",
+                        div { class: "stat-card-value", "{s.queue_count}" }
+                        div { class: "stat-card-label", "Total Queues" }
                     }
-                    div { class: "stat-card-label", "Running" }
-                }
-                div { class: "stat-card",
-                    div { class: "stat-card-value",
-                        {queues().iter().map(|q| q.stats.pending).sum::<u64>().to_string()}
+                    div { class: "stat-card",
+                        div { class: "stat-card-value", "{s.worker_count}" }
+                        div { class: "stat-card-label", "Workers" }
                     }
-                    div { class: "stat-card-label", "Pending Jobs" }
-                }
-                div { class: "stat-card stat-card-accent",
-                    div { class: "stat-card-value",
-                        {queues().iter().map(|q| q.stats.running).sum::<u64>().to_string()}
+                    div { class: "stat-card",
+                        div { class: "stat-card-value", "{s.total_pending}" }
+                        div { class: "stat-card-label", "Pending Jobs" }
+                    }
+                    div { class: "stat-card stat-card-accent",
+                        div { class: "stat-card-value", "{s.total_running}" }
+                        div { class: "stat-card-label", "Running Jobs" }
+                    }
+                    div { class: "stat-card",
+                        div { class: "stat-card-value", {format_uptime(s.uptime_secs)} }
+                        div { class: "stat-card-label", "Uptime ({s.db_backend})" }
                     }
-                    div { class: "stat-card-label", "Running Jobs" }
                 }
             }
 
@@ -118,7 +155,7 @@ pub fn AdminQueuesPage() -> Element {
                     div { class: "empty-state",
                         div { class: "empty-state-icon", "▦" }
                         p { "No queues found" }
-                        p { class: "hint", "Queues will appear here when created" }
+                        p { class: "hint", "Use \"+ New Queue\" to create one" }
                     }
                 } else {
                     div { class: "table-container",
@@ -127,6 +164,7 @@ pub fn AdminQueuesPage() -> Element {
                                 tr {
                                     th { "Name" }
                                     th { "Status" }
+                                    th { "Health" }
                                     th { class: "text-right", "Pending" }
                                     th { class: "text-right", "Running" }
                                     th { class: "text-right", "Completed" }
@@ -138,9 +176,7 @@ pub fn AdminQueuesPage() -> Element {
                                 for queue in queues().iter() {
                                     {
                                         let queue_for_action = queue.clone();
-                                        let queue_for_pause = queue.clone();
-                                        let queue_for_resume = queue.clone();
-                                        let is_paused = queue.state == QueueState::Paused;
+                                        let queue_for_lifecycle = queue.clone();
                                         let queue_id = queue.id.to_string();
 
                                         rsx! {
@@ -160,23 +196,18 @@ pub fn AdminQueuesPage() -> Element {
                                                 td {
                                                     StateBadge { state: queue_for_action.state }
                                                 }
+                                                td {
+                                                    HealthDot { stats: queue_for_action.stats.clone() }
+                                                }
                                                 td { class: "text-right tabular-nums", "{queue_for_action.stats.pending}" }
                                                 td { class: "text-right tabular-nums", "{queue_for_action.stats.running}" }
                                                 td { class: "text-right tabular-nums", "{queue_for_action.stats.completed}" }
                                                 td { class: "text-right tabular-nums", "{queue_for_action.stats.failed}" }
                                                 td { class: "text-right",
-                                                    if is_paused {
-                                                        button {
-                                                            class: "btn btn-small btn-resume",
-                                                            onclick: move |_| on_resume(queue_for_resume.clone()),
-                                                            "Resume"
-                                                        }
-                                                    } else {
-                                                        button {
-                                                            class: "btn btn-small btn-pause",
-                                                            onclick: move |_| on_pause(queue_for_pause.clone()),
-                                                            "Pause"
-                                                        }
+                                                    QueueActions {
+                                                        state: queue_for_action.state,
+                                                        small: true,
+                                                        on_action: move |action| on_action((queue_for_lifecycle.clone(), action)),
                                                     }
                                                 }
                                             }
@@ -0,0 +1,55 @@
+//! Workers page - lists live workers per queue.
+
+use dioxus::prelude::*;
+use queue_core::WorkerInfo;
+
+use crate::admin::WorkerTable;
+
+/// Refresh interval in milliseconds (2 seconds).
+const REFRESH_INTERVAL_MS: u32 = 2000;
+
+/// Workers page component.
+#[component]
+pub fn AdminWorkersPage() -> Element {
+    let mut workers = use_signal(Vec::<WorkerInfo>::new);
+
+    // Auto-refresh: heartbeats go stale quickly, so poll more often than other pages
+    let _refresh = use_resource(move || async move {
+        loop {
+            match api::list_workers().await {
+                Ok(list) => workers.set(list),
+                Err(e) => {
+                    tracing::error!("Failed to list workers: {}", e);
+                }
+            }
+
+            // Wait before next refresh
+            #[cfg(target_arch = "wasm32")]
+            gloo_timers::future::TimeoutFuture::new(REFRESH_INTERVAL_MS).await;
+
+            #[cfg(not(target_arch = "wasm32"))]
+            tokio::time::sleep(std::time::Duration::from_millis(REFRESH_INTERVAL_MS as u64)).await;
+        }
+    });
+
+    let busy = workers().iter().filter(|w| w.is_busy()).count();
+
+    rsx! {
+        div { class: "page-container",
+            // Page header
+            div { class: "page-header",
+                div { class: "page-header-content",
+                    h1 { class: "page-title", "Workers" }
+                    p { class: "page-description", "{busy} of {workers().len()} workers busy" }
+                }
+                div { class: "page-header-actions",
+                    span { class: "auto-refresh-indicator", "Auto-refreshing" }
+                }
+            }
+
+            div { class: "card",
+                WorkerTable { workers: workers() }
+            }
+        }
+    }
+}
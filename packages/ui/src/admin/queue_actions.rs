@@ -0,0 +1,89 @@
+//! Queue lifecycle action buttons.
+
+use dioxus::prelude::*;
+use queue_core::QueueState;
+
+/// A lifecycle action an admin can take on a queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueAction {
+    Pause,
+    Resume,
+    Drain,
+    Stop,
+    Start,
+}
+
+impl QueueAction {
+    /// Actions offered for a queue in the given state, in display order.
+    pub fn available(state: QueueState) -> &'static [QueueAction] {
+        match state {
+            QueueState::Running => &[QueueAction::Pause, QueueAction::Drain, QueueAction::Stop],
+            QueueState::Paused => &[QueueAction::Resume, QueueAction::Drain, QueueAction::Stop],
+            QueueState::Draining => &[QueueAction::Stop],
+            QueueState::Stopped => &[QueueAction::Start],
+        }
+    }
+
+    /// Button label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            QueueAction::Pause => "Pause",
+            QueueAction::Resume => "Resume",
+            QueueAction::Drain => "Drain",
+            QueueAction::Stop => "Stop",
+            QueueAction::Start => "Start",
+        }
+    }
+
+    /// Button style class.
+    fn class(&self) -> &'static str {
+        match self {
+            QueueAction::Pause => "btn-pause",
+            QueueAction::Resume => "btn-resume",
+            QueueAction::Drain => "btn-drain",
+            QueueAction::Stop => "btn-stop",
+            QueueAction::Start => "btn-start",
+        }
+    }
+
+    /// Apply this action to a queue via the server.
+    pub async fn perform(self, queue_id: String) -> Result<(), ServerFnError> {
+        match self {
+            QueueAction::Pause => api::pause_queue(queue_id).await,
+            // Starting a stopped queue and resuming a paused one both return it to Running
+            QueueAction::Resume | QueueAction::Start => api::resume_queue(queue_id).await,
+            QueueAction::Drain => api::drain_queue(queue_id).await,
+            QueueAction::Stop => api::stop_queue(queue_id).await,
+        }
+    }
+}
+
+/// Props for QueueActions component.
+#[derive(Props, Clone, PartialEq)]
+pub struct QueueActionsProps {
+    /// Current state of the queue.
+    pub state: QueueState,
+    /// Use compact buttons (for table rows).
+    #[props(default = false)]
+    pub small: bool,
+    /// Callback when an action is clicked.
+    pub on_action: EventHandler<QueueAction>,
+}
+
+/// Buttons for the lifecycle actions available in a queue's current state.
+#[component]
+pub fn QueueActions(props: QueueActionsProps) -> Element {
+    let size = if props.small { " btn-small" } else { "" };
+    let on_action = props.on_action;
+
+    rsx! {
+        for action in QueueAction::available(props.state).iter().copied() {
+            button {
+                key: "{action.label()}",
+                class: "btn{size} {action.class()}",
+                onclick: move |_| on_action.call(action),
+                "{action.label()}"
+            }
+        }
+    }
+}
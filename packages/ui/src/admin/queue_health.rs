@@ -0,0 +1,59 @@
+//! Queue health indicator derived from the success rate.
+
+use dioxus::prelude::*;
+use queue_core::QueueStats;
+
+/// Success rate (percent) at or above which a queue is healthy.
+const HEALTHY_THRESHOLD: f64 = 95.0;
+
+/// Success rate (percent) at or above which a queue is degraded rather than failing.
+const DEGRADED_THRESHOLD: f64 = 80.0;
+
+/// At-a-glance health of a queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueHealth {
+    /// Nothing processed yet in this window.
+    Idle,
+    Healthy,
+    Degraded,
+    Failing,
+}
+
+impl QueueHealth {
+    /// Health band for a queue's stats.
+    pub fn from_stats(stats: &QueueStats) -> Self {
+        match stats.success_rate() {
+            None => QueueHealth::Idle,
+            Some(rate) if rate >= HEALTHY_THRESHOLD => QueueHealth::Healthy,
+            Some(rate) if rate >= DEGRADED_THRESHOLD => QueueHealth::Degraded,
+            Some(_) => QueueHealth::Failing,
+        }
+    }
+
+    /// Style class suffix.
+    fn class(&self) -> &'static str {
+        match self {
+            QueueHealth::Idle => "health-idle",
+            QueueHealth::Healthy => "health-healthy",
+            QueueHealth::Degraded => "health-degraded",
+            QueueHealth::Failing => "health-failing",
+        }
+    }
+}
+
+/// Colored dot and success rate for a queue.
+#[component]
+pub fn HealthDot(stats: QueueStats) -> Element {
+    let health = QueueHealth::from_stats(&stats);
+    let label = match stats.success_rate() {
+        Some(rate) => format!("{:.1}%", rate),
+        None => "—".to_string(),
+    };
+
+    rsx! {
+        span { class: "health-indicator {health.class()}",
+            span { class: "health-dot" }
+            span { class: "health-rate", "{label}" }
+        }
+    }
+}
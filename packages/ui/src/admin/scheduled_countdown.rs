@@ -0,0 +1,48 @@
+//! Countdown to a scheduled job's run time.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use dioxus::prelude::*;
+
+/// Format the time left before a scheduled run as `1h 02m`, `3m 04s` or `5s`.
+pub fn format_countdown(remaining: TimeDelta) -> String {
+    let secs = remaining.num_seconds().max(0);
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Live countdown until `run_at`, ticking every second.
+#[component]
+pub fn ScheduledCountdown(run_at: DateTime<Utc>) -> Element {
+    let mut now = use_signal(Utc::now);
+
+    // Tick between page refreshes so the countdown doesn't jump
+    use_future(move || async move {
+        loop {
+            #[cfg(target_arch = "wasm32")]
+            gloo_timers::future::TimeoutFuture::new(1000).await;
+
+            #[cfg(not(target_arch = "wasm32"))]
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+            now.set(Utc::now());
+        }
+    });
+
+    let remaining = run_at - now();
+
+    rsx! {
+        span { class: "scheduled-countdown tabular-nums",
+            if remaining > TimeDelta::zero() {
+                "Runs in {format_countdown(remaining)}"
+            } else {
+                "Due now"
+            }
+        }
+    }
+}
@@ -8,6 +8,7 @@ use queue_core::QueueState;
 pub fn StatusBadge(status: String) -> Element {
     let (bg_class, text) = match status.as_str() {
         "pending" => ("badge-pending", "Pending"),
+        "scheduled" => ("badge-scheduled", "Scheduled"),
         "running" => ("badge-running", "Running"),
         "completed" => ("badge-completed", "Completed"),
         "failed" => ("badge-failed", "Failed"),
@@ -24,6 +25,23 @@ pub fn StatusBadge(status: String) -> Element {
     }
 }
 
+/// Badge for displaying whether a worker is running a job.
+#[component]
+pub fn WorkerBadge(busy: bool) -> Element {
+    let (bg_class, text) = if busy {
+        ("badge-running", "Busy")
+    } else {
+        ("badge-default", "Idle")
+    };
+
+    rsx! {
+        span {
+            class: "status-badge {bg_class}",
+            {text}
+        }
+    }
+}
+
 /// Badge for displaying queue state.
 #[component]
 pub fn StateBadge(state: QueueState) -> Element {
@@ -0,0 +1,74 @@
+//! Table of live workers grouped by queue.
+
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use dioxus::prelude::*;
+use queue_core::WorkerInfo;
+
+use super::WorkerBadge;
+
+/// Workers grouped under a header row per queue.
+#[component]
+pub fn WorkerTable(workers: Vec<WorkerInfo>) -> Element {
+    if workers.is_empty() {
+        return rsx! {
+            div { class: "empty-state",
+                p { "No workers running" }
+            }
+        };
+    }
+
+    let now = Utc::now();
+    let mut groups: BTreeMap<String, Vec<WorkerInfo>> = BTreeMap::new();
+    for worker in workers {
+        groups
+            .entry(worker.queue_name.clone())
+            .or_default()
+            .push(worker);
+    }
+
+    rsx! {
+        div { class: "table-container",
+            table { class: "data-table",
+                thead {
+                    tr {
+                        th { "Worker" }
+                        th { "State" }
+                        th { "Current Job" }
+                        th { "Last Heartbeat" }
+                    }
+                }
+                tbody {
+                    for (queue_name, members) in groups {
+                        tr { class: "worker-group-row",
+                            td { colspan: "4",
+                                "{queue_name} "
+                                span { class: "worker-group-count", "({members.len()})" }
+                            }
+                        }
+                        for worker in members.iter() {
+                            {
+                                let ago = (now - worker.last_heartbeat).num_seconds().max(0);
+                                let current_job = worker
+                                    .current_job
+                                    .map_or_else(|| "—".to_string(), |id| id.to_string());
+
+                                rsx! {
+                                    tr { key: "{worker.worker_id}", class: "data-row",
+                                        td { class: "job-id", "{worker.worker_id}" }
+                                        td {
+                                            WorkerBadge { busy: worker.is_busy() }
+                                        }
+                                        td { class: "job-id", "{current_job}" }
+                                        td { class: "tabular-nums", "{ago}s ago" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Tests for selecting jobs and the bulk action toolbar.
+
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use dioxus::prelude::*;
+use queue_core::{Job, JobId, JobStatus, QueueId};
+use serde_json::json;
+use ui::admin::{BulkAction, BulkActionBar, all_selected, retain_listed, toggle_all};
+
+fn job(status: JobStatus) -> Job {
+    let mut job = Job::new(QueueId::new(), "echo", json!({}));
+    job.status = status;
+    job
+}
+
+fn failed() -> Job {
+    job(JobStatus::Failed {
+        started_at: Utc::now(),
+        failed_at: Utc::now(),
+        error: "boom".into(),
+        attempts: 3,
+    })
+}
+
+fn cancelled() -> Job {
+    job(JobStatus::Cancelled {
+        cancelled_at: Utc::now(),
+        reason: None,
+    })
+}
+
+#[component]
+fn Toolbar(selected: Vec<Job>) -> Element {
+    rsx! {
+        BulkActionBar {
+            selected,
+            on_action: move |_| {},
+            on_clear: move |_| {},
+        }
+    }
+}
+
+/// Rendered buttons as (label, disabled), in order.
+fn buttons(html: &str) -> Vec<(&str, bool)> {
+    html.split("<button")
+        .skip(1)
+        .filter_map(|button| {
+            let (attrs, rest) = button.split_once('>')?;
+            let disabled = attrs.contains("disabled") && !attrs.contains("disabled=false");
+            Some((rest.split_once("</button>")?.0, disabled))
+        })
+        .collect()
+}
+
+#[test]
+fn test_actions_apply_only_when_every_selected_job_qualifies() {
+    let pending = job(JobStatus::Pending);
+    let failed = failed();
+    let cancelled = cancelled();
+
+    assert!(BulkAction::Cancel.applies_to(&[&pending]));
+    assert!(!BulkAction::Retry.applies_to(&[&pending]));
+
+    assert!(BulkAction::Retry.applies_to(&[&failed, &cancelled]));
+    assert!(!BulkAction::Cancel.applies_to(&[&failed, &cancelled]));
+
+    // A mixed selection can neither be cancelled nor retried as a whole
+    assert!(!BulkAction::Cancel.applies_to(&[&pending, &failed]));
+    assert!(!BulkAction::Retry.applies_to(&[&pending, &failed]));
+
+    assert!(!BulkAction::Cancel.applies_to(&[]));
+    assert!(!BulkAction::Retry.applies_to(&[]));
+}
+
+#[test]
+fn test_toggle_all_selects_then_clears() {
+    let jobs = vec![job(JobStatus::Pending), failed()];
+    let mut selected = HashSet::<JobId>::new();
+    assert!(!all_selected(&selected, &jobs));
+
+    // Partially selected: toggling fills in the rest
+    selected.insert(jobs[0].id);
+    toggle_all(&mut selected, &jobs);
+    assert!(all_selected(&selected, &jobs));
+    assert_eq!(selected.len(), 2);
+
+    toggle_all(&mut selected, &jobs);
+    assert!(selected.is_empty());
+
+    assert!(!all_selected(&selected, &[]));
+}
+
+#[test]
+fn test_retain_listed_drops_jobs_no_longer_shown() {
+    let jobs = [job(JobStatus::Pending), failed()];
+    let mut selected: HashSet<JobId> = jobs.iter().map(|job| job.id).collect();
+
+    retain_listed(&mut selected, &jobs[1..]);
+    assert_eq!(selected, HashSet::from([jobs[1].id]));
+}
+
+#[test]
+fn test_toolbar_disables_actions_not_applicable_to_selection() {
+    let selected = vec![failed(), cancelled()];
+    let html = common::render_with_props(Toolbar, ToolbarProps { selected });
+    assert!(html.contains("2 selected"));
+    assert_eq!(
+        buttons(&html),
+        vec![
+            ("Cancel selected", true),
+            ("Retry selected", false),
+            ("Clear", false),
+        ]
+    );
+
+    let selected = vec![job(JobStatus::Pending), failed()];
+    let html = common::render_with_props(Toolbar, ToolbarProps { selected });
+    assert_eq!(
+        buttons(&html),
+        vec![
+            ("Cancel selected", true),
+            ("Retry selected", true),
+            ("Clear", false),
+        ]
+    );
+}
@@ -0,0 +1,14 @@
+//! Helpers shared by the UI render tests.
+
+use dioxus::dioxus_core::ComponentFunction;
+use dioxus::prelude::*;
+
+/// Render `root` with `props` to an HTML string via SSR.
+pub fn render_with_props<P: Clone + 'static, M: 'static>(
+    root: impl ComponentFunction<P, M>,
+    props: P,
+) -> String {
+    let mut dom = VirtualDom::new_with_props(root, props);
+    dom.rebuild_in_place();
+    dioxus_ssr::render(&dom)
+}
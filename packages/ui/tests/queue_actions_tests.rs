@@ -0,0 +1,60 @@
+//! Render tests for queue lifecycle controls.
+
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use dioxus::prelude::*;
+use queue_core::QueueState;
+use ui::admin::{QueueActions, StateBadge};
+
+#[component]
+fn QueueControls(state: QueueState) -> Element {
+    rsx! {
+        StateBadge { state }
+        QueueActions { state, on_action: move |_| {} }
+    }
+}
+
+/// Labels of the rendered buttons, in order.
+fn buttons(html: &str) -> Vec<&str> {
+    html.split("<button")
+        .skip(1)
+        .filter_map(|button| {
+            let text = button.split_once('>')?.1;
+            Some(text.split_once("</button>")?.0)
+        })
+        .collect()
+}
+
+#[test]
+fn test_running_queue_controls() {
+    let state = QueueState::Running;
+    let html = common::render_with_props(QueueControls, QueueControlsProps { state });
+    assert!(html.contains("badge-running"));
+    assert_eq!(buttons(&html), vec!["Pause", "Drain", "Stop"]);
+}
+
+#[test]
+fn test_paused_queue_controls() {
+    let state = QueueState::Paused;
+    let html = common::render_with_props(QueueControls, QueueControlsProps { state });
+    assert!(html.contains("badge-paused"));
+    assert_eq!(buttons(&html), vec!["Resume", "Drain", "Stop"]);
+}
+
+#[test]
+fn test_draining_queue_controls() {
+    let state = QueueState::Draining;
+    let html = common::render_with_props(QueueControls, QueueControlsProps { state });
+    assert!(html.contains("badge-draining"));
+    assert_eq!(buttons(&html), vec!["Stop"]);
+}
+
+#[test]
+fn test_stopped_queue_controls() {
+    let state = QueueState::Stopped;
+    let html = common::render_with_props(QueueControls, QueueControlsProps { state });
+    assert!(html.contains("badge-stopped"));
+    assert_eq!(buttons(&html), vec!["Start"]);
+}
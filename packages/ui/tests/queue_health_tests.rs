@@ -0,0 +1,62 @@
+//! Render tests for the queue health indicator.
+
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use dioxus::prelude::*;
+use queue_core::QueueStats;
+use ui::admin::{HealthDot, QueueHealth};
+
+#[component]
+fn QueueHealthCell(stats: QueueStats) -> Element {
+    rsx! {
+        HealthDot { stats }
+    }
+}
+
+/// Health and rendered indicator for a queue with the given outcome counts.
+fn render(completed: u64, failed: u64) -> (QueueHealth, String) {
+    let stats = QueueStats {
+        completed,
+        failed,
+        ..Default::default()
+    };
+    let health = QueueHealth::from_stats(&stats);
+    (
+        health,
+        common::render_with_props(QueueHealthCell, QueueHealthCellProps { stats }),
+    )
+}
+
+#[test]
+fn test_idle_queue_health() {
+    let (health, html) = render(0, 0);
+    assert_eq!(health, QueueHealth::Idle);
+    assert!(html.contains("health-idle"));
+    assert!(html.contains("—"));
+}
+
+#[test]
+fn test_healthy_queue_health() {
+    let (health, html) = render(99, 1);
+    assert_eq!(health, QueueHealth::Healthy);
+    assert!(html.contains("health-healthy"));
+    assert!(html.contains("99.0%"));
+}
+
+#[test]
+fn test_degraded_queue_health() {
+    let (health, html) = render(9, 1);
+    assert_eq!(health, QueueHealth::Degraded);
+    assert!(html.contains("health-degraded"));
+    assert!(html.contains("90.0%"));
+}
+
+#[test]
+fn test_failing_queue_health() {
+    let (health, html) = render(1, 1);
+    assert_eq!(health, QueueHealth::Failing);
+    assert!(html.contains("health-failing"));
+    assert!(html.contains("50.0%"));
+}
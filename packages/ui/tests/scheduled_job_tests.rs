@@ -0,0 +1,48 @@
+//! Render tests for scheduled jobs in the job detail panel.
+
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use chrono::{TimeDelta, Utc};
+use dioxus::prelude::*;
+use queue_core::{Job, QueueId};
+use serde_json::json;
+use ui::admin::{JobDetail, format_countdown};
+
+#[component]
+fn JobDetailPanel(job: Job) -> Element {
+    rsx! {
+        JobDetail {
+            job,
+            on_close: move |_| {},
+            on_cancel: move |_| {},
+            on_retry: move |_| {},
+        }
+    }
+}
+
+#[test]
+fn test_scheduled_job_shows_countdown_and_cancel() {
+    let job = Job::new(QueueId::new(), "echo", json!({}))
+        .with_run_at(Utc::now() + TimeDelta::seconds(90));
+    let html = common::render_with_props(JobDetailPanel, JobDetailPanelProps { job });
+    assert!(html.contains("scheduled-countdown"));
+    assert!(html.contains("Runs in 1m"));
+    assert!(html.contains("Cancel Before It Runs"));
+}
+
+#[test]
+fn test_ready_job_has_no_countdown() {
+    let job = Job::new(QueueId::new(), "echo", json!({}));
+    let html = common::render_with_props(JobDetailPanel, JobDetailPanelProps { job });
+    assert!(!html.contains("scheduled-countdown"));
+    assert!(html.contains("Cancel Job"));
+}
+
+#[test]
+fn test_format_countdown() {
+    assert_eq!(format_countdown(TimeDelta::seconds(5)), "5s");
+    assert_eq!(format_countdown(TimeDelta::seconds(184)), "3m 04s");
+    assert_eq!(format_countdown(TimeDelta::seconds(3720)), "1h 02m");
+}
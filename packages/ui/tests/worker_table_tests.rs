@@ -0,0 +1,53 @@
+//! Render tests for the workers table.
+
+#![allow(clippy::disallowed_methods)]
+
+mod common;
+
+use chrono::{TimeDelta, Utc};
+use dioxus::prelude::*;
+use queue_core::{JobId, QueueId, WorkerInfo};
+use ui::admin::WorkerTable;
+
+#[component]
+fn WorkersCard(workers: Vec<WorkerInfo>) -> Element {
+    rsx! {
+        WorkerTable { workers }
+    }
+}
+
+fn worker(worker_id: &str, queue_name: &str, current_job: Option<JobId>) -> WorkerInfo {
+    WorkerInfo {
+        worker_id: worker_id.to_string(),
+        queue_id: QueueId::new(),
+        queue_name: queue_name.to_string(),
+        current_job,
+        last_heartbeat: Utc::now() - TimeDelta::seconds(3),
+    }
+}
+
+#[test]
+fn test_workers_grouped_with_busy_badges() {
+    let job_id = JobId::new();
+    let workers = vec![
+        worker("worker-1", "emails", Some(job_id)),
+        worker("worker-2", "emails", None),
+        worker("worker-3", "reports", None),
+    ];
+    let html = common::render_with_props(WorkersCard, WorkersCardProps { workers });
+
+    assert!(html.contains("emails"));
+    assert!(html.contains("reports"));
+    assert_eq!(html.matches("worker-group-row").count(), 2);
+    assert!(html.contains("badge-running"));
+    assert_eq!(html.matches("Idle").count(), 2);
+    assert!(html.contains(&job_id.to_string()));
+    assert!(html.contains("3s ago"));
+}
+
+#[test]
+fn test_no_workers_shows_empty_state() {
+    let workers = Vec::new();
+    let html = common::render_with_props(WorkersCard, WorkersCardProps { workers });
+    assert!(html.contains("No workers running"));
+}
@@ -4,7 +4,9 @@
 use dioxus::prelude::*;
 
 use ui::Navbar;
-use ui::admin::{AdminJobDetailPage, AdminQueueDetailPage, AdminQueuesPage};
+use ui::admin::{
+    AdminJobDetailPage, AdminJobsPage, AdminQueueDetailPage, AdminQueuesPage, AdminWorkersPage,
+};
 use views::{Blog, Home};
 
 mod views;
@@ -29,6 +31,10 @@ enum Route {
         AdminQueueDetail { queue_id: String },
         #[route("/admin/queues/:queue_id/jobs/:job_id")]
         AdminJobDetail { queue_id: String, job_id: String },
+        #[route("/admin/jobs")]
+        AdminJobs {},
+        #[route("/admin/workers")]
+        AdminWorkers {},
 }
 
 const FAVICON: Asset = asset!("/assets/favicon.ico");
@@ -42,6 +48,11 @@ fn main() {
         tracing_subscriber::fmt()
             .with_max_level(tracing::Level::INFO)
             .init();
+
+        // Register your own job handlers and queues here; the queue system
+        // starts with them, plus any environment settings, on the first
+        // server function call.
+        api::set_init_builder(|| (api::default_handlers(), api::InitConfig::default()));
     }
 
     dioxus::launch(App);
@@ -104,6 +115,20 @@ fn AdminLayout() -> Element {
                             span { class: "nav-icon", "▦" }
                             span { "Queues" }
                         }
+                        Link {
+                            to: Route::AdminJobs {},
+                            class: "nav-link",
+                            active_class: "active",
+                            span { class: "nav-icon", "≡" }
+                            span { "Jobs" }
+                        }
+                        Link {
+                            to: Route::AdminWorkers {},
+                            class: "nav-link",
+                            active_class: "active",
+                            span { class: "nav-icon", "⚙" }
+                            span { "Workers" }
+                        }
                     }
                 }
                 div { class: "sidebar-footer",
@@ -157,3 +182,19 @@ fn AdminJobDetail(queue_id: String, job_id: String) -> Element {
         AdminJobDetailPage { queue_id, job_id }
     }
 }
+
+/// Jobs search page.
+#[component]
+fn AdminJobs() -> Element {
+    rsx! {
+        AdminJobsPage {}
+    }
+}
+
+/// Workers status page.
+#[component]
+fn AdminWorkers() -> Element {
+    rsx! {
+        AdminWorkersPage {}
+    }
+}